@@ -0,0 +1,336 @@
+use std::{fs::File, path::PathBuf};
+
+use anyhow::{bail, Result};
+use clap::{Subcommand, ValueEnum};
+use gem_bank::state::WhitelistType;
+use gem_client::{farm as ix, pda::find_protocol_config_pda};
+use gem_farm::state::{
+    FarmConfig, FixedRateConfig, FixedRateSchedule, FundingLimits, ProtocolConfig, RewardType,
+    TierConfig, VariableRateConfig,
+};
+use serde::Deserialize;
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+
+use crate::{rarity, App};
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum RewardTypeArg {
+    Variable,
+    Fixed,
+}
+
+impl From<RewardTypeArg> for RewardType {
+    fn from(arg: RewardTypeArg) -> Self {
+        match arg {
+            RewardTypeArg::Variable => RewardType::Variable,
+            RewardTypeArg::Fixed => RewardType::Fixed,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum WhitelistTypeArg {
+    Mint,
+    Creator,
+}
+
+#[derive(Subcommand)]
+pub enum FarmCommand {
+    /// creates a farm + its bank, managed by the signer
+    InitFarm {
+        #[arg(long)]
+        reward_a_mint: Pubkey,
+        #[arg(long, value_enum, default_value = "variable")]
+        reward_a_type: RewardTypeArg,
+        #[arg(long)]
+        reward_b_mint: Pubkey,
+        #[arg(long, value_enum, default_value = "variable")]
+        reward_b_type: RewardTypeArg,
+        #[arg(long, default_value_t = 0)]
+        min_staking_period_sec: u64,
+        #[arg(long, default_value_t = 0)]
+        cooldown_period_sec: u64,
+        #[arg(long, default_value_t = 0)]
+        unstaking_fee_lamp: u64,
+    },
+    /// lets a wallet fund the farm's rewards (the manager has to authorize itself too)
+    AuthorizeFunder {
+        #[arg(long)]
+        farm: Pubkey,
+        #[arg(long)]
+        funder: Pubkey,
+    },
+    /// funds a reward from the signer's token account, variable rate unless --fixed-schedule
+    FundReward {
+        #[arg(long)]
+        farm: Pubkey,
+        #[arg(long)]
+        reward_mint: Pubkey,
+        #[arg(long)]
+        reward_source: Pubkey,
+        #[arg(long)]
+        amount: u64,
+        #[arg(long)]
+        duration_sec: u64,
+        /// json file with a FixedRateSchedule
+        #[arg(long)]
+        fixed_schedule: Option<PathBuf>,
+    },
+    CancelReward {
+        #[arg(long)]
+        farm: Pubkey,
+        #[arg(long)]
+        reward_mint: Pubkey,
+        /// defaults to the signer
+        #[arg(long)]
+        receiver: Option<Pubkey>,
+    },
+    LockReward {
+        #[arg(long)]
+        farm: Pubkey,
+        #[arg(long)]
+        reward_mint: Pubkey,
+    },
+    WhitelistAdd {
+        #[arg(long)]
+        farm: Pubkey,
+        #[arg(long)]
+        address: Pubkey,
+        #[arg(long = "type", value_enum)]
+        whitelist_type: WhitelistTypeArg,
+    },
+    WhitelistRemove {
+        #[arg(long)]
+        farm: Pubkey,
+        #[arg(long)]
+        address: Pubkey,
+    },
+    /// csv (header: mint,points) or json ([{"mint": .., "points": ..}])
+    UploadRarities {
+        #[arg(long)]
+        farm: Pubkey,
+        #[arg(long)]
+        file: PathBuf,
+    },
+    TreasuryPayout {
+        #[arg(long)]
+        farm: Pubkey,
+        #[arg(long)]
+        destination: Pubkey,
+        #[arg(long)]
+        lamports: u64,
+    },
+}
+
+// serde mirrors of the schedule, the program types only derive borsh
+
+#[derive(Deserialize)]
+struct TierFile {
+    reward_rate: u64,
+    required_tenure: u64,
+}
+
+impl From<TierFile> for TierConfig {
+    fn from(tier: TierFile) -> Self {
+        Self {
+            reward_rate: tier.reward_rate,
+            required_tenure: tier.required_tenure,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct ScheduleFile {
+    base_rate: u64,
+    tier1: Option<TierFile>,
+    tier2: Option<TierFile>,
+    tier3: Option<TierFile>,
+    denominator: u64,
+}
+
+impl From<ScheduleFile> for FixedRateSchedule {
+    fn from(schedule: ScheduleFile) -> Self {
+        Self {
+            base_rate: schedule.base_rate,
+            tier1: schedule.tier1.map(Into::into),
+            tier2: schedule.tier2.map(Into::into),
+            tier3: schedule.tier3.map(Into::into),
+            denominator: schedule.denominator,
+        }
+    }
+}
+
+pub fn run(app: &App, command: FarmCommand) -> Result<()> {
+    let payer = app.payer();
+
+    match command {
+        FarmCommand::InitFarm {
+            reward_a_mint,
+            reward_a_type,
+            reward_b_mint,
+            reward_b_type,
+            min_staking_period_sec,
+            cooldown_period_sec,
+            unstaking_fee_lamp,
+        } => {
+            let farm = Keypair::new();
+            let bank = Keypair::new();
+            let fee_acc = app
+                .fetch::<ProtocolConfig>(&find_protocol_config_pda().0)?
+                .fee_wallet;
+
+            app.send(
+                &[ix::init_farm(
+                    &farm.pubkey(),
+                    &bank.pubkey(),
+                    &payer,
+                    &payer,
+                    &fee_acc,
+                    &reward_a_mint,
+                    reward_a_type.into(),
+                    &reward_b_mint,
+                    reward_b_type.into(),
+                    FarmConfig {
+                        min_staking_period_sec,
+                        cooldown_period_sec,
+                        unstaking_fee_lamp,
+                    },
+                    None,
+                )],
+                &[&farm, &bank],
+            )?;
+            println!("farm: {}\nbank: {}", farm.pubkey(), bank.pubkey());
+        }
+        FarmCommand::AuthorizeFunder { farm, funder } => app.send(
+            &[ix::authorize_funder(
+                &farm,
+                &payer,
+                &funder,
+                FundingLimits::default(),
+            )],
+            &[],
+        )?,
+        FarmCommand::FundReward {
+            farm,
+            reward_mint,
+            reward_source,
+            amount,
+            duration_sec,
+            fixed_schedule,
+        } => {
+            let (variable_rate_config, fixed_rate_config) = match fixed_schedule {
+                Some(path) => {
+                    let schedule: ScheduleFile = serde_json::from_reader(File::open(path)?)?;
+                    let config = FixedRateConfig {
+                        schedule: schedule.into(),
+                        amount,
+                        duration_sec,
+                    };
+                    (None, Some(config))
+                }
+                None => {
+                    let config = VariableRateConfig {
+                        amount,
+                        duration_sec,
+                    };
+                    (Some(config), None)
+                }
+            };
+
+            app.send(
+                &[ix::fund_reward(
+                    &farm,
+                    &payer,
+                    &reward_source,
+                    &reward_mint,
+                    variable_rate_config,
+                    fixed_rate_config,
+                )],
+                &[],
+            )?
+        }
+        FarmCommand::CancelReward {
+            farm,
+            reward_mint,
+            receiver,
+        } => app.send(
+            &[ix::cancel_reward(
+                &farm,
+                &payer,
+                &reward_mint,
+                &receiver.unwrap_or(payer),
+            )],
+            &[],
+        )?,
+        FarmCommand::LockReward { farm, reward_mint } => {
+            app.send(&[ix::lock_reward(&farm, &payer, &reward_mint)], &[])?
+        }
+        FarmCommand::WhitelistAdd {
+            farm,
+            address,
+            whitelist_type,
+        } => {
+            let bank = app.fetch::<gem_farm::state::Farm>(&farm)?.bank;
+            let whitelist_type = match whitelist_type {
+                WhitelistTypeArg::Mint => WhitelistType::MINT,
+                WhitelistTypeArg::Creator => WhitelistType::CREATOR,
+            };
+
+            app.send(
+                &[ix::add_to_bank_whitelist(
+                    &farm,
+                    &bank,
+                    &payer,
+                    &address,
+                    whitelist_type.bits(),
+                )],
+                &[],
+            )?
+        }
+        FarmCommand::WhitelistRemove { farm, address } => {
+            let bank = app.fetch::<gem_farm::state::Farm>(&farm)?.bank;
+
+            app.send(
+                &[ix::remove_from_bank_whitelist(
+                    &farm, &bank, &payer, &address,
+                )],
+                &[],
+            )?
+        }
+        FarmCommand::UploadRarities { farm, file } => {
+            let bank = app.fetch::<gem_farm::state::Farm>(&farm)?.bank;
+            let configs = rarity::load_rarities(&file)?;
+            if configs.is_empty() {
+                bail!("no rarities found in {}", file.display());
+            }
+
+            let ixs = rarity::chunk_rarities(&configs, &payer, |chunk| {
+                ix::add_rarities_to_bank(&farm, &bank, &payer, chunk)
+            })?;
+            let tx_count = ixs.len();
+            for (i, ix) in ixs.into_iter().enumerate() {
+                println!("uploading batch {}/{}", i + 1, tx_count);
+                app.send(&[ix], &[])?;
+            }
+            println!("{} rarities recorded", configs.len());
+        }
+        FarmCommand::TreasuryPayout {
+            farm,
+            destination,
+            lamports,
+        } => app.send(
+            &[ix::payout_from_treasury(
+                &farm,
+                &payer,
+                &destination,
+                lamports,
+            )],
+            &[],
+        )?,
+    }
+
+    Ok(())
+}
@@ -0,0 +1,114 @@
+//! admin cli for gem_bank + gem_farm, replaces the one-off ts scripts for ops actions
+//!
+//! defaults to a local validator and the solana cli's default keypair, eg:
+//!   gem-cli show farm <FARM>
+//!   gem-cli upload-rarities --farm <FARM> --file rarities.csv
+
+use std::path::PathBuf;
+
+use anchor_lang::AccountDeserialize;
+use anyhow::{anyhow, Context, Result};
+use clap::{Parser, Subcommand};
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Keypair, Signer},
+    transaction::Transaction,
+};
+
+use crate::rpc::Rpc;
+
+mod farm;
+mod rarity;
+mod rpc;
+mod show;
+
+#[derive(Parser)]
+#[command(name = "gem-cli", about = "manage gem farms and banks")]
+struct Cli {
+    /// json rpc url
+    #[arg(short, long, global = true, default_value = "http://127.0.0.1:8899")]
+    url: String,
+
+    /// signer + fee payer, defaults to ~/.config/solana/id.json
+    #[arg(short, long, global = true)]
+    keypair: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    #[command(flatten)]
+    Farm(farm::FarmCommand),
+
+    /// pretty-print on-chain state
+    #[command(subcommand)]
+    Show(show::ShowCommand),
+}
+
+pub struct App {
+    rpc: Rpc,
+    payer: Keypair,
+}
+
+impl App {
+    pub fn payer(&self) -> Pubkey {
+        self.payer.pubkey()
+    }
+
+    /// errors out if the account is missing or isn't a T
+    pub fn fetch<T: AccountDeserialize>(&self, address: &Pubkey) -> Result<T> {
+        let data = self
+            .rpc
+            .get_account_data(address)?
+            .ok_or_else(|| anyhow!("account {} not found", address))?;
+
+        gem_client::accounts::decode_account(&data)
+            .map_err(|e| anyhow!("failed to decode {}: {}", address, e))
+    }
+
+    /// the payer always signs, pass any other signers (eg fresh farm keypairs) in extra_signers
+    pub fn send(&self, ixs: &[Instruction], extra_signers: &[&Keypair]) -> Result<()> {
+        let mut signers = vec![&self.payer];
+        signers.extend_from_slice(extra_signers);
+
+        let tx = Transaction::new_signed_with_payer(
+            ixs,
+            Some(&self.payer()),
+            &signers,
+            self.rpc.get_latest_blockhash()?,
+        );
+        let signature = self.rpc.send_and_confirm(&tx)?;
+
+        println!("confirmed: {}", signature);
+        Ok(())
+    }
+}
+
+fn default_keypair_path() -> Result<PathBuf> {
+    let home = std::env::var("HOME").context("HOME not set, pass --keypair")?;
+    Ok(PathBuf::from(home).join(".config/solana/id.json"))
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    let keypair_path = match cli.keypair {
+        Some(path) => path,
+        None => default_keypair_path()?,
+    };
+    let payer = read_keypair_file(&keypair_path)
+        .map_err(|e| anyhow!("can't read keypair {}: {}", keypair_path.display(), e))?;
+
+    let app = App {
+        rpc: Rpc::new(cli.url),
+        payer,
+    };
+
+    match cli.command {
+        Command::Farm(command) => farm::run(&app, command),
+        Command::Show(command) => show::run(&app, command),
+    }
+}
@@ -0,0 +1,211 @@
+//! bulk rarity upload: reads (mint, points) pairs and packs them into as few txs as fit
+
+use std::{fs::File, path::Path};
+
+use anyhow::{bail, Result};
+use gem_bank::instructions::RarityConfig;
+use serde::Deserialize;
+use solana_sdk::{
+    instruction::Instruction, packet::PACKET_DATA_SIZE, pubkey::Pubkey, transaction::Transaction,
+};
+
+/// one row of the csv (header: mint,points) or one entry of the json array
+#[derive(Deserialize)]
+struct RarityRow {
+    mint: String,
+    points: u16,
+}
+
+pub fn load_rarities(path: &Path) -> Result<Vec<RarityConfig>> {
+    let rows: Vec<RarityRow> = match path.extension().and_then(|e| e.to_str()) {
+        Some("csv") => csv::Reader::from_path(path)?
+            .deserialize()
+            .collect::<std::result::Result<_, _>>()?,
+        Some("json") => serde_json::from_reader(File::open(path)?)?,
+        _ => bail!("expected a .csv or .json file, got {}", path.display()),
+    };
+
+    let mut configs: Vec<RarityConfig> = vec![];
+    for row in rows {
+        let mint: Pubkey = row.mint.parse()?;
+        // the same rarity PDA would be written twice, with whichever points come last
+        if configs.iter().any(|c| c.mint == mint) {
+            bail!("mint {} is listed more than once", mint);
+        }
+        configs.push(RarityConfig {
+            mint,
+            rarity_points: row.points,
+        });
+    }
+    Ok(configs)
+}
+
+/// as noted in record_rarity_points, tx size (not compute) is the limit - so rather than
+/// guessing a batch size, keep adding configs while the serialized tx still fits a packet
+pub fn chunk_rarities(
+    configs: &[RarityConfig],
+    payer: &Pubkey,
+    build_ix: impl Fn(Vec<RarityConfig>) -> Instruction,
+) -> Result<Vec<Instruction>> {
+    let fits = |chunk: &[RarityConfig]| -> Result<bool> {
+        let tx = Transaction::new_with_payer(&[build_ix(chunk.to_vec())], Some(payer));
+        Ok(bincode::serialized_size(&tx)? as usize <= PACKET_DATA_SIZE)
+    };
+
+    let mut ixs = vec![];
+    let mut start = 0;
+    while start < configs.len() {
+        let mut end = start + 1;
+        if !fits(&configs[start..end])? {
+            bail!("a single rarity config doesn't fit into a tx");
+        }
+        while end < configs.len() && fits(&configs[start..end + 1])? {
+            end += 1;
+        }
+
+        ixs.push(build_ix(configs[start..end].to_vec()));
+        start = end;
+    }
+
+    Ok(ixs)
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::AnchorDeserialize;
+    use gem_client::farm::add_rarities_to_bank;
+    use gem_farm::instruction::AddRaritiesToBank;
+
+    use super::*;
+
+    fn configs(count: u16) -> Vec<RarityConfig> {
+        (0..count)
+            .map(|i| RarityConfig {
+                mint: Pubkey::new_unique(),
+                rarity_points: i,
+            })
+            .collect()
+    }
+
+    fn tx_size(ix: &Instruction, payer: &Pubkey) -> usize {
+        let tx = Transaction::new_with_payer(std::slice::from_ref(ix), Some(payer));
+        bincode::serialized_size(&tx).unwrap() as usize
+    }
+
+    fn decode(ix: &Instruction) -> Vec<RarityConfig> {
+        AddRaritiesToBank::try_from_slice(&ix.data[8..])
+            .unwrap()
+            .rarity_configs
+    }
+
+    #[test]
+    fn test_chunk_rarities() {
+        let (farm, bank, payer) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let build_ix = |chunk| add_rarities_to_bank(&farm, &bank, &payer, chunk);
+        let configs = configs(100);
+
+        let ixs = chunk_rarities(&configs, &payer, build_ix).unwrap();
+        assert!(ixs.len() > 1);
+
+        let mut emitted = vec![];
+        for (i, ix) in ixs.iter().enumerate() {
+            assert!(tx_size(ix, &payer) <= PACKET_DATA_SIZE);
+
+            // every chunk but the last is as full as it gets
+            let chunk = decode(ix);
+            if i < ixs.len() - 1 {
+                let mut bigger = chunk.clone();
+                bigger.push(configs[emitted.len() + chunk.len()].clone());
+                assert!(tx_size(&build_ix(bigger), &payer) > PACKET_DATA_SIZE);
+            }
+            emitted.extend(chunk);
+        }
+
+        // each config exactly once, in order
+        assert_eq!(emitted, configs);
+    }
+
+    #[test]
+    fn test_chunk_rarities_edge_cases() {
+        let payer = Pubkey::new_unique();
+        let build_ix = |chunk| {
+            add_rarities_to_bank(&Pubkey::new_unique(), &Pubkey::new_unique(), &payer, chunk)
+        };
+
+        assert!(chunk_rarities(&[], &payer, build_ix).unwrap().is_empty());
+
+        let single = configs(1);
+        let ixs = chunk_rarities(&single, &payer, build_ix).unwrap();
+        assert_eq!(ixs.len(), 1);
+        assert_eq!(decode(&ixs[0]), single);
+
+        // nothing fits, e.g. an ix with too many accounts
+        let too_big = |chunk: Vec<RarityConfig>| {
+            let mut ix = build_ix(chunk);
+            ix.data = vec![0; PACKET_DATA_SIZE];
+            ix
+        };
+        assert!(chunk_rarities(&single, &payer, too_big).is_err());
+    }
+
+    fn write_file(name: &str, contents: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("gem_cli_{}_{}", std::process::id(), name));
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_load_rarities() {
+        let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let expected = vec![
+            RarityConfig {
+                mint: a,
+                rarity_points: 1,
+            },
+            RarityConfig {
+                mint: b,
+                rarity_points: 65535,
+            },
+        ];
+
+        let csv = write_file("ok.csv", &format!("mint,points\n{},1\n{},65535\n", a, b));
+        assert_eq!(load_rarities(&csv).unwrap(), expected);
+
+        let json = write_file(
+            "ok.json",
+            &format!(
+                r#"[{{"mint":"{}","points":1}},{{"mint":"{}","points":65535}}]"#,
+                a, b
+            ),
+        );
+        assert_eq!(load_rarities(&json).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_load_rarities_bad_rows() {
+        let a = Pubkey::new_unique();
+        let bad_csvs = [
+            "mint,points\nnot_a_mint,1\n".to_string(),
+            format!("mint,points\n{},65536\n", a),
+            format!("mint,points\n{},-1\n", a),
+            format!("mint,points\n{},\n", a),
+            format!("mint,points\n{}\n", a),
+            format!("mint\n{}\n", a),
+            format!("mint,points\n{},1\n{},2\n", a, a),
+        ];
+        for (i, csv) in bad_csvs.iter().enumerate() {
+            let path = write_file(&format!("bad_{}.csv", i), csv);
+            assert!(load_rarities(&path).is_err(), "{}", csv);
+        }
+
+        let bad_json = write_file("bad.json", &format!(r#"[{{"mint":"{}"}}]"#, a));
+        assert!(load_rarities(&bad_json).is_err());
+
+        let bad_extension = write_file("bad.txt", &format!("mint,points\n{},1\n", a));
+        assert!(load_rarities(&bad_extension).is_err());
+    }
+}
@@ -0,0 +1,92 @@
+//! the handful of json rpc calls the cli needs, enough to talk to a local validator
+
+use std::{thread::sleep, time::Duration};
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde_json::{json, Value};
+use solana_sdk::{hash::Hash, pubkey::Pubkey, signature::Signature, transaction::Transaction};
+
+const CONFIRM_RETRIES: u32 = 60;
+const CONFIRM_INTERVAL: Duration = Duration::from_millis(500);
+
+pub struct Rpc {
+    url: String,
+}
+
+impl Rpc {
+    pub fn new(url: String) -> Self {
+        Self { url }
+    }
+
+    fn call(&self, method: &str, params: Value) -> Result<Value> {
+        let response: Value = ureq::post(&self.url)
+            .send_json(json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": method,
+                "params": params,
+            }))
+            .with_context(|| format!("{} request failed", method))?
+            .into_json()?;
+
+        if let Some(error) = response.get("error") {
+            bail!("{} failed: {}", method, error);
+        }
+        response
+            .get("result")
+            .cloned()
+            .ok_or_else(|| anyhow!("{} returned no result", method))
+    }
+
+    /// None if the account doesn't exist
+    pub fn get_account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>> {
+        let result = self.call(
+            "getAccountInfo",
+            json!([address.to_string(), {"encoding": "base64", "commitment": "confirmed"}]),
+        )?;
+
+        match result["value"]["data"][0].as_str() {
+            Some(data) => Ok(Some(base64::decode(data)?)),
+            None => Ok(None),
+        }
+    }
+
+    pub fn get_latest_blockhash(&self) -> Result<Hash> {
+        let result = self.call("getLatestBlockhash", json!([{"commitment": "confirmed"}]))?;
+
+        result["value"]["blockhash"]
+            .as_str()
+            .ok_or_else(|| anyhow!("no blockhash returned"))?
+            .parse()
+            .map_err(|e| anyhow!("invalid blockhash: {:?}", e))
+    }
+
+    pub fn send_and_confirm(&self, tx: &Transaction) -> Result<Signature> {
+        let encoded = base64::encode(bincode::serialize(tx)?);
+        let signature: Signature = self
+            .call(
+                "sendTransaction",
+                json!([encoded, {"encoding": "base64", "preflightCommitment": "confirmed"}]),
+            )?
+            .as_str()
+            .ok_or_else(|| anyhow!("no signature returned"))?
+            .parse()?;
+
+        for _ in 0..CONFIRM_RETRIES {
+            let result = self.call("getSignatureStatuses", json!([[signature.to_string()]]))?;
+            let status = &result["value"][0];
+
+            if !status.is_null() {
+                if !status["err"].is_null() {
+                    bail!("tx {} failed: {}", signature, status["err"]);
+                }
+                if let Some("confirmed" | "finalized") = status["confirmationStatus"].as_str() {
+                    return Ok(signature);
+                }
+            }
+            sleep(CONFIRM_INTERVAL);
+        }
+
+        bail!("tx {} not confirmed in time", signature)
+    }
+}
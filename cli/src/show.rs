@@ -0,0 +1,51 @@
+use anyhow::Result;
+use clap::Subcommand;
+use gem_bank::state::{Bank, Vault};
+use gem_client::pda::find_farmer_pda;
+use gem_farm::state::{Farm, Farmer};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::App;
+
+#[derive(Subcommand)]
+pub enum ShowCommand {
+    Farm {
+        farm: Pubkey,
+    },
+    /// by farmer address, or by --farm + --identity
+    Farmer {
+        farmer: Option<Pubkey>,
+        #[arg(long, requires = "identity", conflicts_with = "farmer")]
+        farm: Option<Pubkey>,
+        #[arg(long, requires = "farm")]
+        identity: Option<Pubkey>,
+    },
+    Bank {
+        bank: Pubkey,
+    },
+    Vault {
+        vault: Pubkey,
+    },
+}
+
+pub fn run(app: &App, command: ShowCommand) -> Result<()> {
+    match command {
+        ShowCommand::Farm { farm } => println!("{:#?}", app.fetch::<Farm>(&farm)?),
+        ShowCommand::Farmer {
+            farmer,
+            farm,
+            identity,
+        } => {
+            let farmer = match (farmer, farm, identity) {
+                (Some(farmer), _, _) => farmer,
+                (None, Some(farm), Some(identity)) => find_farmer_pda(&farm, &identity).0,
+                _ => anyhow::bail!("pass a farmer address, or --farm and --identity"),
+            };
+            println!("{:#?}", app.fetch::<Farmer>(&farmer)?)
+        }
+        ShowCommand::Bank { bank } => println!("{:#?}", app.fetch::<Bank>(&bank)?),
+        ShowCommand::Vault { vault } => println!("{:#?}", app.fetch::<Vault>(&vault)?),
+    }
+
+    Ok(())
+}
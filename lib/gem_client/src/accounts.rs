@@ -0,0 +1,216 @@
+//! account decoders + getProgramAccounts filters, mirroring fetchAll*PDAs in the ts client
+
+use anchor_lang::{prelude::*, Discriminator};
+use gem_bank::state::{Bank, GemDepositReceipt, Rarity, Vault, WhitelistProof};
+use gem_farm::state::{AuthorizationProof, Farm, Farmer};
+
+/// checks the discriminator, then deserializes the account's data
+/// (!) only the original struct is decoded, extensions stored past it are ignored
+pub fn decode_account<T: AccountDeserialize>(data: &[u8]) -> Result<T> {
+    T::try_deserialize(&mut &data[..])
+}
+
+/// a getProgramAccounts memcmp filter, maps 1:1 onto RpcFilterType::Memcmp
+/// (kept rpc-agnostic so this crate doesn't pin a solana-client version)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MemcmpFilter {
+    pub offset: usize,
+    pub bytes: Vec<u8>,
+}
+
+impl MemcmpFilter {
+    pub fn discriminator<T: Discriminator>() -> Self {
+        Self {
+            offset: 0,
+            bytes: T::discriminator().to_vec(),
+        }
+    }
+
+    pub fn pubkey(offset: usize, key: &Pubkey) -> Self {
+        Self {
+            offset,
+            bytes: key.to_bytes().to_vec(),
+        }
+    }
+}
+
+// offsets below include the 8 bytes of anchor's discriminator
+
+fn filters<T: Discriminator>(by_key: &[(usize, Option<&Pubkey>)]) -> Vec<MemcmpFilter> {
+    let mut filters = vec![MemcmpFilter::discriminator::<T>()];
+    for (offset, key) in by_key {
+        if let Some(key) = key {
+            filters.push(MemcmpFilter::pubkey(*offset, key));
+        }
+    }
+    filters
+}
+
+// --------------------------------------- gem bank
+
+pub fn bank_filters(bank_manager: Option<&Pubkey>) -> Vec<MemcmpFilter> {
+    filters::<Bank>(&[(10, bank_manager)])
+}
+
+pub fn vault_filters(bank: Option<&Pubkey>) -> Vec<MemcmpFilter> {
+    filters::<Vault>(&[(8, bank)])
+}
+
+pub fn gdr_filters(vault: Option<&Pubkey>) -> Vec<MemcmpFilter> {
+    filters::<GemDepositReceipt>(&[(8, vault)])
+}
+
+pub fn whitelist_proof_filters(bank: Option<&Pubkey>) -> Vec<MemcmpFilter> {
+    filters::<WhitelistProof>(&[(41, bank)])
+}
+
+/// rarities don't store their bank / mint, derive the PDAs instead to narrow them down
+pub fn rarity_filters() -> Vec<MemcmpFilter> {
+    filters::<Rarity>(&[])
+}
+
+// --------------------------------------- gem farm
+
+pub fn farm_filters(farm_manager: Option<&Pubkey>) -> Vec<MemcmpFilter> {
+    filters::<Farm>(&[(10, farm_manager)])
+}
+
+pub fn farmer_filters(farm: Option<&Pubkey>, identity: Option<&Pubkey>) -> Vec<MemcmpFilter> {
+    filters::<Farmer>(&[(8, farm), (40, identity)])
+}
+
+pub fn authorization_proof_filters(
+    farm: Option<&Pubkey>,
+    funder: Option<&Pubkey>,
+) -> Vec<MemcmpFilter> {
+    filters::<AuthorizationProof>(&[(8, funder), (40, farm)])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// an account with every field zeroed, so the tests only have to set the indexed ones
+    fn zeroed<T: AccountDeserialize>() -> T {
+        T::try_deserialize_unchecked(&mut &[0u8; 10_000][..]).unwrap()
+    }
+
+    fn serialize<T: AccountSerialize>(account: &T) -> Vec<u8> {
+        let mut data = vec![];
+        account.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    fn matches(filters: &[MemcmpFilter], data: &[u8]) -> bool {
+        filters
+            .iter()
+            .all(|f| data.get(f.offset..f.offset + f.bytes.len()) == Some(f.bytes.as_slice()))
+    }
+
+    #[test]
+    fn test_bank_filters() {
+        let manager = Pubkey::new_unique();
+        let mut bank = zeroed::<Bank>();
+        bank.version = u16::MAX;
+        bank.bank_manager = manager;
+        let data = serialize(&bank);
+
+        assert!(matches(&bank_filters(Some(&manager)), &data));
+        assert!(!matches(&bank_filters(Some(&Pubkey::new_unique())), &data));
+        assert!(!matches(&vault_filters(None), &data));
+    }
+
+    #[test]
+    fn test_vault_filters() {
+        let bank = Pubkey::new_unique();
+        let mut vault = zeroed::<Vault>();
+        vault.bank = bank;
+        vault.owner = Pubkey::new_unique();
+        let data = serialize(&vault);
+
+        assert!(matches(&vault_filters(Some(&bank)), &data));
+        assert!(!matches(&vault_filters(Some(&vault.owner)), &data));
+    }
+
+    #[test]
+    fn test_gdr_filters() {
+        let vault = Pubkey::new_unique();
+        let mut gdr = zeroed::<GemDepositReceipt>();
+        gdr.vault = vault;
+        gdr.gem_box_address = Pubkey::new_unique();
+        let data = serialize(&gdr);
+
+        assert!(matches(&gdr_filters(Some(&vault)), &data));
+        assert!(!matches(&gdr_filters(Some(&gdr.gem_box_address)), &data));
+    }
+
+    #[test]
+    fn test_whitelist_proof_filters() {
+        let bank = Pubkey::new_unique();
+        let mut proof = zeroed::<WhitelistProof>();
+        proof.whitelist_type = u8::MAX;
+        proof.whitelisted_address = Pubkey::new_unique();
+        proof.bank = bank;
+        let data = serialize(&proof);
+
+        assert!(matches(&whitelist_proof_filters(Some(&bank)), &data));
+        assert!(!matches(
+            &whitelist_proof_filters(Some(&proof.whitelisted_address)),
+            &data
+        ));
+    }
+
+    #[test]
+    fn test_farm_filters() {
+        let manager = Pubkey::new_unique();
+        let mut farm = zeroed::<Farm>();
+        farm.version = u16::MAX;
+        farm.farm_manager = manager;
+        farm.farm_treasury = Pubkey::new_unique();
+        let data = serialize(&farm);
+
+        assert!(matches(&farm_filters(Some(&manager)), &data));
+        assert!(!matches(&farm_filters(Some(&farm.farm_treasury)), &data));
+    }
+
+    #[test]
+    fn test_farmer_filters() {
+        let farm = Pubkey::new_unique();
+        let identity = Pubkey::new_unique();
+        let mut farmer = zeroed::<Farmer>();
+        farmer.farm = farm;
+        farmer.identity = identity;
+        farmer.vault = Pubkey::new_unique();
+        let data = serialize(&farmer);
+
+        assert!(matches(&farmer_filters(Some(&farm), None), &data));
+        assert!(matches(&farmer_filters(None, Some(&identity)), &data));
+        assert!(matches(
+            &farmer_filters(Some(&farm), Some(&identity)),
+            &data
+        ));
+        assert!(!matches(
+            &farmer_filters(Some(&identity), Some(&farm)),
+            &data
+        ));
+    }
+
+    #[test]
+    fn test_authorization_proof_filters() {
+        let farm = Pubkey::new_unique();
+        let funder = Pubkey::new_unique();
+        let mut proof = zeroed::<AuthorizationProof>();
+        proof.authorized_funder = funder;
+        proof.farm = farm;
+        let data = serialize(&proof);
+
+        assert!(matches(
+            &authorization_proof_filters(Some(&farm), Some(&funder)),
+            &data
+        ));
+        assert!(!matches(
+            &authorization_proof_filters(Some(&funder), Some(&farm)),
+            &data
+        ));
+    }
+}
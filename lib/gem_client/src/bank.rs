@@ -0,0 +1,348 @@
+//! instruction builders for gem_bank, bumps are derived here so callers only pass keys
+
+use anchor_lang::{
+    prelude::*,
+    solana_program::{instruction::Instruction, system_program, sysvar},
+    InstructionData,
+};
+use anchor_spl::{associated_token, token};
+use gem_bank::instructions::{AuthorizationDataLocal, RarityConfig};
+use mpl_token_metadata::pda::find_metadata_account;
+
+use crate::{pda::*, pnft::*};
+
+fn instruction(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: gem_bank::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+/// whitelist proofs go in remaining accounts, in the order deposit_gem expects:
+///   mint proof, then (if the creator is known) gem metadata + creator proof
+/// missing proofs don't matter unless the bank actually has a whitelist
+pub fn whitelist_remaining_accounts(
+    bank: &Pubkey,
+    gem_mint: &Pubkey,
+    verified_creator: Option<&Pubkey>,
+) -> Vec<AccountMeta> {
+    let mut accounts = vec![AccountMeta::new_readonly(
+        find_whitelist_proof_pda(bank, gem_mint).0,
+        false,
+    )];
+
+    if let Some(creator) = verified_creator {
+        accounts.push(AccountMeta::new_readonly(
+            find_metadata_account(gem_mint).0,
+            false,
+        ));
+        accounts.push(AccountMeta::new_readonly(
+            find_whitelist_proof_pda(bank, creator).0,
+            false,
+        ));
+    }
+
+    accounts
+}
+
+pub fn init_vault(
+    bank: &Pubkey,
+    creator: &Pubkey,
+    payer: &Pubkey,
+    owner: Pubkey,
+    name: String,
+) -> (Pubkey, Instruction) {
+    let vault = find_vault_pda(bank, creator).0;
+
+    let ix = instruction(
+        gem_bank::accounts::InitVault {
+            bank: *bank,
+            vault,
+            creator: *creator,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        gem_bank::instruction::InitVault { owner, name },
+    );
+
+    (vault, ix)
+}
+
+pub fn deposit_gem(
+    bank: &Pubkey,
+    vault: &Pubkey,
+    owner: &Pubkey,
+    gem_source: &Pubkey,
+    gem_mint: &Pubkey,
+    verified_creator: Option<&Pubkey>,
+    amount: u64,
+) -> Instruction {
+    let (authority, bump_auth) = find_vault_authority_pda(vault);
+    let (gem_rarity, bump_rarity) = find_rarity_pda(bank, gem_mint);
+
+    let mut ix = instruction(
+        gem_bank::accounts::DepositGem {
+            bank: *bank,
+            vault: *vault,
+            owner: *owner,
+            authority,
+            gem_box: find_gem_box_pda(vault, gem_mint).0,
+            gem_deposit_receipt: find_gdr_pda(vault, gem_mint).0,
+            gem_source: *gem_source,
+            gem_mint: *gem_mint,
+            gem_rarity,
+            token_program: token::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        gem_bank::instruction::DepositGem {
+            _bump_auth: bump_auth,
+            _bump_rarity: bump_rarity,
+            amount,
+        },
+    );
+    ix.accounts.extend(whitelist_remaining_accounts(
+        bank,
+        gem_mint,
+        verified_creator,
+    ));
+
+    ix
+}
+
+pub fn withdraw_gem(
+    bank: &Pubkey,
+    vault: &Pubkey,
+    owner: &Pubkey,
+    gem_destination: &Pubkey,
+    gem_mint: &Pubkey,
+    receiver: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let (authority, bump_auth) = find_vault_authority_pda(vault);
+    let (gem_box, bump_gem_box) = find_gem_box_pda(vault, gem_mint);
+    let (gem_deposit_receipt, bump_gdr) = find_gdr_pda(vault, gem_mint);
+    let (gem_rarity, bump_rarity) = find_rarity_pda(bank, gem_mint);
+
+    instruction(
+        gem_bank::accounts::WithdrawGem {
+            bank: *bank,
+            vault: *vault,
+            owner: *owner,
+            authority,
+            gem_box,
+            gem_deposit_receipt,
+            gem_destination: *gem_destination,
+            gem_mint: *gem_mint,
+            gem_rarity,
+            receiver: *receiver,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        gem_bank::instruction::WithdrawGem {
+            _bump_auth: bump_auth,
+            _bump_gem_box: bump_gem_box,
+            _bump_gdr: bump_gdr,
+            _bump_rarity: bump_rarity,
+            amount,
+        },
+    )
+}
+
+/// rules = the pNFT's rule set (metadata.programmable_config), if any
+#[allow(clippy::too_many_arguments)]
+pub fn deposit_gem_pnft(
+    bank: &Pubkey,
+    vault: &Pubkey,
+    owner: &Pubkey,
+    gem_source: &Pubkey,
+    gem_mint: &Pubkey,
+    verified_creator: Option<&Pubkey>,
+    rules: Option<&Pubkey>,
+    authorization_data: Option<AuthorizationDataLocal>,
+    amount: u64,
+) -> Instruction {
+    let (authority, bump_auth) = find_vault_authority_pda(vault);
+    let (gem_rarity, bump_rarity) = find_rarity_pda(bank, gem_mint);
+    let gem_box = find_gem_box_pda(vault, gem_mint).0;
+    let pnft = PnftTransferAccounts::new(gem_mint, gem_source, &gem_box);
+
+    let mut ix = instruction(
+        gem_bank::accounts::DepositGemPnft {
+            bank: *bank,
+            vault: *vault,
+            owner: *owner,
+            authority,
+            gem_box,
+            gem_deposit_receipt: find_gdr_pda(vault, gem_mint).0,
+            gem_source: *gem_source,
+            gem_mint: *gem_mint,
+            gem_rarity,
+            token_program: token::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+            associated_token_program: associated_token::ID,
+            gem_metadata: pnft.metadata,
+            gem_edition: pnft.edition,
+            owner_token_record: pnft.source_token_record,
+            dest_token_record: pnft.dest_token_record,
+            pnft_shared: prog_nft_shared(),
+        },
+        gem_bank::instruction::DepositGemPnft {
+            _bump_auth: bump_auth,
+            _bump_rarity: bump_rarity,
+            amount,
+            authorization_data,
+            rules_acc_present: rules.is_some(),
+        },
+    );
+
+    // rules go first, then whitelist proofs (metadata is a fixed account here)
+    if let Some(rules) = rules {
+        ix.accounts.push(AccountMeta::new_readonly(*rules, false));
+    }
+    ix.accounts.push(AccountMeta::new_readonly(
+        find_whitelist_proof_pda(bank, gem_mint).0,
+        false,
+    ));
+    if let Some(creator) = verified_creator {
+        ix.accounts.push(AccountMeta::new_readonly(
+            find_whitelist_proof_pda(bank, creator).0,
+            false,
+        ));
+    }
+
+    ix
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn withdraw_gem_pnft(
+    bank: &Pubkey,
+    vault: &Pubkey,
+    owner: &Pubkey,
+    gem_destination: &Pubkey,
+    gem_mint: &Pubkey,
+    receiver: &Pubkey,
+    rules: Option<&Pubkey>,
+    authorization_data: Option<AuthorizationDataLocal>,
+    amount: u64,
+) -> Instruction {
+    let (authority, bump_auth) = find_vault_authority_pda(vault);
+    let (gem_box, bump_gem_box) = find_gem_box_pda(vault, gem_mint);
+    let (gem_deposit_receipt, bump_gdr) = find_gdr_pda(vault, gem_mint);
+    let (gem_rarity, bump_rarity) = find_rarity_pda(bank, gem_mint);
+    let pnft = PnftTransferAccounts::new(gem_mint, &gem_box, gem_destination);
+
+    let mut ix = instruction(
+        gem_bank::accounts::WithdrawGemPnft {
+            bank: *bank,
+            vault: *vault,
+            owner: *owner,
+            authority,
+            gem_box,
+            gem_deposit_receipt,
+            gem_destination: *gem_destination,
+            gem_mint: *gem_mint,
+            gem_rarity,
+            receiver: *receiver,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+            gem_metadata: pnft.metadata,
+            gem_edition: pnft.edition,
+            owner_token_record: pnft.source_token_record,
+            dest_token_record: pnft.dest_token_record,
+            pnft_shared: prog_nft_shared(),
+        },
+        gem_bank::instruction::WithdrawGemPnft {
+            _bump_auth: bump_auth,
+            _bump_gem_box: bump_gem_box,
+            _bump_gdr: bump_gdr,
+            _bump_rarity: bump_rarity,
+            amount,
+            authorization_data,
+            rules_acc_present: rules.is_some(),
+        },
+    );
+
+    if let Some(rules) = rules {
+        ix.accounts.push(AccountMeta::new_readonly(*rules, false));
+    }
+
+    ix
+}
+
+pub fn add_to_whitelist(
+    bank: &Pubkey,
+    bank_manager: &Pubkey,
+    address_to_whitelist: &Pubkey,
+    payer: &Pubkey,
+    whitelist_type: u8,
+) -> Instruction {
+    instruction(
+        gem_bank::accounts::AddToWhitelist {
+            bank: *bank,
+            bank_manager: *bank_manager,
+            address_to_whitelist: *address_to_whitelist,
+            whitelist_proof: find_whitelist_proof_pda(bank, address_to_whitelist).0,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        gem_bank::instruction::AddToWhitelist { whitelist_type },
+    )
+}
+
+pub fn remove_from_whitelist(
+    bank: &Pubkey,
+    bank_manager: &Pubkey,
+    address_to_remove: &Pubkey,
+    funds_receiver: &Pubkey,
+) -> Instruction {
+    let (whitelist_proof, bump) = find_whitelist_proof_pda(bank, address_to_remove);
+
+    instruction(
+        gem_bank::accounts::RemoveFromWhitelist {
+            bank: *bank,
+            bank_manager: *bank_manager,
+            funds_receiver: *funds_receiver,
+            address_to_remove: *address_to_remove,
+            whitelist_proof,
+        },
+        gem_bank::instruction::RemoveFromWhitelist { _bump: bump },
+    )
+}
+
+/// each config's mint + rarity PDA go in remaining accounts, keep batches small to fit tx size
+pub fn record_rarity_points(
+    bank: &Pubkey,
+    bank_manager: &Pubkey,
+    payer: &Pubkey,
+    rarity_configs: Vec<RarityConfig>,
+) -> Instruction {
+    let mut ix = instruction(
+        gem_bank::accounts::RecordRarityPoints {
+            bank: *bank,
+            bank_manager: *bank_manager,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        gem_bank::instruction::RecordRarityPoints {
+            rarity_configs: rarity_configs.clone(),
+        },
+    );
+
+    for config in rarity_configs {
+        ix.accounts
+            .push(AccountMeta::new_readonly(config.mint, false));
+        ix.accounts.push(AccountMeta::new(
+            find_rarity_pda(bank, &config.mint).0,
+            false,
+        ));
+    }
+
+    ix
+}
@@ -0,0 +1,1175 @@
+//! instruction builders for gem_farm, bumps are derived here so callers only pass keys
+
+use anchor_lang::{
+    prelude::*,
+    solana_program::{bpf_loader_upgradeable, instruction::Instruction, system_program, sysvar},
+    InstructionData,
+};
+use anchor_spl::{associated_token, associated_token::get_associated_token_address, token};
+use gem_bank::instructions::RarityConfig;
+use gem_farm::instructions::FlashWithdrawBumps;
+use gem_farm::state::{
+    FarmConfig, FeeTable, FixedRateConfig, FundingLimits, MaxCounts, RewardType, VariableRateConfig,
+};
+
+use crate::{bank::whitelist_remaining_accounts, pda::*};
+
+fn instruction(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: gem_farm::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+/// the farm's token fee (see TokenFees), only needed if the farm charges one for the ix
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TokenFeeAccounts {
+    pub fee_mint: Pubkey,
+    /// payer's token account for fee_mint
+    pub fee_source: Pubkey,
+}
+
+impl TokenFeeAccounts {
+    /// in the order collect_token_fee expects them
+    pub fn remaining_accounts(&self, farm: &Pubkey) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new(self.fee_source, false),
+            AccountMeta::new(find_token_treasury_pda(farm, &self.fee_mint).0, false),
+            AccountMeta::new_readonly(token::ID, false),
+        ]
+    }
+}
+
+fn push_token_fee(ix: &mut Instruction, farm: &Pubkey, token_fee: Option<&TokenFeeAccounts>) {
+    if let Some(token_fee) = token_fee {
+        ix.accounts.extend(token_fee.remaining_accounts(farm));
+    }
+}
+
+/// fee_acc = protocol_config.fee_wallet
+pub fn init_farmer(
+    farm: &Pubkey,
+    bank: &Pubkey,
+    identity: &Pubkey,
+    payer: &Pubkey,
+    fee_acc: &Pubkey,
+) -> Instruction {
+    instruction(
+        gem_farm::accounts::InitFarmer {
+            farm: *farm,
+            farmer: find_farmer_pda(farm, identity).0,
+            identity: *identity,
+            bank: *bank,
+            vault: find_vault_pda(bank, identity).0,
+            gem_bank: gem_bank::ID,
+            payer: *payer,
+            protocol_config: find_protocol_config_pda().0,
+            fee_acc: *fee_acc,
+            fee_exemption: find_fee_exemption_pda(farm).0,
+            system_program: system_program::ID,
+        },
+        gem_farm::instruction::InitFarmer {},
+    )
+}
+
+/// vault = farmer.vault - not derived, a migrated farmer keeps the vault from its first bank
+pub fn stake(
+    farm: &Pubkey,
+    bank: &Pubkey,
+    identity: &Pubkey,
+    vault: &Pubkey,
+    fee_acc: &Pubkey,
+    token_fee: Option<&TokenFeeAccounts>,
+) -> Instruction {
+    let (farm_authority, bump_auth) = find_farm_authority_pda(farm);
+    let (farmer, bump_farmer) = find_farmer_pda(farm, identity);
+
+    let mut ix = instruction(
+        gem_farm::accounts::Stake {
+            farm: *farm,
+            farm_authority,
+            farmer,
+            identity: *identity,
+            bank: *bank,
+            vault: *vault,
+            gem_bank: gem_bank::ID,
+            protocol_config: find_protocol_config_pda().0,
+            fee_acc: *fee_acc,
+            fee_exemption: find_fee_exemption_pda(farm).0,
+            system_program: system_program::ID,
+        },
+        gem_farm::instruction::Stake {
+            _bump_auth: bump_auth,
+            _bump_farmer: bump_farmer,
+        },
+    );
+    push_token_fee(&mut ix, farm, token_fee);
+
+    ix
+}
+
+/// vault = farmer.vault, see stake
+pub fn unstake(
+    farm: &Pubkey,
+    bank: &Pubkey,
+    identity: &Pubkey,
+    vault: &Pubkey,
+    fee_acc: &Pubkey,
+    token_fee: Option<&TokenFeeAccounts>,
+    skip_rewards: bool,
+) -> Instruction {
+    let (farm_authority, bump_auth) = find_farm_authority_pda(farm);
+    let (farm_treasury, bump_treasury) = find_farm_treasury_pda(farm);
+    let (farmer, bump_farmer) = find_farmer_pda(farm, identity);
+
+    let mut ix = instruction(
+        gem_farm::accounts::Unstake {
+            farm: *farm,
+            farm_authority,
+            farm_treasury,
+            farmer,
+            identity: *identity,
+            bank: *bank,
+            vault: *vault,
+            gem_bank: gem_bank::ID,
+            protocol_config: find_protocol_config_pda().0,
+            fee_acc: *fee_acc,
+            fee_exemption: find_fee_exemption_pda(farm).0,
+            system_program: system_program::ID,
+        },
+        gem_farm::instruction::Unstake {
+            _bump_auth: bump_auth,
+            _bump_treasury: bump_treasury,
+            _bump_farmer: bump_farmer,
+            skip_rewards,
+        },
+    );
+    push_token_fee(&mut ix, farm, token_fee);
+
+    ix
+}
+
+/// vault = farmer.vault, see stake
+pub fn instant_unstake(
+    farm: &Pubkey,
+    bank: &Pubkey,
+    identity: &Pubkey,
+    vault: &Pubkey,
+    fee_acc: &Pubkey,
+    token_fee: Option<&TokenFeeAccounts>,
+) -> Instruction {
+    let (farm_authority, bump_auth) = find_farm_authority_pda(farm);
+    let (farm_treasury, bump_treasury) = find_farm_treasury_pda(farm);
+    let (farmer, bump_farmer) = find_farmer_pda(farm, identity);
+
+    let mut ix = instruction(
+        gem_farm::accounts::InstantUnstake {
+            farm: *farm,
+            farm_authority,
+            farm_treasury,
+            farmer,
+            identity: *identity,
+            bank: *bank,
+            vault: *vault,
+            gem_bank: gem_bank::ID,
+            protocol_config: find_protocol_config_pda().0,
+            fee_acc: *fee_acc,
+            fee_exemption: find_fee_exemption_pda(farm).0,
+            system_program: system_program::ID,
+        },
+        gem_farm::instruction::InstantUnstake {
+            _bump_auth: bump_auth,
+            _bump_treasury: bump_treasury,
+            _bump_farmer: bump_farmer,
+        },
+    );
+    push_token_fee(&mut ix, farm, token_fee);
+
+    ix
+}
+
+/// rewards are paid into the identity's ATAs, created if missing
+pub fn claim(
+    farm: &Pubkey,
+    identity: &Pubkey,
+    reward_a_mint: &Pubkey,
+    reward_b_mint: &Pubkey,
+) -> Instruction {
+    let (farm_authority, bump_auth) = find_farm_authority_pda(farm);
+    let (farmer, bump_farmer) = find_farmer_pda(farm, identity);
+    let (reward_a_pot, bump_pot_a) = find_reward_pot_pda(farm, reward_a_mint);
+    let (reward_b_pot, bump_pot_b) = find_reward_pot_pda(farm, reward_b_mint);
+
+    instruction(
+        gem_farm::accounts::Claim {
+            farm: *farm,
+            farm_authority,
+            farmer,
+            identity: *identity,
+            reward_a_pot,
+            reward_a_mint: *reward_a_mint,
+            reward_a_destination: get_associated_token_address(identity, reward_a_mint),
+            reward_b_pot,
+            reward_b_mint: *reward_b_mint,
+            reward_b_destination: get_associated_token_address(identity, reward_b_mint),
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        gem_farm::instruction::Claim {
+            _bump_auth: bump_auth,
+            _bump_farmer: bump_farmer,
+            _bump_pot_a: bump_pot_a,
+            _bump_pot_b: bump_pot_b,
+        },
+    )
+}
+
+/// authority = the identity or its claim delegate; destinations = farmer.reward_*.claim_destination,
+/// None if not set (the identity's ATAs, which have to exist)
+pub fn claim_delegated(
+    farm: &Pubkey,
+    identity: &Pubkey,
+    authority: &Pubkey,
+    reward_a_mint: &Pubkey,
+    reward_b_mint: &Pubkey,
+    reward_a_destination: Option<&Pubkey>,
+    reward_b_destination: Option<&Pubkey>,
+) -> Instruction {
+    let (farm_authority, bump_auth) = find_farm_authority_pda(farm);
+    let (farmer, bump_farmer) = find_farmer_pda(farm, identity);
+    let (reward_a_pot, bump_pot_a) = find_reward_pot_pda(farm, reward_a_mint);
+    let (reward_b_pot, bump_pot_b) = find_reward_pot_pda(farm, reward_b_mint);
+
+    instruction(
+        gem_farm::accounts::ClaimDelegated {
+            farm: *farm,
+            farm_authority,
+            farmer,
+            identity: *identity,
+            authority: *authority,
+            reward_a_pot,
+            reward_a_mint: *reward_a_mint,
+            reward_a_destination: reward_a_destination
+                .copied()
+                .unwrap_or_else(|| get_associated_token_address(identity, reward_a_mint)),
+            reward_b_pot,
+            reward_b_mint: *reward_b_mint,
+            reward_b_destination: reward_b_destination
+                .copied()
+                .unwrap_or_else(|| get_associated_token_address(identity, reward_b_mint)),
+            token_program: token::ID,
+        },
+        gem_farm::instruction::ClaimDelegated {
+            _bump_auth: bump_auth,
+            _bump_farmer: bump_farmer,
+            _bump_pot_a: bump_pot_a,
+            _bump_pot_b: bump_pot_b,
+        },
+    )
+}
+
+/// rewards go to the identity's ATAs and the tip to the cranker's, all of them have to exist
+pub fn crank_claim(
+    farm: &Pubkey,
+    identity: &Pubkey,
+    cranker: &Pubkey,
+    reward_a_mint: &Pubkey,
+    reward_b_mint: &Pubkey,
+) -> Instruction {
+    let (farm_authority, bump_auth) = find_farm_authority_pda(farm);
+    let (farmer, bump_farmer) = find_farmer_pda(farm, identity);
+    let (reward_a_pot, bump_pot_a) = find_reward_pot_pda(farm, reward_a_mint);
+    let (reward_b_pot, bump_pot_b) = find_reward_pot_pda(farm, reward_b_mint);
+
+    instruction(
+        gem_farm::accounts::CrankClaim {
+            farm: *farm,
+            farm_authority,
+            farmer,
+            identity: *identity,
+            cranker: *cranker,
+            reward_a_pot,
+            reward_a_mint: *reward_a_mint,
+            reward_a_destination: get_associated_token_address(identity, reward_a_mint),
+            reward_a_tip_destination: get_associated_token_address(cranker, reward_a_mint),
+            reward_b_pot,
+            reward_b_mint: *reward_b_mint,
+            reward_b_destination: get_associated_token_address(identity, reward_b_mint),
+            reward_b_tip_destination: get_associated_token_address(cranker, reward_b_mint),
+            token_program: token::ID,
+        },
+        gem_farm::instruction::CrankClaim {
+            _bump_auth: bump_auth,
+            _bump_farmer: bump_farmer,
+            _bump_pot_a: bump_pot_a,
+            _bump_pot_b: bump_pot_b,
+        },
+    )
+}
+
+/// vault = farmer.vault, see stake
+#[allow(clippy::too_many_arguments)]
+pub fn flash_deposit(
+    farm: &Pubkey,
+    bank: &Pubkey,
+    identity: &Pubkey,
+    vault: &Pubkey,
+    gem_source: &Pubkey,
+    gem_mint: &Pubkey,
+    verified_creator: Option<&Pubkey>,
+    fee_acc: &Pubkey,
+    token_fee: Option<&TokenFeeAccounts>,
+    amount: u64,
+) -> Instruction {
+    let (farmer, bump_farmer) = find_farmer_pda(farm, identity);
+    let vault = *vault;
+    let (vault_authority, bump_vault_auth) = find_vault_authority_pda(&vault);
+    let (gem_rarity, bump_rarity) = find_rarity_pda(bank, gem_mint);
+
+    let mut ix = instruction(
+        gem_farm::accounts::FlashDeposit {
+            farm: *farm,
+            farm_authority: find_farm_authority_pda(farm).0,
+            farmer,
+            identity: *identity,
+            bank: *bank,
+            vault,
+            vault_authority,
+            gem_box: find_gem_box_pda(&vault, gem_mint).0,
+            gem_deposit_receipt: find_gdr_pda(&vault, gem_mint).0,
+            gem_source: *gem_source,
+            gem_mint: *gem_mint,
+            gem_rarity,
+            token_program: token::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+            gem_bank: gem_bank::ID,
+            protocol_config: find_protocol_config_pda().0,
+            fee_acc: *fee_acc,
+            fee_exemption: find_fee_exemption_pda(farm).0,
+        },
+        gem_farm::instruction::FlashDeposit {
+            _bump_farmer: bump_farmer,
+            bump_vault_auth,
+            bump_rarity,
+            amount,
+        },
+    );
+    ix.accounts.extend(whitelist_remaining_accounts(
+        bank,
+        gem_mint,
+        verified_creator,
+    ));
+    // after the whitelist proofs, see split_token_fee_accounts
+    push_token_fee(&mut ix, farm, token_fee);
+
+    ix
+}
+
+/// vault = farmer.vault, see stake
+#[allow(clippy::too_many_arguments)]
+pub fn begin_flash_withdraw(
+    farm: &Pubkey,
+    bank: &Pubkey,
+    identity: &Pubkey,
+    vault: &Pubkey,
+    gem_mint: &Pubkey,
+    fee_acc: &Pubkey,
+    token_fee: Option<&TokenFeeAccounts>,
+    amount: u64,
+) -> Instruction {
+    let (farm_treasury, bump_treasury) = find_farm_treasury_pda(farm);
+    let (farmer, bump_farmer) = find_farmer_pda(farm, identity);
+    let (gem_rarity, bump_rarity) = find_rarity_pda(bank, gem_mint);
+
+    let mut ix = instruction(
+        gem_farm::accounts::BeginFlashWithdraw {
+            farm: *farm,
+            farm_treasury,
+            farmer,
+            identity: *identity,
+            bank: *bank,
+            vault: *vault,
+            gem_deposit_receipt: find_gdr_pda(vault, gem_mint).0,
+            gem_mint: *gem_mint,
+            gem_rarity,
+            gem_bank: gem_bank::ID,
+            protocol_config: find_protocol_config_pda().0,
+            fee_acc: *fee_acc,
+            fee_exemption: find_fee_exemption_pda(farm).0,
+            system_program: system_program::ID,
+        },
+        gem_farm::instruction::BeginFlashWithdraw {
+            _bump_treasury: bump_treasury,
+            _bump_farmer: bump_farmer,
+            _bump_rarity: bump_rarity,
+            amount,
+        },
+    );
+    push_token_fee(&mut ix, farm, token_fee);
+
+    ix
+}
+
+/// vault = farmer.vault, see stake; gems go to the identity's ATA, created if missing
+pub fn flash_withdraw(
+    farm: &Pubkey,
+    bank: &Pubkey,
+    identity: &Pubkey,
+    vault: &Pubkey,
+    gem_mint: &Pubkey,
+) -> Instruction {
+    let (farmer, bump_farmer) = find_farmer_pda(farm, identity);
+    let (vault_authority, vault_auth) = find_vault_authority_pda(vault);
+    let (gem_box, bump_gem_box) = find_gem_box_pda(vault, gem_mint);
+    let (gem_deposit_receipt, gdr) = find_gdr_pda(vault, gem_mint);
+    let (gem_rarity, rarity) = find_rarity_pda(bank, gem_mint);
+
+    instruction(
+        gem_farm::accounts::FlashWithdraw {
+            farm: *farm,
+            farm_authority: find_farm_authority_pda(farm).0,
+            farmer,
+            identity: *identity,
+            bank: *bank,
+            vault: *vault,
+            vault_authority,
+            gem_box,
+            gem_deposit_receipt,
+            gem_destination: get_associated_token_address(identity, gem_mint),
+            gem_mint: *gem_mint,
+            gem_rarity,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+            gem_bank: gem_bank::ID,
+        },
+        gem_farm::instruction::FlashWithdraw {
+            _bump_farmer: bump_farmer,
+            bumps: FlashWithdrawBumps {
+                vault_auth,
+                gem_box: bump_gem_box,
+                gdr,
+                rarity,
+            },
+        },
+    )
+}
+
+/// vault = from farmer.vault, see stake; gem_mints = every mint in the vault, rarities are read
+/// from to_bank; token_fee = to_farm's stake fee, paid by the authority
+#[allow(clippy::too_many_arguments)]
+pub fn migrate_farmer(
+    from_farm: &Pubkey,
+    from_bank: &Pubkey,
+    to_farm: &Pubkey,
+    to_bank: &Pubkey,
+    identity: &Pubkey,
+    authority: &Pubkey,
+    vault: &Pubkey,
+    gem_mints: &[Pubkey],
+    fee_acc: &Pubkey,
+    token_fee: Option<&TokenFeeAccounts>,
+) -> Instruction {
+    let (from_farm_authority, bump_from_auth) = find_farm_authority_pda(from_farm);
+    let (to_farm_authority, bump_to_auth) = find_farm_authority_pda(to_farm);
+    let (from_farmer, bump_from_farmer) = find_farmer_pda(from_farm, identity);
+    let (migration_proof, bump_proof) = find_migration_proof_pda(from_farm, to_farm);
+
+    let mut ix = instruction(
+        gem_farm::accounts::MigrateFarmer {
+            from_farm: *from_farm,
+            from_farm_authority,
+            from_farmer,
+            to_farm: *to_farm,
+            to_farm_authority,
+            to_farmer: find_farmer_pda(to_farm, identity).0,
+            migration_proof,
+            identity: *identity,
+            authority: *authority,
+            from_bank: *from_bank,
+            to_bank: *to_bank,
+            vault: *vault,
+            gem_bank: gem_bank::ID,
+            protocol_config: find_protocol_config_pda().0,
+            fee_acc: *fee_acc,
+            fee_exemption: find_fee_exemption_pda(to_farm).0,
+            system_program: system_program::ID,
+        },
+        gem_farm::instruction::MigrateFarmer {
+            _bump_from_auth: bump_from_auth,
+            _bump_to_auth: bump_to_auth,
+            _bump_from_farmer: bump_from_farmer,
+            _bump_proof: bump_proof,
+        },
+    );
+    for gem_mint in gem_mints {
+        ix.accounts.extend([
+            AccountMeta::new_readonly(find_gdr_pda(vault, gem_mint).0, false),
+            AccountMeta::new_readonly(find_rarity_pda(to_bank, gem_mint).0, false),
+        ]);
+    }
+    push_token_fee(&mut ix, to_farm, token_fee);
+
+    ix
+}
+
+/// vault = farmer.vault, see stake - closed too, unless it was closed or moved banks already
+pub fn close_farmer(
+    farm: &Pubkey,
+    bank: &Pubkey,
+    identity: &Pubkey,
+    vault: &Pubkey,
+) -> Instruction {
+    let (farmer, bump_farmer) = find_farmer_pda(farm, identity);
+
+    instruction(
+        gem_farm::accounts::CloseFarmer {
+            farm: *farm,
+            farmer,
+            identity: *identity,
+            bank: *bank,
+            vault: *vault,
+            gem_bank: gem_bank::ID,
+        },
+        gem_farm::instruction::CloseFarmer {
+            _bump_farmer: bump_farmer,
+        },
+    )
+}
+
+pub fn refresh_farmer(farm: &Pubkey, identity: &Pubkey) -> Instruction {
+    let (farmer, bump) = find_farmer_pda(farm, identity);
+
+    instruction(
+        gem_farm::accounts::RefreshFarmer {
+            farm: *farm,
+            farmer,
+            identity: *identity,
+        },
+        gem_farm::instruction::RefreshFarmer { _bump: bump },
+    )
+}
+
+pub fn authorize_funder(
+    farm: &Pubkey,
+    farm_manager: &Pubkey,
+    funder_to_authorize: &Pubkey,
+    limits: FundingLimits,
+) -> Instruction {
+    instruction(
+        gem_farm::accounts::AuthorizeFunder {
+            farm: *farm,
+            farm_manager: *farm_manager,
+            funder_to_authorize: *funder_to_authorize,
+            authorization_proof: find_authorization_proof_pda(farm, funder_to_authorize).0,
+            system_program: system_program::ID,
+        },
+        gem_farm::instruction::AuthorizeFunder { limits },
+    )
+}
+
+pub fn fund_reward(
+    farm: &Pubkey,
+    authorized_funder: &Pubkey,
+    reward_source: &Pubkey,
+    reward_mint: &Pubkey,
+    variable_rate_config: Option<VariableRateConfig>,
+    fixed_rate_config: Option<FixedRateConfig>,
+) -> Instruction {
+    let (authorization_proof, bump_proof) = find_authorization_proof_pda(farm, authorized_funder);
+    let (reward_pot, bump_pot) = find_reward_pot_pda(farm, reward_mint);
+
+    instruction(
+        gem_farm::accounts::FundReward {
+            farm: *farm,
+            authorization_proof,
+            authorized_funder: *authorized_funder,
+            reward_pot,
+            reward_source: *reward_source,
+            reward_mint: *reward_mint,
+            token_program: token::ID,
+            system_program: system_program::ID,
+        },
+        gem_farm::instruction::FundReward {
+            _bump_proof: bump_proof,
+            _bump_pot: bump_pot,
+            variable_rate_config,
+            fixed_rate_config,
+        },
+    )
+}
+
+/// simulate it and decode the return data as FarmerRewardsQuote
+pub fn quote_farmer_rewards(farm: &Pubkey, identity: &Pubkey) -> Instruction {
+    instruction(
+        gem_farm::accounts::QuoteFarmerRewards {
+            farm: *farm,
+            farmer: find_farmer_pda(farm, identity).0,
+        },
+        gem_farm::instruction::QuoteFarmerRewards {},
+    )
+}
+
+/// farmers = farmer PDAs (not identities), reward pots are read off the farm account.
+/// Simulate it - it fails with the first broken invariant, unless pause_on_failure is set
+pub fn audit_farm(
+    farm: &Pubkey,
+    reward_a_pot: &Pubkey,
+    reward_b_pot: &Pubkey,
+    farmers: &[Pubkey],
+    pause_on_failure: bool,
+) -> Instruction {
+    let mut ix = instruction(
+        gem_farm::accounts::AuditFarm {
+            farm: *farm,
+            reward_a_pot: *reward_a_pot,
+            reward_b_pot: *reward_b_pot,
+        },
+        gem_farm::instruction::AuditFarm { pause_on_failure },
+    );
+    ix.accounts.extend(
+        farmers
+            .iter()
+            .map(|farmer| AccountMeta::new_readonly(*farmer, false)),
+    );
+
+    ix
+}
+
+// --------------------------------------- admin
+
+/// farm + bank are fresh keypairs, both have to sign; fee_acc = protocol_config.fee_wallet
+#[allow(clippy::too_many_arguments)]
+pub fn init_farm(
+    farm: &Pubkey,
+    bank: &Pubkey,
+    farm_manager: &Pubkey,
+    payer: &Pubkey,
+    fee_acc: &Pubkey,
+    reward_a_mint: &Pubkey,
+    reward_type_a: RewardType,
+    reward_b_mint: &Pubkey,
+    reward_type_b: RewardType,
+    farm_config: FarmConfig,
+    max_counts: Option<MaxCounts>,
+) -> Instruction {
+    let (farm_authority, bump_auth) = find_farm_authority_pda(farm);
+    let (farm_treasury, bump_treasury) = find_farm_treasury_pda(farm);
+
+    instruction(
+        gem_farm::accounts::InitFarm {
+            farm: *farm,
+            farm_manager: *farm_manager,
+            farm_authority,
+            reward_a_pot: find_reward_pot_pda(farm, reward_a_mint).0,
+            reward_a_mint: *reward_a_mint,
+            reward_b_pot: find_reward_pot_pda(farm, reward_b_mint).0,
+            reward_b_mint: *reward_b_mint,
+            bank: *bank,
+            gem_bank: gem_bank::ID,
+            payer: *payer,
+            protocol_config: find_protocol_config_pda().0,
+            fee_acc: *fee_acc,
+            fee_exemption: find_fee_exemption_pda(farm).0,
+            rent: sysvar::rent::ID,
+            token_program: token::ID,
+            system_program: system_program::ID,
+        },
+        gem_farm::instruction::InitFarm {
+            bump_auth,
+            _bump_treasury: bump_treasury,
+            reward_type_a,
+            reward_type_b,
+            farm_config,
+            max_counts,
+            farm_treasury,
+        },
+    )
+}
+
+/// refunds go to the receiver's ATA, created if missing
+pub fn cancel_reward(
+    farm: &Pubkey,
+    farm_manager: &Pubkey,
+    reward_mint: &Pubkey,
+    receiver: &Pubkey,
+) -> Instruction {
+    let (farm_authority, bump_auth) = find_farm_authority_pda(farm);
+    let (reward_pot, bump_pot) = find_reward_pot_pda(farm, reward_mint);
+
+    instruction(
+        gem_farm::accounts::CancelReward {
+            farm: *farm,
+            farm_manager: *farm_manager,
+            farm_authority,
+            reward_pot,
+            reward_destination: get_associated_token_address(receiver, reward_mint),
+            reward_mint: *reward_mint,
+            receiver: *receiver,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        gem_farm::instruction::CancelReward {
+            _bump_auth: bump_auth,
+            _bump_pot: bump_pot,
+        },
+    )
+}
+
+pub fn sweep_dust(
+    farm: &Pubkey,
+    farm_manager: &Pubkey,
+    reward_mint: &Pubkey,
+    receiver: &Pubkey,
+) -> Instruction {
+    let (farm_authority, bump_auth) = find_farm_authority_pda(farm);
+    let (reward_pot, bump_pot) = find_reward_pot_pda(farm, reward_mint);
+
+    instruction(
+        gem_farm::accounts::SweepDust {
+            farm: *farm,
+            farm_manager: *farm_manager,
+            farm_authority,
+            reward_pot,
+            reward_destination: get_associated_token_address(receiver, reward_mint),
+            reward_mint: *reward_mint,
+            receiver: *receiver,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        gem_farm::instruction::SweepDust {
+            _bump_auth: bump_auth,
+            _bump_pot: bump_pot,
+        },
+    )
+}
+
+/// surplus goes to the receiver's ATA, created if missing
+pub fn rescue_surplus(
+    farm: &Pubkey,
+    farm_manager: &Pubkey,
+    reward_mint: &Pubkey,
+    receiver: &Pubkey,
+) -> Instruction {
+    let (farm_authority, bump_auth) = find_farm_authority_pda(farm);
+    let (reward_pot, bump_pot) = find_reward_pot_pda(farm, reward_mint);
+
+    instruction(
+        gem_farm::accounts::RescueSurplus {
+            farm: *farm,
+            farm_manager: *farm_manager,
+            farm_authority,
+            reward_pot,
+            reward_destination: get_associated_token_address(receiver, reward_mint),
+            reward_mint: *reward_mint,
+            receiver: *receiver,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        gem_farm::instruction::RescueSurplus {
+            _bump_auth: bump_auth,
+            _bump_pot: bump_pot,
+        },
+    )
+}
+
+pub fn roll_surplus(farm: &Pubkey, farm_manager: &Pubkey, reward_mint: &Pubkey) -> Instruction {
+    let (reward_pot, bump_pot) = find_reward_pot_pda(farm, reward_mint);
+
+    instruction(
+        gem_farm::accounts::RollSurplus {
+            farm: *farm,
+            farm_manager: *farm_manager,
+            reward_pot,
+            reward_mint: *reward_mint,
+        },
+        gem_farm::instruction::RollSurplus {
+            _bump_pot: bump_pot,
+        },
+    )
+}
+
+pub fn lock_reward(farm: &Pubkey, farm_manager: &Pubkey, reward_mint: &Pubkey) -> Instruction {
+    instruction(
+        gem_farm::accounts::LockReward {
+            farm: *farm,
+            farm_manager: *farm_manager,
+            reward_mint: *reward_mint,
+        },
+        gem_farm::instruction::LockReward {},
+    )
+}
+
+pub fn set_funding_paused(farm: &Pubkey, farm_manager: &Pubkey, paused: bool) -> Instruction {
+    instruction(
+        gem_farm::accounts::SetFundingPaused {
+            farm: *farm,
+            farm_manager: *farm_manager,
+        },
+        gem_farm::instruction::SetFundingPaused { paused },
+    )
+}
+
+pub fn add_to_bank_whitelist(
+    farm: &Pubkey,
+    bank: &Pubkey,
+    farm_manager: &Pubkey,
+    address_to_whitelist: &Pubkey,
+    whitelist_type: u8,
+) -> Instruction {
+    let (farm_authority, bump_auth) = find_farm_authority_pda(farm);
+
+    instruction(
+        gem_farm::accounts::AddToBankWhitelist {
+            farm: *farm,
+            farm_manager: *farm_manager,
+            farm_authority,
+            bank: *bank,
+            address_to_whitelist: *address_to_whitelist,
+            whitelist_proof: find_whitelist_proof_pda(bank, address_to_whitelist).0,
+            system_program: system_program::ID,
+            gem_bank: gem_bank::ID,
+        },
+        gem_farm::instruction::AddToBankWhitelist {
+            _bump_auth: bump_auth,
+            whitelist_type,
+        },
+    )
+}
+
+pub fn remove_from_bank_whitelist(
+    farm: &Pubkey,
+    bank: &Pubkey,
+    farm_manager: &Pubkey,
+    address_to_remove: &Pubkey,
+) -> Instruction {
+    let (farm_authority, bump_auth) = find_farm_authority_pda(farm);
+    let (whitelist_proof, bump_wl) = find_whitelist_proof_pda(bank, address_to_remove);
+
+    instruction(
+        gem_farm::accounts::RemoveFromBankWhitelist {
+            farm: *farm,
+            farm_manager: *farm_manager,
+            farm_authority,
+            bank: *bank,
+            address_to_remove: *address_to_remove,
+            whitelist_proof,
+            gem_bank: gem_bank::ID,
+        },
+        gem_farm::instruction::RemoveFromBankWhitelist {
+            _bump_auth: bump_auth,
+            bump_wl,
+        },
+    )
+}
+
+/// same remaining accounts layout as gem_bank's record_rarity_points, keep batches small
+pub fn add_rarities_to_bank(
+    farm: &Pubkey,
+    bank: &Pubkey,
+    farm_manager: &Pubkey,
+    rarity_configs: Vec<RarityConfig>,
+) -> Instruction {
+    let (farm_authority, bump_auth) = find_farm_authority_pda(farm);
+
+    let mut ix = instruction(
+        gem_farm::accounts::AddRaritiesToBank {
+            farm: *farm,
+            farm_manager: *farm_manager,
+            farm_authority,
+            bank: *bank,
+            gem_bank: gem_bank::ID,
+            system_program: system_program::ID,
+        },
+        gem_farm::instruction::AddRaritiesToBank {
+            _bump_auth: bump_auth,
+            rarity_configs: rarity_configs.clone(),
+        },
+    );
+
+    for config in rarity_configs {
+        ix.accounts
+            .push(AccountMeta::new_readonly(config.mint, false));
+        ix.accounts.push(AccountMeta::new(
+            find_rarity_pda(bank, &config.mint).0,
+            false,
+        ));
+    }
+
+    ix
+}
+
+pub fn payout_from_treasury(
+    farm: &Pubkey,
+    farm_manager: &Pubkey,
+    destination: &Pubkey,
+    lamports: u64,
+) -> Instruction {
+    let (farm_authority, bump_auth) = find_farm_authority_pda(farm);
+    let (farm_treasury, bump_treasury) = find_farm_treasury_pda(farm);
+
+    instruction(
+        gem_farm::accounts::TreasuryPayout {
+            farm: *farm,
+            farm_manager: *farm_manager,
+            farm_authority,
+            farm_treasury,
+            destination: *destination,
+            system_program: system_program::ID,
+        },
+        gem_farm::instruction::PayoutFromTreasury {
+            _bump_auth: bump_auth,
+            bump_treasury,
+            lamports,
+        },
+    )
+}
+
+/// permissionless, token fees can't be collected until it exists
+pub fn init_token_treasury(farm: &Pubkey, fee_mint: &Pubkey, payer: &Pubkey) -> Instruction {
+    let (farm_authority, bump_auth) = find_farm_authority_pda(farm);
+
+    instruction(
+        gem_farm::accounts::InitTokenTreasury {
+            farm: *farm,
+            farm_authority,
+            token_treasury: find_token_treasury_pda(farm, fee_mint).0,
+            fee_mint: *fee_mint,
+            payer: *payer,
+            rent: sysvar::rent::ID,
+            token_program: token::ID,
+            system_program: system_program::ID,
+        },
+        gem_farm::instruction::InitTokenTreasury {
+            _bump_auth: bump_auth,
+        },
+    )
+}
+
+/// farm_manager = anyone with the TreasuryAdmin role
+pub fn payout_from_token_treasury(
+    farm: &Pubkey,
+    farm_manager: &Pubkey,
+    fee_mint: &Pubkey,
+    destination: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let (farm_authority, bump_auth) = find_farm_authority_pda(farm);
+    let (token_treasury, bump_treasury) = find_token_treasury_pda(farm, fee_mint);
+
+    instruction(
+        gem_farm::accounts::TokenTreasuryPayout {
+            farm: *farm,
+            farm_manager: *farm_manager,
+            farm_authority,
+            token_treasury,
+            destination: *destination,
+            token_program: token::ID,
+        },
+        gem_farm::instruction::PayoutFromTokenTreasury {
+            _bump_auth: bump_auth,
+            _bump_treasury: bump_treasury,
+            amount,
+        },
+    )
+}
+
+// --------------------------------------- protocol
+
+/// admin = the program's upgrade authority
+pub fn init_protocol_config(admin: &Pubkey, fee_wallet: &Pubkey, fees: FeeTable) -> Instruction {
+    let (program_data, _bump) =
+        Pubkey::find_program_address(&[gem_farm::ID.as_ref()], &bpf_loader_upgradeable::ID);
+
+    instruction(
+        gem_farm::accounts::InitProtocolConfig {
+            protocol_config: find_protocol_config_pda().0,
+            admin: *admin,
+            program: gem_farm::ID,
+            program_data,
+            system_program: system_program::ID,
+        },
+        gem_farm::instruction::InitProtocolConfig {
+            fee_wallet: *fee_wallet,
+            fees,
+        },
+    )
+}
+
+pub fn update_protocol_config(
+    admin: &Pubkey,
+    new_admin: Option<Pubkey>,
+    fee_wallet: Option<Pubkey>,
+    fees: Option<FeeTable>,
+) -> Instruction {
+    instruction(
+        gem_farm::accounts::UpdateProtocolConfig {
+            protocol_config: find_protocol_config_pda().0,
+            admin: *admin,
+        },
+        gem_farm::instruction::UpdateProtocolConfig {
+            admin: new_admin,
+            fee_wallet,
+            fees,
+        },
+    )
+}
+
+pub fn add_fee_exemption(admin: &Pubkey, farm: &Pubkey) -> Instruction {
+    instruction(
+        gem_farm::accounts::AddFeeExemption {
+            protocol_config: find_protocol_config_pda().0,
+            admin: *admin,
+            farm: *farm,
+            fee_exemption: find_fee_exemption_pda(farm).0,
+            system_program: system_program::ID,
+        },
+        gem_farm::instruction::AddFeeExemption {},
+    )
+}
+
+pub fn remove_fee_exemption(admin: &Pubkey, farm: &Pubkey) -> Instruction {
+    let (fee_exemption, bump) = find_fee_exemption_pda(farm);
+
+    instruction(
+        gem_farm::accounts::RemoveFeeExemption {
+            protocol_config: find_protocol_config_pda().0,
+            admin: *admin,
+            farm: *farm,
+            fee_exemption,
+            system_program: system_program::ID,
+        },
+        gem_farm::instruction::RemoveFeeExemption { _bump: bump },
+    )
+}
+
+// --------------------------------------- versions
+
+/// payer covers the rent for the account growing to its latest layout
+pub fn migrate_farm_version(farm: &Pubkey, payer: &Pubkey) -> Instruction {
+    instruction(
+        gem_farm::accounts::MigrateFarmVersion {
+            farm: *farm,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        gem_farm::instruction::MigrateFarmVersion {},
+    )
+}
+
+/// farmer = the farmer PDA, not the identity; payer as in migrate_farm_version
+pub fn migrate_farmer_version(farmer: &Pubkey, payer: &Pubkey) -> Instruction {
+    instruction(
+        gem_farm::accounts::MigrateFarmerVersion {
+            farmer: *farmer,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        gem_farm::instruction::MigrateFarmerVersion {},
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_fee_accounts_come_last() {
+        let (farm, bank, identity, vault) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let (gem_source, gem_mint, creator, fee_acc) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let token_fee = TokenFeeAccounts {
+            fee_mint: Pubkey::new_unique(),
+            fee_source: Pubkey::new_unique(),
+        };
+        let flash_deposit = |token_fee| {
+            flash_deposit(
+                &farm,
+                &bank,
+                &identity,
+                &vault,
+                &gem_source,
+                &gem_mint,
+                Some(&creator),
+                &fee_acc,
+                token_fee,
+                1,
+            )
+        };
+
+        // whitelist proofs first, then the token fee accounts
+        let without_fee = flash_deposit(None);
+        let with_fee = flash_deposit(Some(&token_fee));
+        let split = without_fee.accounts.len();
+        assert_eq!(without_fee.accounts, with_fee.accounts[..split]);
+        assert_eq!(
+            token_fee.remaining_accounts(&farm),
+            with_fee.accounts[split..]
+        );
+
+        // same for the ixs with no other remaining accounts
+        let without_fee = stake(&farm, &bank, &identity, &vault, &fee_acc, None);
+        let with_fee = stake(&farm, &bank, &identity, &vault, &fee_acc, Some(&token_fee));
+        let split = without_fee.accounts.len();
+        assert_eq!(without_fee.accounts, with_fee.accounts[..split]);
+        assert_eq!(
+            token_fee.remaining_accounts(&farm),
+            with_fee.accounts[split..]
+        );
+
+        // migrate_farmer pays to_farm's fee, after the (gdr, rarity) pair of every gem
+        let (to_farm, to_bank) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let migrate_farmer = |token_fee| {
+            migrate_farmer(
+                &farm,
+                &bank,
+                &to_farm,
+                &to_bank,
+                &identity,
+                &identity,
+                &vault,
+                &[gem_mint],
+                &fee_acc,
+                token_fee,
+            )
+        };
+        let without_fee = migrate_farmer(None);
+        let with_fee = migrate_farmer(Some(&token_fee));
+        let split = without_fee.accounts.len();
+        assert_eq!(
+            without_fee.accounts[split - 2..]
+                .iter()
+                .map(|meta| meta.pubkey)
+                .collect::<Vec<_>>(),
+            [
+                find_gdr_pda(&vault, &gem_mint).0,
+                find_rarity_pda(&to_bank, &gem_mint).0
+            ]
+        );
+        assert_eq!(without_fee.accounts, with_fee.accounts[..split]);
+        assert_eq!(
+            token_fee.remaining_accounts(&to_farm),
+            with_fee.accounts[split..]
+        );
+    }
+}
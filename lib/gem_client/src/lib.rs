@@ -0,0 +1,15 @@
+//! native client for gem_bank + gem_farm, the rust counterpart of the ts client in src/
+//!
+//! - pda: every PDA the programs use
+//! - bank / farm: instruction builders that derive bumps and fill in remaining accounts
+//! - accounts: decoders + getProgramAccounts filters
+//! - pnft: account sets for programmable NFTs
+
+pub mod accounts;
+pub mod bank;
+pub mod farm;
+pub mod pda;
+pub mod pnft;
+
+pub use gem_bank;
+pub use gem_farm;
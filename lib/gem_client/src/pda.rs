@@ -0,0 +1,320 @@
+//! every PDA used by gem_bank and gem_farm, mirrors the seeds in the programs' account constraints
+
+use anchor_lang::prelude::*;
+
+// --------------------------------------- gem bank
+
+pub fn find_vault_pda(bank: &Pubkey, creator: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"vault".as_ref(), bank.as_ref(), creator.as_ref()],
+        &gem_bank::ID,
+    )
+}
+
+pub fn find_vault_authority_pda(vault: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[vault.as_ref()], &gem_bank::ID)
+}
+
+pub fn find_gem_box_pda(vault: &Pubkey, gem_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"gem_box".as_ref(), vault.as_ref(), gem_mint.as_ref()],
+        &gem_bank::ID,
+    )
+}
+
+pub fn find_gdr_pda(vault: &Pubkey, gem_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"gem_deposit_receipt".as_ref(),
+            vault.as_ref(),
+            gem_mint.as_ref(),
+        ],
+        &gem_bank::ID,
+    )
+}
+
+pub fn find_rarity_pda(bank: &Pubkey, gem_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"gem_rarity".as_ref(), bank.as_ref(), gem_mint.as_ref()],
+        &gem_bank::ID,
+    )
+}
+
+pub fn find_whitelist_proof_pda(bank: &Pubkey, whitelisted_address: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"whitelist".as_ref(),
+            bank.as_ref(),
+            whitelisted_address.as_ref(),
+        ],
+        &gem_bank::ID,
+    )
+}
+
+// --------------------------------------- gem farm
+
+pub fn find_farm_authority_pda(farm: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[farm.as_ref()], &gem_farm::ID)
+}
+
+pub fn find_farmer_pda(farm: &Pubkey, identity: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"farmer".as_ref(), farm.as_ref(), identity.as_ref()],
+        &gem_farm::ID,
+    )
+}
+
+pub fn find_farm_treasury_pda(farm: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"treasury".as_ref(), farm.as_ref()], &gem_farm::ID)
+}
+
+pub fn find_token_treasury_pda(farm: &Pubkey, fee_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"token_treasury".as_ref(), farm.as_ref(), fee_mint.as_ref()],
+        &gem_farm::ID,
+    )
+}
+
+pub fn find_reward_pot_pda(farm: &Pubkey, reward_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"reward_pot".as_ref(), farm.as_ref(), reward_mint.as_ref()],
+        &gem_farm::ID,
+    )
+}
+
+pub fn find_authorization_proof_pda(farm: &Pubkey, funder: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"authorization".as_ref(), farm.as_ref(), funder.as_ref()],
+        &gem_farm::ID,
+    )
+}
+
+pub fn find_migration_proof_pda(from_farm: &Pubkey, to_farm: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"migration".as_ref(), from_farm.as_ref(), to_farm.as_ref()],
+        &gem_farm::ID,
+    )
+}
+
+pub fn find_pending_farm_config_pda(farm: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"pending_config".as_ref(), farm.as_ref()], &gem_farm::ID)
+}
+
+pub fn find_fee_exemption_pda(farm: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"fee_exemption".as_ref(), farm.as_ref()], &gem_farm::ID)
+}
+
+pub fn find_protocol_config_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"protocol_config".as_ref()], &gem_farm::ID)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path};
+
+    use super::*;
+
+    /// a seed as spelled in a `seeds = [..]` constraint: a byte literal or some account's key
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    enum Seed {
+        Literal(Vec<u8>),
+        Key,
+    }
+
+    fn collect_sources(dir: &Path, sources: &mut Vec<String>) {
+        for entry in fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                collect_sources(&path, sources);
+            } else if path.extension() == Some("rs".as_ref()) {
+                sources.push(fs::read_to_string(path).unwrap());
+            }
+        }
+    }
+
+    /// every `seeds = [..]` constraint in both programs, metaplex PDAs aside
+    fn program_seeds() -> Vec<Vec<Seed>> {
+        let programs = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../programs");
+        let mut sources = vec![];
+        collect_sources(&programs.join("gem_bank/src"), &mut sources);
+        collect_sources(&programs.join("gem_farm/src"), &mut sources);
+
+        let mut all_seeds = vec![];
+        for source in sources {
+            let source: String = source.split_whitespace().collect();
+            for constraint in source.split("seeds=[").skip(1) {
+                let seeds = &constraint[..constraint.find(']').unwrap()];
+                if seeds.contains("mpl_token_metadata") {
+                    continue;
+                }
+
+                let seeds: Vec<Seed> = seeds
+                    .split(',')
+                    .filter(|seed| !seed.is_empty())
+                    .map(|seed| match seed.strip_prefix("b\"") {
+                        Some(literal) => {
+                            Seed::Literal(literal[..literal.find('"').unwrap()].into())
+                        }
+                        None => Seed::Key,
+                    })
+                    .collect();
+                if !all_seeds.contains(&seeds) {
+                    all_seeds.push(seeds);
+                }
+            }
+        }
+        all_seeds
+    }
+
+    /// derives the PDA from the constraint's seeds, filling in the keys in order
+    fn derive(seeds: &[Seed], keys: &[Pubkey], program_id: &Pubkey) -> (Pubkey, u8) {
+        let mut keys = keys.iter();
+        let seeds: Vec<&[u8]> = seeds
+            .iter()
+            .map(|seed| match seed {
+                Seed::Literal(literal) => literal.as_slice(),
+                Seed::Key => keys.next().unwrap().as_ref(),
+            })
+            .collect();
+        assert!(keys.next().is_none());
+        Pubkey::find_program_address(&seeds, program_id)
+    }
+
+    #[test]
+    fn test_pdas_match_program_constraints() {
+        let program_seeds = program_seeds();
+        let mut covered = vec![];
+
+        let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut check = |literal: Option<&str>, keys: &[Pubkey], program_id, found| {
+            let mut seeds: Vec<Seed> = literal
+                .map(|l| Seed::Literal(l.into()))
+                .into_iter()
+                .collect();
+            seeds.extend(keys.iter().map(|_| Seed::Key));
+
+            assert!(
+                program_seeds.contains(&seeds),
+                "no constraint uses {:?}",
+                seeds
+            );
+            assert_eq!(derive(&seeds, keys, program_id), found, "{:?}", seeds);
+            covered.push(seeds);
+        };
+
+        // gem bank
+        check(
+            Some("vault"),
+            &[a, b],
+            &gem_bank::ID,
+            find_vault_pda(&a, &b),
+        );
+        check(None, &[a], &gem_bank::ID, find_vault_authority_pda(&a));
+        check(
+            Some("gem_box"),
+            &[a, b],
+            &gem_bank::ID,
+            find_gem_box_pda(&a, &b),
+        );
+        check(
+            Some("gem_deposit_receipt"),
+            &[a, b],
+            &gem_bank::ID,
+            find_gdr_pda(&a, &b),
+        );
+        check(
+            Some("gem_rarity"),
+            &[a, b],
+            &gem_bank::ID,
+            find_rarity_pda(&a, &b),
+        );
+        check(
+            Some("whitelist"),
+            &[a, b],
+            &gem_bank::ID,
+            find_whitelist_proof_pda(&a, &b),
+        );
+
+        // gem farm
+        check(None, &[a], &gem_farm::ID, find_farm_authority_pda(&a));
+        check(
+            Some("farmer"),
+            &[a, b],
+            &gem_farm::ID,
+            find_farmer_pda(&a, &b),
+        );
+        check(
+            Some("treasury"),
+            &[a],
+            &gem_farm::ID,
+            find_farm_treasury_pda(&a),
+        );
+        check(
+            Some("token_treasury"),
+            &[a, b],
+            &gem_farm::ID,
+            find_token_treasury_pda(&a, &b),
+        );
+        check(
+            Some("reward_pot"),
+            &[a, b],
+            &gem_farm::ID,
+            find_reward_pot_pda(&a, &b),
+        );
+        check(
+            Some("authorization"),
+            &[a, b],
+            &gem_farm::ID,
+            find_authorization_proof_pda(&a, &b),
+        );
+        check(
+            Some("migration"),
+            &[a, b],
+            &gem_farm::ID,
+            find_migration_proof_pda(&a, &b),
+        );
+        check(
+            Some("pending_config"),
+            &[a],
+            &gem_farm::ID,
+            find_pending_farm_config_pda(&a),
+        );
+        check(
+            Some("fee_exemption"),
+            &[a],
+            &gem_farm::ID,
+            find_fee_exemption_pda(&a),
+        );
+        check(
+            Some("protocol_config"),
+            &[],
+            &gem_farm::ID,
+            find_protocol_config_pda(),
+        );
+
+        // and nothing the programs derive is missing here
+        for seeds in program_seeds.iter() {
+            assert!(covered.contains(seeds), "no client PDA for {:?}", seeds);
+        }
+    }
+
+    #[test]
+    fn test_authority_pdas_match_signer_seeds() {
+        // what the programs actually sign with, see Farm::farm_seeds / Vault::vault_seeds
+        let farm = Pubkey::new_unique();
+        let (farm_authority, bump) = find_farm_authority_pda(&farm);
+        let farm_seeds: [&[u8]; 2] = [farm.as_ref(), &[bump]];
+        assert_eq!(
+            Pubkey::create_program_address(&farm_seeds, &gem_farm::ID).unwrap(),
+            farm_authority
+        );
+
+        let vault = Pubkey::new_unique();
+        let (vault_authority, bump) = find_vault_authority_pda(&vault);
+        let vault_seeds: [&[u8]; 2] = [vault.as_ref(), &[bump]];
+        assert_eq!(
+            Pubkey::create_program_address(&vault_seeds, &gem_bank::ID).unwrap(),
+            vault_authority
+        );
+    }
+}
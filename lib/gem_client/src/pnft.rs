@@ -0,0 +1,35 @@
+//! account sets needed to move programmable NFTs through token metadata
+
+use anchor_lang::prelude::*;
+use mpl_token_metadata::pda::{
+    find_master_edition_account, find_metadata_account, find_token_record_account,
+};
+
+/// the pNFT accounts of a single transfer, from `source` to `destination` token accounts
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PnftTransferAccounts {
+    pub metadata: Pubkey,
+    /// master edition and edition share the same seeds
+    pub edition: Pubkey,
+    pub source_token_record: Pubkey,
+    pub dest_token_record: Pubkey,
+}
+
+impl PnftTransferAccounts {
+    pub fn new(mint: &Pubkey, source: &Pubkey, destination: &Pubkey) -> Self {
+        Self {
+            metadata: find_metadata_account(mint).0,
+            edition: find_master_edition_account(mint).0,
+            source_token_record: find_token_record_account(mint, source).0,
+            dest_token_record: find_token_record_account(mint, destination).0,
+        }
+    }
+}
+
+pub fn prog_nft_shared() -> gem_bank::accounts::ProgNftShared {
+    gem_bank::accounts::ProgNftShared {
+        token_metadata_program: mpl_token_metadata::id(),
+        instructions: anchor_lang::solana_program::sysvar::instructions::ID,
+        authorization_rules_program: mpl_token_auth_rules::id(),
+    }
+}
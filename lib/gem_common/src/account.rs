@@ -1,6 +1,10 @@
 use std::io::Write;
 
-use anchor_lang::{__private::CLOSED_ACCOUNT_DISCRIMINATOR, prelude::*};
+use anchor_lang::{
+    __private::CLOSED_ACCOUNT_DISCRIMINATOR,
+    prelude::*,
+    solana_program::{program::invoke, system_instruction},
+};
 
 use crate::{errors::ErrorCode, try_math::*};
 
@@ -23,3 +27,60 @@ pub fn close_account(
         .map_err(|_| error!(ErrorCode::AnchorSerializationIssue))?;
     Ok(())
 }
+
+/// grows an account to new_len (new bytes are zeroed), topping it up to stay rent exempt
+pub fn realloc_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    new_len: usize,
+) -> Result<()> {
+    if account.data_len() >= new_len {
+        return Ok(());
+    }
+
+    let rent_due = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(account.lamports());
+    if rent_due > 0 {
+        invoke(
+            &system_instruction::transfer(payer.key, account.key, rent_due),
+            &[payer.clone(), account.clone(), system_program.clone()],
+        )?;
+    }
+
+    account.realloc(new_len, true).map_err(Into::into)
+}
+
+/// Extensions are stored right after the space originally allocated for an account, for
+/// anything that no longer fits into its reserved space. Anchor only ever (de)serializes the
+/// original struct, so they're loaded / stored separately. None = account predates it
+pub fn load_extension<T: AnchorDeserialize>(
+    account: &AccountInfo,
+    offset: usize,
+) -> Result<Option<T>> {
+    let data = account.try_borrow_data()?;
+    if data.len() <= offset {
+        return Ok(None);
+    }
+
+    T::deserialize(&mut &data[offset..])
+        .map(Some)
+        .map_err(|_| error!(ErrorCode::AnchorSerializationIssue))
+}
+
+pub fn store_extension<T: AnchorSerialize>(
+    account: &AccountInfo,
+    offset: usize,
+    extension: &T,
+) -> Result<()> {
+    let mut data = account.try_borrow_mut_data()?;
+    if data.len() <= offset {
+        return Err(error!(ErrorCode::OutdatedAccountVersion));
+    }
+
+    let mut dst: &mut [u8] = &mut data[offset..];
+    extension
+        .serialize(&mut dst)
+        .map_err(|_| error!(ErrorCode::AnchorSerializationIssue))
+}
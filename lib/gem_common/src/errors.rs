@@ -36,18 +36,41 @@ pub enum ErrorCode {
     #[msg("bad metadata passed")]
     BadMetadata,
 
-    Reserved8,
-    Reserved9,
-    Reserved10,
-    Reserved11,
-    Reserved12,
-    Reserved13,
-    Reserved14,
-    Reserved15,
-    Reserved16,
-    Reserved17,
-    Reserved18,
-    Reserved19,
+    #[msg("account has to be migrated to the latest version first")]
+    OutdatedAccountVersion,
+
+    #[msg("signer hasn't been proposed as the new owner")]
+    NotPendingOwner,
+
+    #[msg("signer is neither the farm manager nor the admin for this role")]
+    MissingRole,
+
+    #[msg("funder's authorization has expired or doesn't cover this reward type")]
+    FundingNotAllowed,
+
+    #[msg("funding exceeds what's left of the funder's allowance")]
+    FundingAllowanceExceeded,
+
+    #[msg("token fee accounts are missing or don't match the farm's token treasury")]
+    InvalidTokenFeeAccounts,
+
+    #[msg("treasury splits have to add up to 10000 bps and match the recipients passed")]
+    InvalidTreasurySplits,
+
+    #[msg("audit: fixed rate reserved amount exceeds the reward's pending funds")]
+    ReservedExceedsPending,
+
+    #[msg("audit: reward pot holds less than pending funds plus what's owed to farmers")]
+    RewardPotInsolvent,
+
+    #[msg("audit: farm's staked counts don't match the farmer accounts passed")]
+    StakedCountsMismatch,
+
+    #[msg("funding is paused for this farm until the reward admin clears it")]
+    FundingPaused,
+
+    #[msg("dust can only be swept once the reward has ended and every farmer has unstaked")]
+    RewardNotSettled,
 
     // --------------------------------------- bank specific (20 - 39)
     #[msg("vault is currently locked or frozen and cannot be accessed")]
@@ -64,9 +87,16 @@ pub enum ErrorCode {
 
     #[msg("attempted transfer not allowed")]
     TransferNotAllowed,
-    Reserved25,
-    Reserved26,
-    Reserved27,
+
+    #[msg("every gem box in the vault has to be passed in, exactly once")]
+    GemBoxesMissing, //0x1789
+
+    #[msg("bank can't be closed while it still has vaults or whitelist proofs")]
+    BankNotEmpty,
+
+    #[msg("vault can't be closed while it still holds gems")]
+    VaultNotEmpty,
+
     Reserved28,
     Reserved29,
     Reserved30,
@@ -93,7 +123,8 @@ pub enum ErrorCode {
     #[msg("can't unstake, cooldown period has not passed yet")]
     CooldownNotPassed,
 
-    Reserved44,
+    #[msg("pending config change can't be applied before its delay has passed")]
+    ConfigChangeNotDue,
 
     #[msg("reward has insufficient funding, please top up")]
     RewardUnderfunded, //0x179D
@@ -116,12 +147,42 @@ pub enum ErrorCode {
     #[msg("unstaking fee lamports must be either 0 or greater than 890880")]
     InvalidUnstakingFee, //0x17a3
 
-    Reserved52,
-    Reserved53,
-    Reserved54,
-    Reserved55,
-    Reserved56,
-    Reserved57,
-    Reserved58,
-    Reserved59,
+    #[msg("farm is in emergency mode, rewards are frozen and farmers can only unstake")]
+    FarmInEmergency, //0x17a4
+
+    #[msg("farmer has to be staked")]
+    FarmerNotStaked, //0x17a5
+
+    #[msg("farm can't be closed while it has farmers, funded pots, authorized funders, migrations or token treasuries")]
+    FarmNotEmpty,
+
+    #[msg("farmer can only be closed once unstaked, with all rewards claimed")]
+    FarmerNotEmpty,
+
+    #[msg("crank tip can't exceed 500 bps")]
+    InvalidCrankTip,
+
+    #[msg("instant unstaking isn't enabled for this farm")]
+    InstantUnstakeDisabled,
+
+    #[msg("no more farms can be exempted from protocol fees")]
+    TooManyFeeExemptions,
+
+    #[msg("config delay has been locked and can only be increased")]
+    ConfigDelayLocked,
+
+    #[msg("surplus can only be rolled into a variable reward while it's still running")]
+    RewardNotActive,
+
+    #[msg("farmer hasn't opted in to auto claim")]
+    AutoClaimDisabled,
+
+    #[msg("the previous flash withdrawal has to be released first")]
+    WithdrawalPending,
+
+    #[msg("no flash withdrawal is pending for this gem mint")]
+    NoPendingWithdrawal,
+
+    #[msg("config delay can only be lowered through propose_farm_config")]
+    ConfigDelayDecrease,
 }
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+// emitted via sol_log_data, which is a lot cheaper than formatting the same info with msg!
+// all ts are unix seconds, all vault counts are totals after the change
+
+#[event]
+pub struct GemDeposited {
+    pub bank: Pubkey,
+    pub vault: Pubkey,
+    pub gem_mint: Pubkey,
+    pub amount: u64,
+    pub vault_gem_count: u64,
+    pub vault_rarity_points: u64,
+    pub ts: u64,
+}
+
+#[event]
+pub struct GemWithdrawn {
+    pub bank: Pubkey,
+    pub vault: Pubkey,
+    pub gem_mint: Pubkey,
+    pub amount: u64,
+    pub vault_gem_count: u64,
+    pub vault_rarity_points: u64,
+    pub ts: u64,
+}
+
+#[event]
+pub struct WhitelistUpdated {
+    pub bank: Pubkey,
+    pub address: Pubkey,
+    /// see WhitelistType, 0 = removed from the whitelist
+    pub whitelist_type: u8,
+    pub ts: u64,
+}
+
+#[event]
+pub struct RarityRecorded {
+    pub bank: Pubkey,
+    pub gem_mint: Pubkey,
+    pub rarity_points: u16,
+    pub ts: u64,
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use gem_common::errors::ErrorCode;
+
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct AcceptVaultOwner<'info> {
+    // bank
+    #[account(constraint = bank.version == LATEST_BANK_VERSION @ ErrorCode::OutdatedAccountVersion)]
+    pub bank: Box<Account<'info, Bank>>,
+
+    // vault
+    // same rationale for not verifying the PDA as in deposit
+    #[account(mut, has_one = bank,
+        constraint = vault.pending_owner == new_owner.key() @ ErrorCode::NotPendingOwner,
+        constraint = vault.version == LATEST_VAULT_VERSION @ ErrorCode::OutdatedAccountVersion)]
+    pub vault: Box<Account<'info, Vault>>,
+    pub new_owner: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<AcceptVaultOwner>) -> Result<()> {
+    let bank = &ctx.accounts.bank;
+    let vault = &mut ctx.accounts.vault;
+
+    if Bank::read_flags(bank.flags)?.contains(BankFlags::FREEZE_VAULTS) {
+        return Err(error!(ErrorCode::VaultAccessSuspended));
+    }
+
+    vault.owner = ctx.accounts.new_owner.key();
+    vault.pending_owner = Pubkey::default();
+
+    //msg!("owner updated to: {}", vault.owner);
+    Ok(())
+}
@@ -1,14 +1,15 @@
 use anchor_lang::prelude::*;
 use anchor_lang::Discriminator;
 use arrayref::array_ref;
-use gem_common::*;
+use gem_common::{errors::ErrorCode, *};
 
-use crate::state::*;
+use crate::{events::*, state::*};
 
 #[derive(Accounts)]
 pub struct AddToWhitelist<'info> {
     // bank
-    #[account(mut, has_one = bank_manager)]
+    #[account(mut, has_one = bank_manager,
+        constraint = bank.version == LATEST_BANK_VERSION @ ErrorCode::OutdatedAccountVersion)]
     pub bank: Box<Account<'info, Bank>>,
     pub bank_manager: Signer<'info>,
 
@@ -76,6 +77,13 @@ pub fn handler(ctx: Context<AddToWhitelist>, whitelist_type: u8) -> Result<()> {
         bank.whitelisted_mints.try_add_assign(1)?;
     }
 
+    emit!(WhitelistUpdated {
+        bank: bank.key(),
+        address: ctx.accounts.address_to_whitelist.key(),
+        whitelist_type: new_whitelist.bits(),
+        ts: now_ts()?,
+    });
+
     // msg!(
     //     "{} added to whitelist",
     //     &ctx.accounts.address_to_whitelist.key()
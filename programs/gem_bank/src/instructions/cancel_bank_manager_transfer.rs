@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+use gem_common::errors::ErrorCode;
+
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct CancelBankManagerTransfer<'info> {
+    // bank
+    #[account(mut, has_one = bank_manager,
+        constraint = bank.version == LATEST_BANK_VERSION @ ErrorCode::OutdatedAccountVersion)]
+    pub bank: Box<Account<'info, Bank>>,
+    pub bank_manager: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<CancelBankManagerTransfer>) -> Result<()> {
+    let bank = &mut ctx.accounts.bank;
+
+    bank.pending_bank_manager = Pubkey::default();
+
+    //msg!("bank manager transfer cancelled");
+    Ok(())
+}
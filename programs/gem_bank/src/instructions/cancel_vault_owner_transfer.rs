@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+use gem_common::errors::ErrorCode;
+
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct CancelVaultOwnerTransfer<'info> {
+    // vault
+    #[account(mut, has_one = owner,
+        constraint = vault.version == LATEST_VAULT_VERSION @ ErrorCode::OutdatedAccountVersion)]
+    pub vault: Box<Account<'info, Vault>>,
+    pub owner: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<CancelVaultOwnerTransfer>) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+
+    vault.pending_owner = Pubkey::default();
+
+    //msg!("owner transfer cancelled");
+    Ok(())
+}
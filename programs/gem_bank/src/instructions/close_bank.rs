@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+use gem_common::{errors::ErrorCode, *};
+
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct CloseBank<'info> {
+    // bank
+    #[account(mut, has_one = bank_manager)]
+    pub bank: Box<Account<'info, Bank>>,
+    pub bank_manager: Signer<'info>,
+    /// CHECK:
+    #[account(mut)]
+    pub funds_receiver: AccountInfo<'info>,
+    //
+    // remaining accounts can be any number of:
+    //   #[account(mut, has_one = bank)]
+    //   pub whitelist_proof: Box<Account<'info, WhitelistProof>>,
+}
+
+pub fn handler<'a, 'b, 'c, 'info>(ctx: Context<'a, 'b, 'c, 'info, CloseBank<'info>>) -> Result<()> {
+    // vaults would be left pointing to a non-existent bank, with no way to withdraw
+    if ctx.accounts.bank.vault_count > 0 {
+        return Err(error!(ErrorCode::BankNotEmpty));
+    }
+
+    // close any whitelist proofs passed in
+    let bank = &mut ctx.accounts.bank;
+
+    for proof_info in ctx.remaining_accounts.iter() {
+        let proof = Account::<WhitelistProof>::try_from(proof_info)?;
+        if proof.bank != bank.key() {
+            return Err(error!(ErrorCode::InvalidParameter));
+        }
+
+        if let Ok(()) = proof.contains_type(WhitelistType::MINT) {
+            bank.whitelisted_mints.try_sub_assign(1)?;
+        }
+        if let Ok(()) = proof.contains_type(WhitelistType::CREATOR) {
+            bank.whitelisted_creators.try_sub_assign(1)?;
+        }
+
+        close_account(
+            &mut proof_info.to_account_info(),
+            &mut ctx.accounts.funds_receiver,
+        )?;
+    }
+
+    // all of them have to go
+    if bank.whitelisted_mints > 0 || bank.whitelisted_creators > 0 {
+        return Err(error!(ErrorCode::BankNotEmpty));
+    }
+
+    close_account(
+        &mut bank.to_account_info(),
+        &mut ctx.accounts.funds_receiver,
+    )?;
+
+    //msg!("bank {} closed", bank.key());
+    Ok(())
+}
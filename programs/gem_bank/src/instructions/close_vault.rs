@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use gem_common::{errors::ErrorCode, *};
+
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct CloseVault<'info> {
+    // bank
+    #[account(mut)]
+    pub bank: Box<Account<'info, Bank>>,
+
+    // vault
+    // same rationale for not verifying the PDA as in deposit
+    #[account(mut, has_one = bank, has_one = owner)]
+    pub vault: Box<Account<'info, Vault>>,
+    pub owner: Signer<'info>,
+    /// CHECK:
+    #[account(mut)]
+    pub funds_receiver: AccountInfo<'info>,
+}
+
+pub fn handler(ctx: Context<CloseVault>) -> Result<()> {
+    let bank = &mut ctx.accounts.bank;
+    let vault = &mut ctx.accounts.vault;
+
+    if vault.access_suspended(bank.flags)? {
+        return Err(error!(ErrorCode::VaultAccessSuspended));
+    }
+
+    // gem boxes / GDRs are owned by the vault, closing it would orphan them
+    if vault.gem_box_count > 0 {
+        return Err(error!(ErrorCode::VaultNotEmpty));
+    }
+
+    bank.vault_count.try_sub_assign(1)?;
+
+    close_account(
+        &mut vault.to_account_info(),
+        &mut ctx.accounts.funds_receiver,
+    )?;
+
+    //msg!("vault {} closed", vault.key());
+    Ok(())
+}
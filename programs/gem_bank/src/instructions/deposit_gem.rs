@@ -3,18 +3,20 @@ use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
 use arrayref::array_ref;
 use gem_common::{errors::ErrorCode, *};
 
-use crate::{assert_decode_metadata, state::*};
+use crate::{assert_decode_metadata, events::*, state::*};
 
 #[derive(Accounts)]
 #[instruction(bump_auth: u8, bump_rarity: u8)]
 pub struct DepositGem<'info> {
     // bank
+    #[account(constraint = bank.version == LATEST_BANK_VERSION @ ErrorCode::OutdatedAccountVersion)]
     pub bank: Box<Account<'info, Bank>>,
 
     // vault
     // skipped vault PDA verification because requires passing in creator, which is tedious
     // sec wise secure enough: vault has owner -> owner is signer
-    #[account(mut, has_one = bank, has_one = owner, has_one = authority)]
+    #[account(mut, has_one = bank, has_one = owner, has_one = authority,
+        constraint = vault.version == LATEST_VAULT_VERSION @ ErrorCode::OutdatedAccountVersion)]
     pub vault: Box<Account<'info, Vault>>,
     // currently only the vault owner can deposit
     // add a "depositor" account, and remove Signer from vault owner to let anyone to deposit
@@ -230,6 +232,16 @@ pub fn handler<'info>(
     let gdr = &mut *ctx.accounts.gem_deposit_receipt;
     let gem_box = &*ctx.accounts.gem_box;
 
+    emit!(GemDeposited {
+        bank: ctx.accounts.bank.key(),
+        vault: vault.key(),
+        gem_mint: ctx.accounts.gem_mint.key(),
+        amount,
+        vault_gem_count: vault.gem_count,
+        vault_rarity_points: vault.rarity_points,
+        ts: now_ts()?,
+    });
+
     gdr.vault = vault.key();
     gdr.gem_box_address = gem_box.key();
     gdr.gem_mint = gem_box.mint;
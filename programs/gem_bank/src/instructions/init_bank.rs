@@ -5,7 +5,7 @@ use crate::state::*;
 #[derive(Accounts)]
 pub struct InitBank<'info> {
     // bank
-    #[account(init, payer = payer, space = 8 + std::mem::size_of::<Bank>())]
+    #[account(init, payer = payer, space = Bank::SPACE)]
     pub bank: Box<Account<'info, Bank>>,
     pub bank_manager: Signer<'info>,
 
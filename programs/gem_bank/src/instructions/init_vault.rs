@@ -1,14 +1,15 @@
 use std::io::Write;
 
 use anchor_lang::prelude::*;
-use gem_common::*;
+use gem_common::{errors::ErrorCode, *};
 
 use crate::state::*;
 
 #[derive(Accounts)]
 pub struct InitVault<'info> {
     // bank
-    #[account(mut)]
+    #[account(mut,
+        constraint = bank.version == LATEST_BANK_VERSION @ ErrorCode::OutdatedAccountVersion)]
     pub bank: Box<Account<'info, Bank>>,
 
     // vault
@@ -19,7 +20,7 @@ pub struct InitVault<'info> {
         ],
         bump,
         payer = payer,
-        space = 8 + std::mem::size_of::<Vault>())]
+        space = Vault::SPACE)]
     pub vault: Box<Account<'info, Vault>>,
     pub creator: Signer<'info>,
 
@@ -49,6 +50,7 @@ pub fn handler(ctx: Context<InitVault>, owner: Pubkey, name: String) -> Result<(
     vault.authority_seed = vault_address;
     vault.authority_bump_seed = [bump];
     vault.locked = false;
+    vault.version = LATEST_VAULT_VERSION;
     (&mut vault.name[..]).write_all(name.as_bytes())?;
 
     //msg!("new vault founded by {}", &ctx.accounts.creator.key());
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use gem_common::*;
+
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct MigrateBankVersion<'info> {
+    // bank
+    #[account(mut)]
+    pub bank: Box<Account<'info, Bank>>,
+
+    // misc
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// permissionless - anyone willing to pay the extra rent can bring a bank up to date
+pub fn handler(ctx: Context<MigrateBankVersion>) -> Result<()> {
+    let bank = &mut ctx.accounts.bank;
+
+    if bank.version == 0 {
+        realloc_account(
+            &bank.to_account_info(),
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            Bank::SPACE,
+        )?;
+        bank.version = 1;
+    }
+
+    //msg!("bank {} at version {}", bank.key(), bank.version);
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use gem_common::*;
+
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct MigrateVaultVersion<'info> {
+    // vault
+    #[account(mut)]
+    pub vault: Box<Account<'info, Vault>>,
+
+    // misc
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// permissionless - anyone willing to pay the extra rent can bring a vault up to date
+pub fn handler(ctx: Context<MigrateVaultVersion>) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+
+    if vault.version == 0 {
+        realloc_account(
+            &vault.to_account_info(),
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            Vault::SPACE,
+        )?;
+        vault.version = 1;
+    }
+
+    //msg!("vault {} at version {}", vault.key(), vault.version);
+    Ok(())
+}
@@ -1,29 +1,47 @@
+pub mod accept_bank_manager;
+pub mod accept_vault_owner;
 pub mod add_to_whitelist;
+pub mod cancel_bank_manager_transfer;
+pub mod cancel_vault_owner_transfer;
+pub mod close_bank;
+pub mod close_vault;
 pub mod deposit_gem;
 pub mod deposit_gem_pnft;
 pub mod init_bank;
 pub mod init_vault;
+pub mod migrate_bank_version;
+pub mod migrate_vault_version;
 pub mod record_rarity_points;
 pub mod remove_from_whitelist;
 pub mod set_bank_flags;
 pub mod set_vault_lock;
 pub mod shared;
+pub mod transfer_vault;
 pub mod update_bank_manager;
 pub mod update_vault_owner;
 pub mod withdraw_gem;
 pub mod withdraw_gem_pnft;
 pub mod withdraw_tokens_auth;
 
+pub use accept_bank_manager::*;
+pub use accept_vault_owner::*;
 pub use add_to_whitelist::*;
+pub use cancel_bank_manager_transfer::*;
+pub use cancel_vault_owner_transfer::*;
+pub use close_bank::*;
+pub use close_vault::*;
 pub use deposit_gem::*;
 pub use deposit_gem_pnft::*;
 pub use init_bank::*;
 pub use init_vault::*;
+pub use migrate_bank_version::*;
+pub use migrate_vault_version::*;
 pub use record_rarity_points::*;
 pub use remove_from_whitelist::*;
 pub use set_bank_flags::*;
 pub use set_vault_lock::*;
 pub use shared::*;
+pub use transfer_vault::*;
 pub use update_bank_manager::*;
 pub use update_vault_owner::*;
 pub use withdraw_gem::*;
@@ -17,13 +17,15 @@ use anchor_lang::prelude::*;
 use anchor_lang::solana_program::hash::hash;
 use anchor_lang::solana_program::program::invoke_signed;
 use anchor_lang::solana_program::system_instruction::create_account;
+use gem_common::{errors::ErrorCode, *};
 
-use crate::state::*;
+use crate::{events::*, state::*};
 
 #[derive(Accounts)]
 pub struct RecordRarityPoints<'info> {
     // bank
-    #[account(has_one = bank_manager)]
+    #[account(has_one = bank_manager,
+        constraint = bank.version == LATEST_BANK_VERSION @ ErrorCode::OutdatedAccountVersion)]
     pub bank: Box<Account<'info, Bank>>,
     pub bank_manager: Signer<'info>,
 
@@ -43,6 +45,7 @@ pub fn handler<'a, 'b, 'c, 'info>(
     rarity_configs: Vec<RarityConfig>,
 ) -> Result<()> {
     let remaining_accs = &mut ctx.remaining_accounts.iter();
+    let now_ts = now_ts()?;
 
     // the limiting factor here is actually not compute budget, but tx size client-side
     for config in rarity_configs.iter() {
@@ -81,6 +84,13 @@ pub fn handler<'a, 'b, 'c, 'info>(
         let mut gem_rarity_raw = gem_rarity.data.borrow_mut();
         gem_rarity_raw[..8].clone_from_slice(&disc.to_bytes()[..8]);
         gem_rarity_raw[8..10].clone_from_slice(&config.rarity_points.to_le_bytes());
+
+        emit!(RarityRecorded {
+            bank: ctx.accounts.bank.key(),
+            gem_mint: gem_mint.key(),
+            rarity_points: config.rarity_points,
+            ts: now_ts,
+        });
     }
 
     Ok(())
@@ -1,13 +1,14 @@
 use anchor_lang::prelude::*;
-use gem_common::*;
+use gem_common::{errors::ErrorCode, *};
 
-use crate::state::*;
+use crate::{events::*, state::*};
 
 #[derive(Accounts)]
 #[instruction(bump: u8)]
 pub struct RemoveFromWhitelist<'info> {
     // bank
-    #[account(mut, has_one = bank_manager)]
+    #[account(mut, has_one = bank_manager,
+        constraint = bank.version == LATEST_BANK_VERSION @ ErrorCode::OutdatedAccountVersion)]
     pub bank: Box<Account<'info, Bank>>,
     pub bank_manager: Signer<'info>,
     /// CHECK:
@@ -44,6 +45,13 @@ pub fn handler(ctx: Context<RemoveFromWhitelist>) -> Result<()> {
         &mut ctx.accounts.funds_receiver,
     )?;
 
+    emit!(WhitelistUpdated {
+        bank: ctx.accounts.bank.key(),
+        address: ctx.accounts.address_to_remove.key(),
+        whitelist_type: 0,
+        ts: now_ts()?,
+    });
+
     // msg!(
     //     "{} removed from whitelist",
     //     &ctx.accounts.address_to_remove.key()
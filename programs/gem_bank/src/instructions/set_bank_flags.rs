@@ -1,11 +1,13 @@
 use anchor_lang::prelude::*;
+use gem_common::errors::ErrorCode;
 
 use crate::state::*;
 
 #[derive(Accounts)]
 pub struct SetBankFlags<'info> {
     // bank
-    #[account(mut, has_one = bank_manager)]
+    #[account(mut, has_one = bank_manager,
+        constraint = bank.version == LATEST_BANK_VERSION @ ErrorCode::OutdatedAccountVersion)]
     pub bank: Box<Account<'info, Bank>>,
     pub bank_manager: Signer<'info>,
 }
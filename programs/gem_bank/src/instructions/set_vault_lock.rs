@@ -6,7 +6,8 @@ use crate::state::*;
 #[derive(Accounts)]
 pub struct SetVaultLock<'info> {
     // bank
-    #[account(has_one = bank_manager)]
+    #[account(has_one = bank_manager,
+        constraint = bank.version == LATEST_BANK_VERSION @ ErrorCode::OutdatedAccountVersion)]
     pub bank: Box<Account<'info, Bank>>,
     // vaults are locked / unlocked by THE MANAGER
     // (depositing / withdrawing doesn't require them)
@@ -15,7 +16,8 @@ pub struct SetVaultLock<'info> {
     // vault
     // not doing PDA verification because passing in creator tedious
     // this ix is designed for BM to execute, who by defn can pass in any vault
-    #[account(mut, has_one = bank)]
+    #[account(mut, has_one = bank,
+        constraint = vault.version == LATEST_VAULT_VERSION @ ErrorCode::OutdatedAccountVersion)]
     pub vault: Box<Account<'info, Vault>>,
 }
 
@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+use gem_common::{errors::ErrorCode, *};
+
+use crate::{instructions::calc_rarity_points, state::*};
+
+#[derive(Accounts)]
+pub struct TransferVault<'info> {
+    // bank
+    #[account(mut, has_one = bank_manager,
+        constraint = bank.version == LATEST_BANK_VERSION @ ErrorCode::OutdatedAccountVersion)]
+    pub bank: Box<Account<'info, Bank>>,
+    pub bank_manager: Signer<'info>,
+
+    // new bank
+    // both managers sign off - the new bank takes on gems it hasn't whitelisted itself
+    #[account(mut, constraint = new_bank.key() != bank.key(),
+        constraint = new_bank.bank_manager == new_bank_manager.key(),
+        constraint = new_bank.version == LATEST_BANK_VERSION @ ErrorCode::OutdatedAccountVersion)]
+    pub new_bank: Box<Account<'info, Bank>>,
+    pub new_bank_manager: Signer<'info>,
+
+    // vault
+    // same rationale for not verifying the PDA as in set_vault_lock
+    #[account(mut, has_one = bank,
+        constraint = vault.version == LATEST_VAULT_VERSION @ ErrorCode::OutdatedAccountVersion)]
+    pub vault: Box<Account<'info, Vault>>,
+    //
+    // remaining accounts, for EACH gem box in the vault:
+    // - gem_deposit_receipt
+    // - gem_rarity <- recorded on the NEW bank, pass the (empty) PDA if not recorded
+}
+
+/// gem boxes and GDRs are PDAs of the vault (not of the bank), so gems don't have to move -
+/// re-pointing the vault is enough. Rarity points however are recorded per bank and are recalculated
+pub fn handler<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, TransferVault<'info>>,
+) -> Result<()> {
+    let bank = &ctx.accounts.bank;
+    let new_bank = &ctx.accounts.new_bank;
+    let vault = &ctx.accounts.vault;
+
+    if Bank::read_flags(bank.flags)?.contains(BankFlags::FREEZE_VAULTS)
+        || Bank::read_flags(new_bank.flags)?.contains(BankFlags::FREEZE_VAULTS)
+    {
+        return Err(error!(ErrorCode::VaultAccessSuspended));
+    }
+
+    // every gem box has to be accounted for, otherwise we'd end up with wrong rarity points
+    if ctx.remaining_accounts.len() as u64 != vault.gem_box_count.try_mul(2)? {
+        return Err(error!(ErrorCode::GemBoxesMissing));
+    }
+
+    let mut seen_mints: Vec<Pubkey> = Vec::new();
+    let mut gem_count: u64 = 0;
+    let mut rarity_points: u64 = 0;
+
+    for accs in ctx.remaining_accounts.chunks(2) {
+        let gdr = Account::<GemDepositReceipt>::try_from(&accs[0])?;
+        let gem_rarity = &accs[1];
+
+        if gdr.vault != vault.key() || seen_mints.contains(&gdr.gem_mint) {
+            return Err(error!(ErrorCode::GemBoxesMissing));
+        }
+        seen_mints.push(gdr.gem_mint);
+
+        let (rarity_addr, _bump) = Pubkey::find_program_address(
+            &[
+                b"gem_rarity".as_ref(),
+                new_bank.key().as_ref(),
+                gdr.gem_mint.as_ref(),
+            ],
+            ctx.program_id,
+        );
+        if rarity_addr != gem_rarity.key() {
+            return Err(error!(ErrorCode::InvalidParameter));
+        }
+
+        gem_count.try_add_assign(gdr.gem_count)?;
+        rarity_points.try_add_assign(calc_rarity_points(gem_rarity, gdr.gem_count)?)?;
+    }
+
+    if gem_count != vault.gem_count {
+        return Err(error!(ErrorCode::GemBoxesMissing));
+    }
+
+    // re-point the vault
+    let vault = &mut ctx.accounts.vault;
+
+    vault.bank = new_bank.key();
+    vault.rarity_points = rarity_points;
+
+    // update banks
+    ctx.accounts.bank.vault_count.try_sub_assign(1)?;
+    ctx.accounts.new_bank.vault_count.try_add_assign(1)?;
+
+    //msg!("vault {} transferred to bank {}", vault.key(), new_bank.key());
+    Ok(())
+}
@@ -1,11 +1,13 @@
 use anchor_lang::prelude::*;
+use gem_common::errors::ErrorCode;
 
 use crate::state::*;
 
 #[derive(Accounts)]
 pub struct UpdateBankManager<'info> {
     // bank
-    #[account(mut, has_one = bank_manager)]
+    #[account(mut, has_one = bank_manager,
+        constraint = bank.version == LATEST_BANK_VERSION @ ErrorCode::OutdatedAccountVersion)]
     pub bank: Box<Account<'info, Bank>>,
     pub bank_manager: Signer<'info>,
 }
@@ -13,8 +15,9 @@ pub struct UpdateBankManager<'info> {
 pub fn handler(ctx: Context<UpdateBankManager>, new_manager: Pubkey) -> Result<()> {
     let bank = &mut ctx.accounts.bank;
 
-    bank.bank_manager = new_manager;
+    // only proposes the new manager, who then has to accept_bank_manager
+    bank.pending_bank_manager = new_manager;
 
-    //msg!("bank manager updated to: {}", new_manager);
+    //msg!("bank manager proposed: {}", new_manager);
     Ok(())
 }
@@ -6,11 +6,13 @@ use crate::state::*;
 #[derive(Accounts)]
 pub struct UpdateVaultOwner<'info> {
     // bank
+    #[account(constraint = bank.version == LATEST_BANK_VERSION @ ErrorCode::OutdatedAccountVersion)]
     pub bank: Box<Account<'info, Bank>>,
 
     // vault
     // same rationale for not verifying the PDA as in deposit
-    #[account(mut, has_one = bank, has_one = owner)]
+    #[account(mut, has_one = bank, has_one = owner,
+        constraint = vault.version == LATEST_VAULT_VERSION @ ErrorCode::OutdatedAccountVersion)]
     pub vault: Box<Account<'info, Vault>>,
     pub owner: Signer<'info>,
 }
@@ -23,8 +25,9 @@ pub fn handler(ctx: Context<UpdateVaultOwner>, new_owner: Pubkey) -> Result<()>
         return Err(error!(ErrorCode::VaultAccessSuspended));
     }
 
-    vault.owner = new_owner;
+    // only proposes the new owner, who then has to accept_vault_owner
+    vault.pending_owner = new_owner;
 
-    //msg!("owner updated to: {}", new_owner);
+    //msg!("owner proposed: {}", new_owner);
     Ok(())
 }
@@ -5,17 +5,19 @@ use anchor_spl::{
 };
 use gem_common::{errors::ErrorCode, *};
 
-use crate::*;
+use crate::{events::*, *};
 
 #[derive(Accounts)]
 #[instruction(bump_auth: u8, bump_gem_box: u8, bump_gdr: u8, bump_rarity: u8)]
 pub struct WithdrawGemPnft<'info> {
     // bank
+    #[account(constraint = bank.version == LATEST_BANK_VERSION @ ErrorCode::OutdatedAccountVersion)]
     pub bank: Box<Account<'info, Bank>>,
 
     // vault
     // same rationale for not verifying the PDA as in deposit
-    #[account(mut, has_one = bank, has_one = owner, has_one = authority)]
+    #[account(mut, has_one = bank, has_one = owner, has_one = authority,
+        constraint = vault.version == LATEST_VAULT_VERSION @ ErrorCode::OutdatedAccountVersion)]
     pub vault: Box<Account<'info, Vault>>,
     #[account(mut)]
     pub owner: Signer<'info>,
@@ -215,6 +217,16 @@ pub fn handler<'info>(
         .rarity_points
         .try_sub_assign(calc_rarity_points(&ctx.accounts.gem_rarity, amount)?)?;
 
+    emit!(GemWithdrawn {
+        bank: ctx.accounts.bank.key(),
+        vault: vault.key(),
+        gem_mint: ctx.accounts.gem_mint.key(),
+        amount,
+        vault_gem_count: vault.gem_count,
+        vault_rarity_points: vault.rarity_points,
+        ts: now_ts()?,
+    });
+
     //msg!("{} gems withdrawn from ${} gem box", amount, gem_box.key());
     Ok(())
 }
@@ -3,6 +3,7 @@ use instructions::*;
 
 declare_id!("bankHHdqMuaaST4qQk6mkzxGeKPHWmqdgor6Gs8r88m");
 
+pub mod events;
 pub mod instructions;
 pub mod state;
 
@@ -21,6 +22,12 @@ pub mod gem_bank {
         instructions::set_bank_flags::handler(ctx, flags)
     }
 
+    pub fn close_bank<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, CloseBank<'info>>,
+    ) -> Result<()> {
+        instructions::close_bank::handler(ctx)
+    }
+
     pub fn init_vault(ctx: Context<InitVault>, owner: Pubkey, name: String) -> Result<()> {
         instructions::init_vault::handler(ctx, owner, name)
     }
@@ -33,6 +40,24 @@ pub mod gem_bank {
         instructions::update_vault_owner::handler(ctx, new_owner)
     }
 
+    pub fn accept_vault_owner(ctx: Context<AcceptVaultOwner>) -> Result<()> {
+        instructions::accept_vault_owner::handler(ctx)
+    }
+
+    pub fn cancel_vault_owner_transfer(ctx: Context<CancelVaultOwnerTransfer>) -> Result<()> {
+        instructions::cancel_vault_owner_transfer::handler(ctx)
+    }
+
+    pub fn transfer_vault<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, TransferVault<'info>>,
+    ) -> Result<()> {
+        instructions::transfer_vault::handler(ctx)
+    }
+
+    pub fn close_vault(ctx: Context<CloseVault>) -> Result<()> {
+        instructions::close_vault::handler(ctx)
+    }
+
     pub fn deposit_gem<'info>(
         ctx: Context<'_, '_, '_, 'info, DepositGem<'info>>,
         _bump_auth: u8,
@@ -65,6 +90,14 @@ pub mod gem_bank {
         instructions::update_bank_manager::handler(ctx, new_manager)
     }
 
+    pub fn accept_bank_manager(ctx: Context<AcceptBankManager>) -> Result<()> {
+        instructions::accept_bank_manager::handler(ctx)
+    }
+
+    pub fn cancel_bank_manager_transfer(ctx: Context<CancelBankManagerTransfer>) -> Result<()> {
+        instructions::cancel_bank_manager_transfer::handler(ctx)
+    }
+
     pub fn record_rarity_points<'a, 'b, 'c, 'info>(
         ctx: Context<'a, 'b, 'c, 'info, RecordRarityPoints<'info>>,
         rarity_configs: Vec<RarityConfig>,
@@ -100,4 +133,12 @@ pub mod gem_bank {
     ) -> Result<()> {
         instructions::withdraw_gem_pnft::handler(ctx, amount, authorization_data, rules_acc_present)
     }
+
+    pub fn migrate_bank_version(ctx: Context<MigrateBankVersion>) -> Result<()> {
+        instructions::migrate_bank_version::handler(ctx)
+    }
+
+    pub fn migrate_vault_version(ctx: Context<MigrateVaultVersion>) -> Result<()> {
+        instructions::migrate_vault_version::handler(ctx)
+    }
 }
@@ -1,16 +1,20 @@
 use anchor_lang::prelude::*;
 use gem_common::errors::ErrorCode;
 
-pub const LATEST_BANK_VERSION: u16 = 0;
+pub const LATEST_BANK_VERSION: u16 = 1;
+
+/// room for growth allocated after the Bank struct since v1, see gem_common::load_extension
+pub const BANK_EXTENSION_SPACE: usize = 256;
 
 #[proc_macros::assert_size(120)] // +2 to make it /8
 #[repr(C)]
 #[account]
+#[derive(Debug)]
 pub struct Bank {
     pub version: u16,
 
     /// sole control over gem whitelist, un/locking the vaults, and bank flags
-    /// can update itself to another Pubkey
+    /// can hand itself over to another Pubkey, which then has to accept
     pub bank_manager: Pubkey,
 
     pub flags: u32,
@@ -25,11 +29,18 @@ pub struct Bank {
     /// total vault count registered with this bank
     pub vault_count: u64,
 
+    /// proposed by the current manager, takes over once it calls accept_bank_manager
+    pub pending_bank_manager: Pubkey,
+
     /// reserved for future updates, has to be /8
-    _reserved: [u8; 64],
+    _reserved: [u8; 32],
 }
 
 impl Bank {
+    pub const EXTENSION_OFFSET: usize = 8 + std::mem::size_of::<Bank>();
+
+    pub const SPACE: usize = Self::EXTENSION_OFFSET + BANK_EXTENSION_SPACE;
+
     pub fn read_flags(flags: u32) -> Result<BankFlags> {
         BankFlags::from_bits(flags).ok_or(error!(ErrorCode::InvalidParameter))
     }
@@ -2,9 +2,15 @@ use anchor_lang::prelude::*;
 
 use crate::state::{Bank, BankFlags};
 
+pub const LATEST_VAULT_VERSION: u16 = 1;
+
+/// room for growth allocated after the Vault struct since v1, see gem_common::load_extension
+pub const VAULT_EXTENSION_SPACE: usize = 128;
+
 #[proc_macros::assert_size(288)] // + 6 to make it /8
 #[repr(C)]
 #[account]
+#[derive(Debug)]
 pub struct Vault {
     /// each vault is registered with a single bank, used for indexing
     pub bank: Pubkey,
@@ -39,11 +45,21 @@ pub struct Vault {
     /// thus worst case, when rarities aren't enabled, this is == gem_count
     pub rarity_points: u64,
 
+    /// 0 for vaults created before versioning was introduced
+    pub version: u16,
+
     /// reserved for future updates, has to be /8
-    _reserved: [u8; 64],
+    _reserved: [u8; 30],
+
+    /// proposed by the current owner, takes over once it calls accept_vault_owner
+    pub pending_owner: Pubkey,
 }
 
 impl Vault {
+    pub const EXTENSION_OFFSET: usize = 8 + std::mem::size_of::<Vault>();
+
+    pub const SPACE: usize = Self::EXTENSION_OFFSET + VAULT_EXTENSION_SPACE;
+
     pub fn vault_seeds(&self) -> [&[u8]; 2] {
         [self.authority_seed.as_ref(), &self.authority_bump_seed]
     }
@@ -0,0 +1,137 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Farmer, FarmerState};
+
+// all ts are unix seconds, all farmer counts are totals after the change
+
+#[event]
+pub struct FarmInitialized {
+    pub farm: Pubkey,
+    pub farm_manager: Pubkey,
+    pub bank: Pubkey,
+    pub reward_a_mint: Pubkey,
+    pub reward_b_mint: Pubkey,
+    pub ts: u64,
+}
+
+/// stake, or flash_deposit on top of an existing stake
+#[event]
+pub struct FarmerStaked {
+    pub farm: Pubkey,
+    pub farmer: Pubkey,
+    pub gems_staked: u64,
+    pub rarity_points_staked: u64,
+    pub min_staking_ends_ts: u64,
+    pub ts: u64,
+}
+
+impl FarmerStaked {
+    pub fn new(farmer: &Account<Farmer>, ts: u64) -> Self {
+        Self {
+            farm: farmer.farm,
+            farmer: farmer.key(),
+            gems_staked: farmer.gems_staked,
+            rarity_points_staked: farmer.rarity_points_staked,
+            min_staking_ends_ts: farmer.min_staking_ends_ts,
+            ts,
+        }
+    }
+}
+
+/// every step on the way out: staked -> cooldown -> unstaked, or begin_flash_withdraw
+#[event]
+pub struct FarmerUnstaked {
+    pub farm: Pubkey,
+    pub farmer: Pubkey,
+    pub state: FarmerState,
+    pub gems_staked: u64,
+    pub rarity_points_staked: u64,
+    pub cooldown_ends_ts: u64,
+    /// unstaking fee + any instant unstake exit fee + the protocol fee charged, in lamports
+    pub fee_lamp: u64,
+    pub ts: u64,
+}
+
+impl FarmerUnstaked {
+    pub fn new(farmer: &Account<Farmer>, fee_lamp: u64, ts: u64) -> Self {
+        Self {
+            farm: farmer.farm,
+            farmer: farmer.key(),
+            state: farmer.state,
+            gems_staked: farmer.gems_staked,
+            rarity_points_staked: farmer.rarity_points_staked,
+            cooldown_ends_ts: farmer.cooldown_ends_ts,
+            fee_lamp,
+            ts,
+        }
+    }
+}
+
+#[event]
+pub struct RewardsClaimed {
+    pub farm: Pubkey,
+    pub farmer: Pubkey,
+    /// identity, claim delegate or cranker
+    pub claimed_by: Pubkey,
+    /// including the tips below
+    pub amount_a: u64,
+    pub amount_b: u64,
+    /// paid to the cranker, if cranked
+    pub tip_a: u64,
+    pub tip_b: u64,
+    pub ts: u64,
+}
+
+#[event]
+pub struct RewardFunded {
+    pub farm: Pubkey,
+    pub reward_mint: Pubkey,
+    pub funder: Pubkey,
+    pub amount: u64,
+    pub reward_end_ts: u64,
+    pub ts: u64,
+}
+
+#[event]
+pub struct RewardCancelled {
+    pub farm: Pubkey,
+    pub reward_mint: Pubkey,
+    pub amount_refunded: u64,
+    pub ts: u64,
+}
+
+#[event]
+pub struct RewardLocked {
+    pub farm: Pubkey,
+    pub reward_mint: Pubkey,
+    pub lock_end_ts: u64,
+    pub ts: u64,
+}
+
+/// by audit_farm when an invariant fails, or by the reward admin via set_funding_paused
+#[event]
+pub struct FundingPauseUpdated {
+    pub farm: Pubkey,
+    pub paused: bool,
+    /// the audit error that triggered the pause, 0 if set by the reward admin
+    pub audit_error_code: u32,
+    pub ts: u64,
+}
+
+#[event]
+pub struct DustSwept {
+    pub farm: Pubkey,
+    pub reward_mint: Pubkey,
+    pub amount: u64,
+    pub ts: u64,
+}
+
+#[event]
+pub struct SurplusRescued {
+    pub farm: Pubkey,
+    pub reward_mint: Pubkey,
+    pub amount: u64,
+    /// false = withdrawn
+    pub rolled_into_funding: bool,
+    pub ts: u64,
+}
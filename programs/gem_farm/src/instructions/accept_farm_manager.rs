@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+use gem_common::errors::ErrorCode;
+
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct AcceptFarmManager<'info> {
+    // farm
+    #[account(mut,
+        constraint = farm.version == LATEST_FARM_VERSION @ ErrorCode::OutdatedAccountVersion)]
+    pub farm: Box<Account<'info, Farm>>,
+    pub new_farm_manager: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<AcceptFarmManager>) -> Result<()> {
+    let farm = &mut ctx.accounts.farm;
+    let farm_info = farm.to_account_info();
+    let new_farm_manager = ctx.accounts.new_farm_manager.key();
+
+    let mut extension = Farm::extension(&farm_info)?;
+    if extension.pending_farm_manager != new_farm_manager {
+        return Err(error!(ErrorCode::NotPendingOwner));
+    }
+
+    farm.farm_manager = new_farm_manager;
+    extension.pending_farm_manager = Pubkey::default();
+    Farm::set_extension(&farm_info, &extension)?;
+
+    msg!("farm manager updated to: {}", new_farm_manager);
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct AddFeeExemption<'info> {
+    #[account(has_one = admin)]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    // farm - not checked so that a farm can be exempted before it's created (init_farm is charged too)
+    /// CHECK:
+    pub farm: AccountInfo<'info>,
+    #[account(init, seeds = [
+            b"fee_exemption".as_ref(),
+            farm.key().as_ref(),
+        ],
+        bump,
+        payer = admin,
+        space = 8 + std::mem::size_of::<FeeExemption>())]
+    fee_exemption: Box<Account<'info, FeeExemption>>,
+
+    // misc
+    system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<AddFeeExemption>) -> Result<()> {
+    let fee_exemption = &mut ctx.accounts.fee_exemption;
+    fee_exemption.farm = ctx.accounts.farm.key();
+
+    msg!("farm {} is now fee exempt", fee_exemption.farm);
+    Ok(())
+}
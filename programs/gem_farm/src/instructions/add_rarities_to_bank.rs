@@ -2,6 +2,7 @@ use anchor_lang::prelude::*;
 use gem_bank::{
     self, cpi::accounts::RecordRarityPoints, instructions::RarityConfig, program::GemBank,
 };
+use gem_common::errors::ErrorCode;
 
 use crate::state::*;
 
@@ -9,8 +10,11 @@ use crate::state::*;
 #[instruction(bump_auth: u8)]
 pub struct AddRaritiesToBank<'info> {
     // farm
-    #[account(has_one = farm_manager, has_one = farm_authority, has_one = bank)]
+    #[account(has_one = farm_authority, has_one = bank,
+        constraint = farm.version == LATEST_FARM_VERSION @ ErrorCode::OutdatedAccountVersion,
+        constraint = farm.has_role(&farm.to_account_info(), FarmRole::RarityAdmin, farm_manager.key())? @ ErrorCode::MissingRole)]
     pub farm: Box<Account<'info, Farm>>,
+    // the farm manager, or the rarity admin
     #[account(mut)]
     pub farm_manager: Signer<'info>,
     /// CHECK:
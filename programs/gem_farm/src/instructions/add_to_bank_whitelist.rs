@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
 use gem_bank::{self, cpi::accounts::AddToWhitelist, program::GemBank, state::Bank};
+use gem_common::errors::ErrorCode;
 
 use crate::state::*;
 
@@ -7,8 +8,11 @@ use crate::state::*;
 #[instruction(bump_auth: u8)]
 pub struct AddToBankWhitelist<'info> {
     // farm
-    #[account(has_one = farm_manager, has_one = farm_authority, has_one = bank)]
+    #[account(has_one = farm_authority, has_one = bank,
+        constraint = farm.version == LATEST_FARM_VERSION @ ErrorCode::OutdatedAccountVersion,
+        constraint = farm.has_role(&farm.to_account_info(), FarmRole::WhitelistAdmin, farm_manager.key())? @ ErrorCode::MissingRole)]
     pub farm: Box<Account<'info, Farm>>,
+    // the farm manager, or the whitelist admin
     #[account(mut)]
     pub farm_manager: Signer<'info>,
     /// CHECK:
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use gem_common::{errors::ErrorCode, *};
+
+use crate::state::*;
+
+#[derive(Accounts)]
+#[instruction(bump: u8)]
+pub struct ApplyFarmConfig<'info> {
+    // farm
+    #[account(mut, has_one = farm_manager,
+        constraint = farm.version == LATEST_FARM_VERSION @ ErrorCode::OutdatedAccountVersion)]
+    pub farm: Box<Account<'info, Farm>>,
+    // receives the rent back, doesn't have to sign - applying is permissionless
+    /// CHECK:
+    #[account(mut)]
+    pub farm_manager: AccountInfo<'info>,
+
+    // pending config
+    #[account(mut, has_one = farm, seeds = [
+            b"pending_config".as_ref(),
+            farm.key().as_ref(),
+        ],
+        bump = bump)]
+    pub pending_farm_config: Box<Account<'info, PendingFarmConfig>>,
+}
+
+pub fn handler(ctx: Context<ApplyFarmConfig>) -> Result<()> {
+    let farm = &mut ctx.accounts.farm;
+    let pending = &ctx.accounts.pending_farm_config;
+
+    if now_ts()? < pending.applies_ts {
+        return Err(error!(ErrorCode::ConfigChangeNotDue));
+    }
+
+    farm.config = pending.config;
+    farm.max_counts = pending.max_counts;
+    farm.instant_unstake_fee_lamp_per_sec = pending.instant_unstake_fee_lamp_per_sec;
+    farm.set_config_delay_sec(pending.config_delay_sec)?;
+
+    let farm_info = farm.to_account_info();
+    let mut extension = Farm::extension(&farm_info)?;
+    extension.token_fees = pending.token_fees;
+    Farm::set_extension(&farm_info, &extension)?;
+
+    close_account(
+        &mut ctx.accounts.pending_farm_config.to_account_info(),
+        &mut ctx.accounts.farm_manager.to_account_info(),
+    )?;
+
+    msg!("farm config applied");
+    Ok(())
+}
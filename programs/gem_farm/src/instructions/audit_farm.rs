@@ -0,0 +1,394 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use gem_common::{errors::ErrorCode, *};
+
+use crate::{events::*, state::*};
+
+#[derive(Accounts)]
+pub struct AuditFarm<'info> {
+    // farm
+    #[account(mut,
+        constraint = farm.version == LATEST_FARM_VERSION @ ErrorCode::OutdatedAccountVersion)]
+    pub farm: Box<Account<'info, Farm>>,
+
+    // reward pots
+    #[account(address = farm.reward_a.reward_pot)]
+    pub reward_a_pot: Box<Account<'info, TokenAccount>>,
+    #[account(address = farm.reward_b.reward_pot)]
+    pub reward_b_pot: Box<Account<'info, TokenAccount>>,
+    //
+    // remaining accounts, any number of the farm's farmers, each at most once:
+    //   pub farmer: Box<Account<'info, Farmer>>,
+    // a subset can't add up to more than the farm's staked counts, all of them have to match exactly
+}
+
+/// permissionless - meant to be simulated continuously by monitoring bots.
+/// Fails on the first broken invariant, or pauses funding instead if pause_on_failure is set
+pub fn handler<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, AuditFarm<'info>>,
+    pause_on_failure: bool,
+) -> Result<()> {
+    let now_ts = now_ts()?;
+    let pot_balances = [
+        ctx.accounts.reward_a_pot.amount,
+        ctx.accounts.reward_b_pot.amount,
+    ];
+
+    let violation = audit(
+        &ctx.accounts.farm,
+        pot_balances,
+        ctx.remaining_accounts,
+        now_ts,
+    )?;
+
+    settle(&mut ctx.accounts.farm, violation, pause_on_failure, now_ts)
+}
+
+fn settle(
+    farm: &mut Account<Farm>,
+    violation: Option<ErrorCode>,
+    pause_on_failure: bool,
+    now_ts: u64,
+) -> Result<()> {
+    let error_code = match violation {
+        None => return Ok(()),
+        Some(error_code) => error_code,
+    };
+
+    if !pause_on_failure {
+        return Err(error!(error_code));
+    }
+
+    // only ever reached on an actual violation, so it's safe to let anyone pause
+    farm.funding_paused = true;
+
+    emit!(FundingPauseUpdated {
+        farm: farm.key(),
+        paused: true,
+        audit_error_code: error_code.into(),
+        ts: now_ts,
+    });
+
+    Ok(())
+}
+
+/// runs against copies of the farm + farmers brought up to now, like quote_farmer_rewards.
+/// Bad accounts are returned as errors, broken invariants as Some(violation)
+fn audit<'info>(
+    farm: &Account<'info, Farm>,
+    pot_balances: [u64; 2],
+    farmers: &[AccountInfo<'info>],
+    now_ts: u64,
+) -> Result<Option<ErrorCode>> {
+    let mut farm = farm.clone();
+
+    let mut farmer_keys = vec![];
+    let mut staked_farmer_count: u64 = 0;
+    let mut gems_staked: u64 = 0;
+    let mut rarity_points_staked: u64 = 0;
+    let mut outstanding_a: u64 = 0;
+    let mut outstanding_b: u64 = 0;
+
+    for info in farmers.iter() {
+        let mut farmer = Account::<Farmer>::try_from(info)?;
+        require!(farmer.farm == farm.key(), ErrorCode::InvalidParameter);
+        farmer_keys.push(farmer.key());
+
+        farm.update_rewards(now_ts, Some(&mut farmer), false)?;
+
+        outstanding_a.try_add_assign(farmer.reward_a.outstanding_reward()?)?;
+        outstanding_b.try_add_assign(farmer.reward_b.outstanding_reward()?)?;
+
+        if farmer.state == FarmerState::Staked {
+            staked_farmer_count.try_add_assign(1)?;
+            gems_staked.try_add_assign(farmer.gems_staked)?;
+            rarity_points_staked.try_add_assign(farmer.rarity_points_staked)?;
+        }
+    }
+    farm.update_rewards(now_ts, None, false)?;
+
+    farmer_keys.sort();
+    farmer_keys.dedup();
+    require!(
+        farmer_keys.len() == farmers.len(),
+        ErrorCode::InvalidParameter
+    );
+
+    // rewards
+    let rewards = [
+        ("a", &farm.reward_a, pot_balances[0], outstanding_a),
+        ("b", &farm.reward_b, pot_balances[1], outstanding_b),
+    ];
+    for (name, reward, pot_balance, outstanding) in rewards {
+        let pending = reward.funds.pending_amount()?;
+
+        // the farm-wide counter covers every farmer ever credited, the sum covers farms
+        // that were already running before it was tracked
+        let outstanding = std::cmp::max(outstanding, reward.outstanding_liability);
+
+        if reward.fixed_rate.reserved_amount > pending {
+            msg!(
+                "reward {}: reserved {} > pending {}",
+                name,
+                reward.fixed_rate.reserved_amount,
+                pending
+            );
+            return Ok(Some(ErrorCode::ReservedExceedsPending));
+        }
+
+        if pending.try_add(outstanding)? > pot_balance {
+            msg!(
+                "reward {}: pending {} + outstanding {} > pot {}",
+                name,
+                pending,
+                outstanding,
+                pot_balance
+            );
+            return Ok(Some(ErrorCode::RewardPotInsolvent));
+        }
+    }
+
+    // counts
+    let all_farmers = farmer_keys.len() as u64 == farm.farmer_count;
+    let counts = [
+        (staked_farmer_count, farm.staked_farmer_count),
+        (gems_staked, farm.gems_staked),
+        (rarity_points_staked, farm.rarity_points_staked),
+    ];
+    if counts
+        .iter()
+        .any(|&(sum, total)| sum > total || (all_farmers && sum != total))
+    {
+        msg!(
+            "staked farmers {}/{}, gems {}/{}, rarity points {}/{}",
+            staked_farmer_count,
+            farm.staked_farmer_count,
+            gems_staked,
+            farm.gems_staked,
+            rarity_points_staked,
+            farm.rarity_points_staked
+        );
+        return Ok(Some(ErrorCode::StakedCountsMismatch));
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn zeroed<T: AccountDeserialize + anchor_lang::Discriminator>() -> T {
+        let mut data = T::discriminator().to_vec();
+        data.resize(8 + 2000, 0);
+        T::try_deserialize(&mut &data[..]).unwrap()
+    }
+
+    fn serialized<T: AccountSerialize>(account: &T) -> Vec<u8> {
+        let mut data = vec![];
+        account.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    /// 2 farmers, only the first one staked: 3 gems worth 5 rarity points
+    fn farm_and_farmers(farm_key: Pubkey) -> (Farm, Vec<Farmer>) {
+        let mut farm: Farm = zeroed();
+        farm.farmer_count = 2;
+        farm.staked_farmer_count = 1;
+        farm.gems_staked = 3;
+        farm.rarity_points_staked = 5;
+
+        let farmers = (0..2)
+            .map(|i| {
+                let mut farmer: Farmer = zeroed();
+                farmer.farm = farm_key;
+                farmer.identity = Pubkey::new_unique();
+                if i == 0 {
+                    farmer.state = FarmerState::Staked;
+                    farmer.gems_staked = 3;
+                    farmer.rarity_points_staked = 5;
+                }
+                farmer
+            })
+            .collect();
+
+        (farm, farmers)
+    }
+
+    /// audits the farm against the farmers at the given indices (repeats allowed)
+    fn run_audit(
+        farm: &Farm,
+        farm_key: Pubkey,
+        farmers: &[Farmer],
+        indices: &[usize],
+        pot_balances: [u64; 2],
+    ) -> Result<Option<ErrorCode>> {
+        let farmer_keys: Vec<Pubkey> = farmers.iter().map(|_| Pubkey::new_unique()).collect();
+        let mut farm_lamports = 0;
+        let mut farm_data = serialized(farm);
+        let farm_info = AccountInfo::new(
+            &farm_key,
+            false,
+            true,
+            &mut farm_lamports,
+            &mut farm_data,
+            &crate::ID,
+            false,
+            0,
+        );
+        let farm = Account::<Farm>::try_from(&farm_info)?;
+
+        let mut lamports = vec![0; farmers.len()];
+        let mut data: Vec<Vec<u8>> = farmers.iter().map(serialized).collect();
+        let infos: Vec<AccountInfo> = farmer_keys
+            .iter()
+            .zip(lamports.iter_mut())
+            .zip(data.iter_mut())
+            .map(|((key, lamports), data)| {
+                AccountInfo::new(key, false, true, lamports, data, &crate::ID, false, 0)
+            })
+            .collect();
+        let supplied: Vec<AccountInfo> = indices.iter().map(|&i| infos[i].clone()).collect();
+
+        audit(&farm, pot_balances, &supplied, 100)
+    }
+
+    #[test]
+    fn test_audit_farm_healthy() {
+        let farm_key = Pubkey::new_unique();
+        let (farm, farmers) = farm_and_farmers(farm_key);
+
+        for indices in [&[0, 1][..], &[1, 0], &[0], &[1], &[]] {
+            assert!(run_audit(&farm, farm_key, &farmers, indices, [0, 0])
+                .unwrap()
+                .is_none());
+        }
+    }
+
+    #[test]
+    fn test_audit_farm_reserved_exceeds_pending() {
+        let farm_key = Pubkey::new_unique();
+        let (mut farm, farmers) = farm_and_farmers(farm_key);
+        farm.reward_b.funds.total_funded = 100;
+        farm.reward_b.fixed_rate.reserved_amount = 100;
+        assert!(run_audit(&farm, farm_key, &farmers, &[0, 1], [0, 100])
+            .unwrap()
+            .is_none());
+
+        farm.reward_b.fixed_rate.reserved_amount = 101;
+        assert!(matches!(
+            run_audit(&farm, farm_key, &farmers, &[0, 1], [0, 1000]).unwrap(),
+            Some(ErrorCode::ReservedExceedsPending)
+        ));
+    }
+
+    #[test]
+    fn test_audit_farm_reward_pot_insolvent() {
+        let farm_key = Pubkey::new_unique();
+        let (mut farm, mut farmers) = farm_and_farmers(farm_key);
+
+        // pending funds not covered
+        farm.reward_a.funds.total_funded = 100;
+        assert!(run_audit(&farm, farm_key, &farmers, &[0, 1], [100, 0])
+            .unwrap()
+            .is_none());
+        assert!(matches!(
+            run_audit(&farm, farm_key, &farmers, &[0, 1], [99, 0]).unwrap(),
+            Some(ErrorCode::RewardPotInsolvent)
+        ));
+
+        // rewards still owed to a supplied farmer not covered
+        farmers[1].reward_a.accrued_reward = 10;
+        assert!(run_audit(&farm, farm_key, &farmers, &[0, 1], [110, 0])
+            .unwrap()
+            .is_none());
+        assert!(matches!(
+            run_audit(&farm, farm_key, &farmers, &[0, 1], [109, 0]).unwrap(),
+            Some(ErrorCode::RewardPotInsolvent)
+        ));
+
+        // rewards owed to farmers that weren't supplied, via the farm-wide counter
+        farm.reward_a.outstanding_liability = 20;
+        assert!(matches!(
+            run_audit(&farm, farm_key, &farmers, &[0], [119, 0]).unwrap(),
+            Some(ErrorCode::RewardPotInsolvent)
+        ));
+    }
+
+    #[test]
+    fn test_audit_farm_staked_counts_mismatch() {
+        let farm_key = Pubkey::new_unique();
+        let (mut farm, farmers) = farm_and_farmers(farm_key);
+
+        // a subset may add up to less than the farm's counts, all the farmers have to match
+        farm.gems_staked = 4;
+        assert!(run_audit(&farm, farm_key, &farmers, &[0], [0, 0])
+            .unwrap()
+            .is_none());
+        assert!(matches!(
+            run_audit(&farm, farm_key, &farmers, &[0, 1], [0, 0]).unwrap(),
+            Some(ErrorCode::StakedCountsMismatch)
+        ));
+
+        // but never to more
+        farm.gems_staked = 3;
+        farm.rarity_points_staked = 4;
+        assert!(matches!(
+            run_audit(&farm, farm_key, &farmers, &[0], [0, 0]).unwrap(),
+            Some(ErrorCode::StakedCountsMismatch)
+        ));
+
+        farm.rarity_points_staked = 5;
+        farm.staked_farmer_count = 2;
+        assert!(matches!(
+            run_audit(&farm, farm_key, &farmers, &[0, 1], [0, 0]).unwrap(),
+            Some(ErrorCode::StakedCountsMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_audit_farm_bad_farmers() {
+        let farm_key = Pubkey::new_unique();
+        let (farm, mut farmers) = farm_and_farmers(farm_key);
+
+        // the same farmer twice would double count
+        assert!(run_audit(&farm, farm_key, &farmers, &[0, 0], [0, 0]).is_err());
+        assert!(run_audit(&farm, farm_key, &farmers, &[0, 1, 1], [0, 0]).is_err());
+
+        // farmer of another farm
+        farmers[1].farm = Pubkey::new_unique();
+        assert!(run_audit(&farm, farm_key, &farmers, &[0, 1], [0, 0]).is_err());
+    }
+
+    #[test]
+    fn test_audit_farm_pause_on_failure() {
+        let farm_key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = serialized(&zeroed::<Farm>());
+        let info = AccountInfo::new(
+            &farm_key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &crate::ID,
+            false,
+            0,
+        );
+        let mut farm = Account::<Farm>::try_from(&info).unwrap();
+
+        // nothing to report
+        settle(&mut farm, None, true, 100).unwrap();
+        settle(&mut farm, None, false, 100).unwrap();
+        assert!(!farm.funding_paused);
+
+        // violation without pausing fails the whole tx
+        let violation = Some(ErrorCode::RewardPotInsolvent);
+        assert!(settle(&mut farm, violation, false, 100).is_err());
+        assert!(!farm.funding_paused);
+
+        // with pausing it succeeds, so that the pause sticks
+        settle(&mut farm, violation, true, 100).unwrap();
+        assert!(farm.funding_paused);
+    }
+}
@@ -1,14 +1,15 @@
 use anchor_lang::prelude::*;
 use anchor_lang::Discriminator;
 use arrayref::array_ref;
-use gem_common::*;
+use gem_common::{errors::ErrorCode, *};
 
 use crate::state::*;
 
 #[derive(Accounts)]
 pub struct AuthorizeFunder<'info> {
     // farm
-    #[account(mut, has_one = farm_manager)]
+    #[account(mut, has_one = farm_manager,
+        constraint = farm.version == LATEST_FARM_VERSION @ ErrorCode::OutdatedAccountVersion)]
     pub farm: Box<Account<'info, Farm>>,
     #[account(mut)]
     pub farm_manager: Signer<'info>,
@@ -30,7 +31,7 @@ pub struct AuthorizeFunder<'info> {
     system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<AuthorizeFunder>) -> Result<()> {
+pub fn handler(ctx: Context<AuthorizeFunder>, limits: FundingLimits) -> Result<()> {
     // fix missing discriminator check
     {
         let acct = ctx.accounts.authorization_proof.to_account_info();
@@ -44,14 +45,18 @@ pub fn handler(ctx: Context<AuthorizeFunder>) -> Result<()> {
 
     // create/update authorization proof
     let proof = &mut ctx.accounts.authorization_proof;
+    let is_new = proof.farm == Pubkey::default();
 
     proof.authorized_funder = ctx.accounts.funder_to_authorize.key();
     proof.farm = ctx.accounts.farm.key();
+    proof.set_limits(limits);
 
-    // update farm
-    let farm = &mut ctx.accounts.farm;
+    // update farm - re-authorizing only updates the limits
+    if is_new {
+        let farm = &mut ctx.accounts.farm;
 
-    farm.authorized_funder_count.try_add_assign(1)?;
+        farm.authorized_funder_count.try_add_assign(1)?;
+    }
 
     msg!(
         "funder authorized: {}",
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use gem_common::{errors::ErrorCode, *};
+
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct AuthorizeMigration<'info> {
+    // farm being left
+    #[account(mut, constraint = from_farm.farm_manager == from_farm_manager.key(),
+        constraint = from_farm.version == LATEST_FARM_VERSION @ ErrorCode::OutdatedAccountVersion)]
+    pub from_farm: Box<Account<'info, Farm>>,
+    #[account(mut)]
+    pub from_farm_manager: Signer<'info>,
+
+    // farm being joined
+    // has to sign off too - it takes on gems its bank hasn't whitelisted itself
+    #[account(constraint = to_farm.key() != from_farm.key(),
+        constraint = to_farm.farm_manager == to_farm_manager.key(),
+        constraint = to_farm.version == LATEST_FARM_VERSION @ ErrorCode::OutdatedAccountVersion)]
+    pub to_farm: Box<Account<'info, Farm>>,
+    pub to_farm_manager: Signer<'info>,
+
+    // migration
+    #[account(init, seeds = [
+            b"migration".as_ref(),
+            from_farm.key().as_ref(),
+            to_farm.key().as_ref(),
+        ],
+        bump,
+        payer = from_farm_manager,
+        space = 8 + std::mem::size_of::<MigrationProof>())]
+    migration_proof: Box<Account<'info, MigrationProof>>,
+
+    // misc
+    system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<AuthorizeMigration>) -> Result<()> {
+    let proof = &mut ctx.accounts.migration_proof;
+
+    proof.from_farm = ctx.accounts.from_farm.key();
+    proof.to_farm = ctx.accounts.to_farm.key();
+
+    let from_farm = &mut ctx.accounts.from_farm;
+    from_farm.migration_proof_count.try_add_assign(1)?;
+
+    msg!(
+        "migration authorized: {} -> {}",
+        proof.from_farm,
+        proof.to_farm
+    );
+    Ok(())
+}
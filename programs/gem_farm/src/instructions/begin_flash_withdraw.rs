@@ -0,0 +1,177 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{program::invoke, system_instruction},
+};
+use gem_bank::{
+    self,
+    instructions::calc_rarity_points,
+    program::GemBank,
+    state::{Bank, GemDepositReceipt, Vault},
+};
+use gem_common::{errors::ErrorCode, *};
+
+use crate::{events::*, instructions::collect_token_fee, state::*};
+
+#[derive(Accounts)]
+#[instruction(bump_treasury: u8, bump_farmer: u8, bump_rarity: u8)]
+pub struct BeginFlashWithdraw<'info> {
+    // farm
+    #[account(mut, has_one = farm_treasury, has_one = bank,
+        constraint = farm.version == LATEST_FARM_VERSION @ ErrorCode::OutdatedAccountVersion)]
+    pub farm: Box<Account<'info, Farm>>,
+    /// CHECK:
+    #[account(mut, seeds = [b"treasury".as_ref(), farm.key().as_ref()], bump = bump_treasury)]
+    pub farm_treasury: AccountInfo<'info>,
+
+    // farmer
+    #[account(mut, has_one = farm, has_one = identity, has_one = vault,
+        seeds = [
+            b"farmer".as_ref(),
+            farm.key().as_ref(),
+            identity.key().as_ref(),
+        ],
+        bump = bump_farmer,
+        constraint = Farmer::version(&farmer.to_account_info())? == LATEST_FARMER_VERSION @ ErrorCode::OutdatedAccountVersion)]
+    pub farmer: Box<Account<'info, Farmer>>,
+    #[account(mut)]
+    pub identity: Signer<'info>,
+
+    // gems - nothing leaves the vault yet, see flash_withdraw
+    pub bank: Box<Account<'info, Bank>>,
+    pub vault: Box<Account<'info, Vault>>,
+    #[account(has_one = vault, has_one = gem_mint)]
+    pub gem_deposit_receipt: Box<Account<'info, GemDepositReceipt>>,
+    /// CHECK:
+    pub gem_mint: AccountInfo<'info>,
+    /// CHECK:
+    #[account(seeds = [
+            b"gem_rarity".as_ref(),
+            bank.key().as_ref(),
+            gem_mint.key().as_ref()
+        ],
+        bump = bump_rarity,
+        seeds::program = gem_bank.key())]
+    pub gem_rarity: AccountInfo<'info>,
+    pub gem_bank: Program<'info, GemBank>,
+
+    // misc
+    // only one can ever exist (see init_protocol_config), so no need to check seeds
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+    /// CHECK:
+    #[account(mut, address = protocol_config.fee_wallet)]
+    pub fee_acc: AccountInfo<'info>,
+    // only exists if the farm doesn't pay protocol fees (see add_fee_exemption)
+    /// CHECK:
+    #[account(seeds = [b"fee_exemption".as_ref(), farm.key().as_ref()], bump)]
+    pub fee_exemption: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> BeginFlashWithdraw<'info> {
+    fn pay_treasury(&self, lamports: u64) -> Result<()> {
+        invoke(
+            &system_instruction::transfer(self.identity.key, self.farm_treasury.key, lamports),
+            &[
+                self.identity.to_account_info(),
+                self.farm_treasury.clone(),
+                self.system_program.to_account_info(),
+            ],
+        )
+        .map_err(Into::into)
+    }
+
+    /// returns the protocol fee actually charged
+    fn transfer_fee(&self) -> Result<u64> {
+        let fee = self.protocol_config.fee_for(
+            &self.fee_exemption,
+            self.protocol_config.fees.flash_withdraw_lamp,
+        );
+        if fee == 0 {
+            return Ok(0);
+        }
+
+        invoke(
+            &system_instruction::transfer(self.identity.key, self.fee_acc.key, fee),
+            &[
+                self.identity.to_account_info(),
+                self.fee_acc.clone(),
+                self.system_program.to_account_info(),
+            ],
+        )?;
+
+        Ok(fee)
+    }
+}
+
+pub fn handler<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, BeginFlashWithdraw<'info>>,
+    amount: u64,
+) -> Result<()> {
+    // one withdrawal at a time, so the vault never holds back more than a single pending one
+    let farmer_info = ctx.accounts.farmer.to_account_info();
+    if Farmer::pending_withdrawal(&farmer_info)?.amount > 0 {
+        return Err(error!(ErrorCode::WithdrawalPending));
+    }
+
+    if amount == 0 || amount > ctx.accounts.gem_deposit_receipt.gem_count {
+        return Err(error!(ErrorCode::InvalidParameter));
+    }
+
+    // same fees as a regular unstake
+    let farm = &ctx.accounts.farm;
+    let unstaking_fee = farm.config.unstaking_fee_lamp;
+    if unstaking_fee > 0 {
+        ctx.accounts.pay_treasury(unstaking_fee)?
+    }
+
+    let unstake_fee = Farm::extension(&farm.to_account_info())?
+        .token_fees
+        .unstake_fee;
+    collect_token_fee(
+        farm,
+        unstake_fee,
+        &ctx.accounts.identity.to_account_info(),
+        ctx.remaining_accounts,
+    )?;
+
+    // update accrued rewards BEFORE we decrement the stake
+    let farm = &mut ctx.accounts.farm;
+    let farmer = &mut ctx.accounts.farmer;
+    let vault = &ctx.accounts.vault;
+    let now_ts = now_ts()?;
+
+    farm.update_rewards(now_ts, Some(farmer), true)?;
+
+    // the withdrawn gems stop earning now, but stay in the vault for the cooldown
+    let withdrawn_rarity = calc_rarity_points(&ctx.accounts.gem_rarity, amount)?;
+    farm.unstake_some_gems(
+        now_ts,
+        vault.gem_count.try_sub(amount)?,
+        vault.rarity_points.try_sub(withdrawn_rarity)?,
+        amount,
+        withdrawn_rarity,
+        farmer,
+    )?;
+
+    Farmer::set_pending_withdrawal(
+        &farmer_info,
+        PendingWithdrawal {
+            gem_mint: ctx.accounts.gem_mint.key(),
+            amount,
+            rarity_points: withdrawn_rarity,
+            releases_ts: farmer.cooldown_ends_ts,
+        },
+    )?;
+
+    //collect a fee for unstaking
+    let protocol_fee = ctx.accounts.transfer_fee()?;
+
+    emit!(FarmerUnstaked::new(
+        &ctx.accounts.farmer,
+        unstaking_fee.try_add(protocol_fee)?,
+        now_ts
+    ));
+
+    // msg!("{} gems pending withdrawal for {}", amount, farmer.key());
+    Ok(())
+}
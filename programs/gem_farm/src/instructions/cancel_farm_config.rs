@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+use gem_common::{errors::ErrorCode, *};
+
+use crate::state::*;
+
+#[derive(Accounts)]
+#[instruction(bump: u8)]
+pub struct CancelFarmConfig<'info> {
+    // farm
+    #[account(has_one = farm_manager,
+        constraint = farm.version == LATEST_FARM_VERSION @ ErrorCode::OutdatedAccountVersion)]
+    pub farm: Box<Account<'info, Farm>>,
+    #[account(mut)]
+    pub farm_manager: Signer<'info>,
+
+    // pending config
+    #[account(mut, has_one = farm, seeds = [
+            b"pending_config".as_ref(),
+            farm.key().as_ref(),
+        ],
+        bump = bump)]
+    pub pending_farm_config: Box<Account<'info, PendingFarmConfig>>,
+}
+
+pub fn handler(ctx: Context<CancelFarmConfig>) -> Result<()> {
+    close_account(
+        &mut ctx.accounts.pending_farm_config.to_account_info(),
+        &mut ctx.accounts.farm_manager.to_account_info(),
+    )?;
+
+    msg!("farm config proposal cancelled");
+    Ok(())
+}
@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+use gem_common::errors::ErrorCode;
+
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct CancelFarmManagerTransfer<'info> {
+    // farm
+    #[account(mut, has_one = farm_manager,
+        constraint = farm.version == LATEST_FARM_VERSION @ ErrorCode::OutdatedAccountVersion)]
+    pub farm: Box<Account<'info, Farm>>,
+    pub farm_manager: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<CancelFarmManagerTransfer>) -> Result<()> {
+    let farm_info = ctx.accounts.farm.to_account_info();
+
+    let mut extension = Farm::extension(&farm_info)?;
+    extension.pending_farm_manager = Pubkey::default();
+    Farm::set_extension(&farm_info, &extension)?;
+
+    msg!("farm manager transfer cancelled");
+    Ok(())
+}
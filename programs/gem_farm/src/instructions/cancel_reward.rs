@@ -3,16 +3,19 @@ use anchor_spl::{
     associated_token::AssociatedToken,
     token::{self, Mint, Token, TokenAccount, Transfer},
 };
-use gem_common::*;
+use gem_common::{errors::ErrorCode, *};
 
-use crate::state::*;
+use crate::{events::*, state::*};
 
 #[derive(Accounts)]
 #[instruction(bump_auth: u8, bump_pot: u8)]
 pub struct CancelReward<'info> {
     // farm
-    #[account(mut, has_one = farm_manager, has_one = farm_authority)]
+    #[account(mut, has_one = farm_authority,
+        constraint = farm.version == LATEST_FARM_VERSION @ ErrorCode::OutdatedAccountVersion,
+        constraint = farm.has_role(&farm.to_account_info(), FarmRole::RewardAdmin, farm_manager.key())? @ ErrorCode::MissingRole)]
     pub farm: Box<Account<'info, Farm>>,
+    // the farm manager, or the reward admin
     #[account(mut)]
     pub farm_manager: Signer<'info>,
     /// CHECK:
@@ -76,6 +79,13 @@ pub fn handler(ctx: Context<CancelReward>) -> Result<()> {
         cancel_amount,
     )?;
 
+    emit!(RewardCancelled {
+        farm: ctx.accounts.farm.key(),
+        reward_mint: ctx.accounts.reward_mint.key(),
+        amount_refunded: cancel_amount,
+        ts: now_ts,
+    });
+
     msg!(
         "{} reward cancelled, {} tokens refunded",
         ctx.accounts.reward_mint.key(),
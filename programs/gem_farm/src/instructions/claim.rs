@@ -3,15 +3,16 @@ use anchor_spl::{
     associated_token::AssociatedToken,
     token::{self, Mint, Token, TokenAccount, Transfer},
 };
-use gem_common::*;
+use gem_common::{errors::ErrorCode, *};
 
-use crate::state::*;
+use crate::{events::*, state::*};
 
 #[derive(Accounts)]
 #[instruction(bump_auth: u8, bump_farmer: u8, bump_pot_a: u8, bump_pot_b: u8)]
 pub struct Claim<'info> {
     // farm
-    #[account(mut, has_one = farm_authority)]
+    #[account(mut, has_one = farm_authority,
+        constraint = farm.version == LATEST_FARM_VERSION @ ErrorCode::OutdatedAccountVersion)]
     pub farm: Box<Account<'info, Farm>>,
     /// CHECK:
     #[account(seeds = [farm.key().as_ref()], bump = bump_auth)]
@@ -23,7 +24,8 @@ pub struct Claim<'info> {
             farm.key().as_ref(),
             identity.key().as_ref(),
         ],
-        bump = bump_farmer)]
+        bump = bump_farmer,
+        constraint = Farmer::version(&farmer.to_account_info())? == LATEST_FARMER_VERSION @ ErrorCode::OutdatedAccountVersion)]
     pub farmer: Box<Account<'info, Farmer>>,
     #[account(mut)] //payer
     pub identity: Signer<'info>,
@@ -34,7 +36,8 @@ pub struct Claim<'info> {
             farm.key().as_ref(),
             reward_a_mint.key().as_ref(),
         ],
-        bump = bump_pot_a)]
+        bump = bump_pot_a,
+        address = farm.reward_a.reward_pot)]
     pub reward_a_pot: Box<Account<'info, TokenAccount>>,
     pub reward_a_mint: Box<Account<'info, Mint>>,
     #[account(init_if_needed,
@@ -49,7 +52,8 @@ pub struct Claim<'info> {
             farm.key().as_ref(),
             reward_b_mint.key().as_ref(),
         ],
-        bump = bump_pot_b)]
+        bump = bump_pot_b,
+        address = farm.reward_b.reward_pot)]
     pub reward_b_pot: Box<Account<'info, TokenAccount>>,
     pub reward_b_mint: Box<Account<'info, Mint>>,
     #[account(init_if_needed,
@@ -94,15 +98,17 @@ pub fn handler(ctx: Context<Claim>) -> Result<()> {
     let farm = &mut ctx.accounts.farm;
     let farmer = &mut ctx.accounts.farmer;
 
-    farm.update_rewards(now_ts()?, Some(farmer), true)?;
+    let now_ts = now_ts()?;
+
+    farm.update_rewards(now_ts, Some(farmer), true)?;
 
     // calculate claimed amounts (capped at what's available in the pot)
-    let to_claim_a = farmer
+    let to_claim_a = farm
         .reward_a
-        .claim_reward(ctx.accounts.reward_a_pot.amount)?;
-    let to_claim_b = farmer
+        .claim_reward(&mut farmer.reward_a, ctx.accounts.reward_a_pot.amount)?;
+    let to_claim_b = farm
         .reward_b
-        .claim_reward(ctx.accounts.reward_b_pot.amount)?;
+        .claim_reward(&mut farmer.reward_b, ctx.accounts.reward_b_pot.amount)?;
 
     // do the transfers
     if to_claim_a > 0 {
@@ -122,6 +128,17 @@ pub fn handler(ctx: Context<Claim>) -> Result<()> {
         )?;
     }
 
+    emit!(RewardsClaimed {
+        farm: ctx.accounts.farm.key(),
+        farmer: ctx.accounts.farmer.key(),
+        claimed_by: ctx.accounts.identity.key(),
+        amount_a: to_claim_a,
+        amount_b: to_claim_b,
+        tip_a: 0,
+        tip_b: 0,
+        ts: now_ts,
+    });
+
     msg!("rewards claimed ({} A) and ({} B)", to_claim_a, to_claim_b);
     Ok(())
 }
@@ -0,0 +1,150 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::get_associated_token_address,
+    token::{self, Mint, Token, TokenAccount, Transfer},
+};
+use gem_common::{errors::ErrorCode, *};
+
+use crate::{events::*, state::*};
+
+#[derive(Accounts)]
+#[instruction(bump_auth: u8, bump_farmer: u8, bump_pot_a: u8, bump_pot_b: u8)]
+pub struct ClaimDelegated<'info> {
+    // farm
+    #[account(mut, has_one = farm_authority,
+        constraint = farm.version == LATEST_FARM_VERSION @ ErrorCode::OutdatedAccountVersion)]
+    pub farm: Box<Account<'info, Farm>>,
+    /// CHECK:
+    #[account(seeds = [farm.key().as_ref()], bump = bump_auth)]
+    pub farm_authority: AccountInfo<'info>,
+
+    // farmer
+    #[account(mut, has_one = farm, has_one = identity, seeds = [
+            b"farmer".as_ref(),
+            farm.key().as_ref(),
+            identity.key().as_ref(),
+        ],
+        bump = bump_farmer,
+        constraint = Farmer::version(&farmer.to_account_info())? == LATEST_FARMER_VERSION @ ErrorCode::OutdatedAccountVersion)]
+    pub farmer: Box<Account<'info, Farmer>>,
+    /// CHECK:
+    pub identity: AccountInfo<'info>,
+    // either the identity itself or the delegate it picked
+    #[account(constraint = authority.key() == identity.key()
+        || authority.key() == farmer.claim_delegate @ ErrorCode::InvalidParameter)]
+    pub authority: Signer<'info>,
+
+    // reward a
+    #[account(mut, seeds = [
+            b"reward_pot".as_ref(),
+            farm.key().as_ref(),
+            reward_a_mint.key().as_ref(),
+        ],
+        bump = bump_pot_a,
+        address = farm.reward_a.reward_pot)]
+    pub reward_a_pot: Box<Account<'info, TokenAccount>>,
+    pub reward_a_mint: Box<Account<'info, Mint>>,
+    #[account(mut, token::mint = reward_a_mint,
+        constraint = reward_a_destination.key() == farmer.reward_a.claim_destination_or(
+            get_associated_token_address(&identity.key(), &reward_a_mint.key())
+        ) @ ErrorCode::InvalidParameter)]
+    pub reward_a_destination: Box<Account<'info, TokenAccount>>,
+
+    // reward b
+    #[account(mut, seeds = [
+            b"reward_pot".as_ref(),
+            farm.key().as_ref(),
+            reward_b_mint.key().as_ref(),
+        ],
+        bump = bump_pot_b,
+        address = farm.reward_b.reward_pot)]
+    pub reward_b_pot: Box<Account<'info, TokenAccount>>,
+    pub reward_b_mint: Box<Account<'info, Mint>>,
+    #[account(mut, token::mint = reward_b_mint,
+        constraint = reward_b_destination.key() == farmer.reward_b.claim_destination_or(
+            get_associated_token_address(&identity.key(), &reward_b_mint.key())
+        ) @ ErrorCode::InvalidParameter)]
+    pub reward_b_destination: Box<Account<'info, TokenAccount>>,
+
+    // misc
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> ClaimDelegated<'info> {
+    fn transfer_a_ctx(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        CpiContext::new(
+            self.token_program.to_account_info(),
+            Transfer {
+                from: self.reward_a_pot.to_account_info(),
+                to: self.reward_a_destination.to_account_info(),
+                authority: self.farm_authority.to_account_info(),
+            },
+        )
+    }
+
+    fn transfer_b_ctx(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        CpiContext::new(
+            self.token_program.to_account_info(),
+            Transfer {
+                from: self.reward_b_pot.to_account_info(),
+                to: self.reward_b_destination.to_account_info(),
+                authority: self.farm_authority.to_account_info(),
+            },
+        )
+    }
+}
+
+pub fn handler(ctx: Context<ClaimDelegated>) -> Result<()> {
+    // update accrued rewards before claiming
+    let farm = &mut ctx.accounts.farm;
+    let farmer = &mut ctx.accounts.farmer;
+
+    let now_ts = now_ts()?;
+
+    farm.update_rewards(now_ts, Some(farmer), true)?;
+
+    // calculate claimed amounts (capped at what's available in the pot)
+    let to_claim_a = farm
+        .reward_a
+        .claim_reward(&mut farmer.reward_a, ctx.accounts.reward_a_pot.amount)?;
+    let to_claim_b = farm
+        .reward_b
+        .claim_reward(&mut farmer.reward_b, ctx.accounts.reward_b_pot.amount)?;
+
+    // do the transfers
+    if to_claim_a > 0 {
+        token::transfer(
+            ctx.accounts
+                .transfer_a_ctx()
+                .with_signer(&[&ctx.accounts.farm.farm_seeds()]),
+            to_claim_a,
+        )?;
+    }
+    if to_claim_b > 0 {
+        token::transfer(
+            ctx.accounts
+                .transfer_b_ctx()
+                .with_signer(&[&ctx.accounts.farm.farm_seeds()]),
+            to_claim_b,
+        )?;
+    }
+
+    emit!(RewardsClaimed {
+        farm: ctx.accounts.farm.key(),
+        farmer: ctx.accounts.farmer.key(),
+        claimed_by: ctx.accounts.authority.key(),
+        amount_a: to_claim_a,
+        amount_b: to_claim_b,
+        tip_a: 0,
+        tip_b: 0,
+        ts: now_ts,
+    });
+
+    msg!(
+        "rewards claimed ({} A) and ({} B) by {}",
+        to_claim_a,
+        to_claim_b,
+        ctx.accounts.authority.key()
+    );
+    Ok(())
+}
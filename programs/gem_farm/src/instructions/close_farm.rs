@@ -0,0 +1,249 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{program::invoke_signed, system_instruction},
+    Discriminator,
+};
+use anchor_spl::token::{self, CloseAccount, Token, TokenAccount};
+use gem_bank::{self, cpi::accounts::CloseBank, program::GemBank, state::Bank};
+use gem_common::{errors::ErrorCode, *};
+
+use crate::state::*;
+
+#[derive(Accounts)]
+#[instruction(bump_auth: u8, bump_treasury: u8)]
+pub struct CloseFarm<'info> {
+    // farm
+    #[account(mut, has_one = farm_manager, has_one = farm_authority,
+        has_one = farm_treasury, has_one = bank)]
+    pub farm: Box<Account<'info, Farm>>,
+    #[account(mut)]
+    pub farm_manager: Signer<'info>,
+    /// CHECK:
+    #[account(seeds = [farm.key().as_ref()], bump = bump_auth)]
+    pub farm_authority: AccountInfo<'info>,
+    /// CHECK:
+    #[account(mut, seeds = [b"treasury".as_ref(), farm.key().as_ref()], bump = bump_treasury)]
+    pub farm_treasury: AccountInfo<'info>,
+
+    // rewards
+    #[account(mut, address = farm.reward_a.reward_pot)]
+    pub reward_a_pot: Box<Account<'info, TokenAccount>>,
+    #[account(mut, address = farm.reward_b.reward_pot)]
+    pub reward_b_pot: Box<Account<'info, TokenAccount>>,
+
+    // pending config - closed here if a config change is still pending
+    /// CHECK:
+    #[account(mut, seeds = [b"pending_config".as_ref(), farm.key().as_ref()], bump)]
+    pub pending_farm_config: AccountInfo<'info>,
+
+    // cpi
+    #[account(mut)]
+    pub bank: Box<Account<'info, Bank>>,
+    pub gem_bank: Program<'info, GemBank>,
+
+    // misc
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    //
+    // remaining accounts can be any number of:
+    // - authorization_proof <- owned by gem_farm, closed here
+    // - migration_proof (from this farm) <- owned by gem_farm, closed here
+    // - whitelist_proof <- owned by gem_bank, closed together with the bank
+    // - token_treasury <- owned by the token program, has to be empty (see token_treasury_payout)
+}
+
+impl<'info> CloseFarm<'info> {
+    fn close_pot_ctx(
+        &self,
+        reward_pot: &Account<'info, TokenAccount>,
+    ) -> CpiContext<'_, '_, '_, 'info, CloseAccount<'info>> {
+        CpiContext::new(
+            self.token_program.to_account_info(),
+            CloseAccount {
+                account: reward_pot.to_account_info(),
+                destination: self.farm_manager.to_account_info(),
+                authority: self.farm_authority.clone(),
+            },
+        )
+    }
+
+    fn close_token_treasury_ctx(
+        &self,
+        token_treasury: &AccountInfo<'info>,
+    ) -> CpiContext<'_, '_, '_, 'info, CloseAccount<'info>> {
+        CpiContext::new(
+            self.token_program.to_account_info(),
+            CloseAccount {
+                account: token_treasury.clone(),
+                destination: self.farm_manager.to_account_info(),
+                authority: self.farm_authority.clone(),
+            },
+        )
+    }
+
+    fn close_bank_ctx(&self) -> CpiContext<'_, '_, '_, 'info, CloseBank<'info>> {
+        CpiContext::new(
+            self.gem_bank.to_account_info(),
+            CloseBank {
+                bank: self.bank.to_account_info(),
+                bank_manager: self.farm_authority.clone(),
+                funds_receiver: self.farm_manager.to_account_info(),
+            },
+        )
+    }
+
+    fn empty_treasury(&self, bump_treasury: u8) -> Result<()> {
+        invoke_signed(
+            &system_instruction::transfer(
+                self.farm_treasury.key,
+                self.farm_manager.key,
+                self.farm_treasury.lamports(),
+            ),
+            &[
+                self.farm_treasury.clone(),
+                self.farm_manager.to_account_info(),
+                self.system_program.to_account_info(),
+            ],
+            &[&[
+                b"treasury".as_ref(),
+                self.farm.key().as_ref(),
+                &[bump_treasury],
+            ]],
+        )
+        .map_err(Into::into)
+    }
+}
+
+pub fn handler<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, CloseFarm<'info>>,
+    bump_treasury: u8,
+) -> Result<()> {
+    // pots have to be drained (cancel rewards / let farmers claim) before the farm can go,
+    // and every farmer closed - close_farmer needs the farm, so their rent would be stuck
+    if ctx.accounts.farm.farmer_count > 0
+        || ctx.accounts.farm.staked_farmer_count > 0
+        || ctx.accounts.reward_a_pot.amount > 0
+        || ctx.accounts.reward_b_pot.amount > 0
+    {
+        return Err(error!(ErrorCode::FarmNotEmpty));
+    }
+
+    // close the pending config, if any
+    if ctx.accounts.pending_farm_config.owner == &crate::ID {
+        close_account(
+            &mut ctx.accounts.pending_farm_config,
+            &mut ctx.accounts.farm_manager.to_account_info(),
+        )?;
+    }
+
+    // close authorization + migration proofs and token treasuries,
+    // pass whitelist proofs on to the bank
+    let mut whitelist_proofs = Vec::new();
+
+    for proof_info in ctx.remaining_accounts.iter() {
+        if proof_info.owner == &gem_bank::ID {
+            whitelist_proofs.push(proof_info.clone());
+            continue;
+        }
+
+        if proof_info.owner == &token::ID {
+            let token_treasury = Account::<TokenAccount>::try_from(proof_info)?;
+            let (token_treasury_key, _bump) = Pubkey::find_program_address(
+                &[
+                    b"token_treasury".as_ref(),
+                    ctx.accounts.farm.key().as_ref(),
+                    token_treasury.mint.as_ref(),
+                ],
+                &crate::ID,
+            );
+            if token_treasury.key() != token_treasury_key {
+                return Err(error!(ErrorCode::InvalidParameter));
+            }
+            if token_treasury.amount > 0 {
+                return Err(error!(ErrorCode::FarmNotEmpty));
+            }
+
+            token::close_account(
+                ctx.accounts
+                    .close_token_treasury_ctx(proof_info)
+                    .with_signer(&[&ctx.accounts.farm.farm_seeds()]),
+            )?;
+
+            ctx.accounts.farm.token_treasury_count.try_sub_assign(1)?;
+            continue;
+        }
+
+        let farm = &mut ctx.accounts.farm;
+
+        let is_migration_proof = proof_info
+            .try_borrow_data()?
+            .starts_with(&MigrationProof::discriminator());
+        if is_migration_proof {
+            let proof = Account::<MigrationProof>::try_from(proof_info)?;
+            if proof.from_farm != farm.key() {
+                return Err(error!(ErrorCode::InvalidParameter));
+            }
+
+            close_account(
+                &mut proof_info.to_account_info(),
+                &mut ctx.accounts.farm_manager.to_account_info(),
+            )?;
+
+            farm.migration_proof_count.try_sub_assign(1)?;
+            continue;
+        }
+
+        let proof = Account::<AuthorizationProof>::try_from(proof_info)?;
+        if proof.farm != farm.key() {
+            return Err(error!(ErrorCode::InvalidParameter));
+        }
+
+        close_account(
+            &mut proof_info.to_account_info(),
+            &mut ctx.accounts.farm_manager.to_account_info(),
+        )?;
+
+        farm.authorized_funder_count.try_sub_assign(1)?;
+    }
+
+    let farm = &ctx.accounts.farm;
+    if farm.authorized_funder_count > 0
+        || farm.migration_proof_count > 0
+        || farm.token_treasury_count > 0
+    {
+        return Err(error!(ErrorCode::FarmNotEmpty));
+    }
+
+    // close reward pots & empty treasury
+    let farm_seeds = ctx.accounts.farm.farm_seeds();
+
+    token::close_account(
+        ctx.accounts
+            .close_pot_ctx(&ctx.accounts.reward_a_pot)
+            .with_signer(&[&farm_seeds]),
+    )?;
+    token::close_account(
+        ctx.accounts
+            .close_pot_ctx(&ctx.accounts.reward_b_pot)
+            .with_signer(&[&farm_seeds]),
+    )?;
+
+    ctx.accounts.empty_treasury(bump_treasury)?;
+
+    // close bank (fails if any vaults are still registered with it)
+    gem_bank::cpi::close_bank(
+        ctx.accounts
+            .close_bank_ctx()
+            .with_signer(&[&farm_seeds])
+            .with_remaining_accounts(whitelist_proofs),
+    )?;
+
+    // finally close the farm itself
+    close_account(
+        &mut ctx.accounts.farm.to_account_info(),
+        &mut ctx.accounts.farm_manager.to_account_info(),
+    )?;
+
+    msg!("farm {} closed", ctx.accounts.farm.key());
+    Ok(())
+}
@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+use gem_bank::{
+    self,
+    cpi::accounts::CloseVault,
+    program::GemBank,
+    state::{Bank, Vault},
+};
+use gem_common::{errors::ErrorCode, *};
+
+use crate::state::*;
+
+#[derive(Accounts)]
+#[instruction(bump_farmer: u8)]
+pub struct CloseFarmer<'info> {
+    // farm
+    #[account(mut, has_one = bank)]
+    pub farm: Box<Account<'info, Farm>>,
+
+    // farmer
+    #[account(mut, has_one = farm, has_one = identity, has_one = vault,
+        seeds = [
+            b"farmer".as_ref(),
+            farm.key().as_ref(),
+            identity.key().as_ref(),
+        ],
+        bump = bump_farmer)]
+    pub farmer: Box<Account<'info, Farmer>>,
+    #[account(mut)] //receives the rent
+    pub identity: Signer<'info>,
+
+    // cpi
+    #[account(mut)]
+    pub bank: Box<Account<'info, Bank>>,
+    // might have been closed directly through gem_bank already
+    /// CHECK:
+    #[account(mut)]
+    pub vault: AccountInfo<'info>,
+    pub gem_bank: Program<'info, GemBank>,
+}
+
+impl<'info> CloseFarmer<'info> {
+    fn close_vault_ctx(&self) -> CpiContext<'_, '_, '_, 'info, CloseVault<'info>> {
+        CpiContext::new(
+            self.gem_bank.to_account_info(),
+            CloseVault {
+                bank: self.bank.to_account_info(),
+                vault: self.vault.clone(),
+                owner: self.identity.to_account_info(),
+                funds_receiver: self.identity.to_account_info(),
+            },
+        )
+    }
+}
+
+pub fn handler(ctx: Context<CloseFarmer>) -> Result<()> {
+    // make sure everything owed to the farmer has been recorded
+    let farm = &mut ctx.accounts.farm;
+    let farmer = &mut ctx.accounts.farmer;
+    let now_ts = now_ts()?;
+
+    farm.update_rewards(now_ts, Some(farmer), false)?;
+
+    if !farmer.can_close()? {
+        return Err(error!(ErrorCode::FarmerNotEmpty));
+    }
+
+    farm.farmer_count.try_sub_assign(1)?;
+
+    // do a cpi call to close the vault (fails if it still holds gems)
+    // a vault that was already closed by its owner, or has since moved to another farm's bank
+    // (migrate_farmer), isn't ours to close
+    let vault_info = &ctx.accounts.vault;
+    if vault_info.owner == &gem_bank::ID && !vault_info.data_is_empty() {
+        let vault = Account::<Vault>::try_from(vault_info)?;
+        if vault.bank == ctx.accounts.bank.key() {
+            gem_bank::cpi::close_vault(ctx.accounts.close_vault_ctx())?;
+        }
+    }
+
+    close_account(
+        &mut ctx.accounts.farmer.to_account_info(),
+        &mut ctx.accounts.identity.to_account_info(),
+    )?;
+
+    msg!("farmer {} closed", ctx.accounts.identity.key());
+    Ok(())
+}
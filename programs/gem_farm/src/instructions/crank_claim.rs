@@ -0,0 +1,161 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use gem_common::{errors::ErrorCode, *};
+
+use crate::{events::*, state::*};
+
+#[derive(Accounts)]
+#[instruction(bump_auth: u8, bump_farmer: u8, bump_pot_a: u8, bump_pot_b: u8)]
+pub struct CrankClaim<'info> {
+    // farm
+    #[account(mut, has_one = farm_authority,
+        constraint = farm.version == LATEST_FARM_VERSION @ ErrorCode::OutdatedAccountVersion)]
+    pub farm: Box<Account<'info, Farm>>,
+    /// CHECK:
+    #[account(seeds = [farm.key().as_ref()], bump = bump_auth)]
+    pub farm_authority: AccountInfo<'info>,
+
+    // farmer
+    #[account(mut, has_one = farm, has_one = identity, seeds = [
+            b"farmer".as_ref(),
+            farm.key().as_ref(),
+            identity.key().as_ref(),
+        ],
+        bump = bump_farmer,
+        constraint = Farmer::version(&farmer.to_account_info())? == LATEST_FARMER_VERSION @ ErrorCode::OutdatedAccountVersion,
+        // only farmers that opted in can be cranked
+        constraint = Farmer::extension(&farmer.to_account_info())?.auto_claim @ ErrorCode::AutoClaimDisabled)]
+    pub farmer: Box<Account<'info, Farmer>>,
+    /// CHECK:
+    pub identity: AccountInfo<'info>,
+
+    // anyone can crank
+    pub cranker: Signer<'info>,
+
+    // reward a
+    #[account(mut, seeds = [
+            b"reward_pot".as_ref(),
+            farm.key().as_ref(),
+            reward_a_mint.key().as_ref(),
+        ],
+        bump = bump_pot_a,
+        address = farm.reward_a.reward_pot)]
+    pub reward_a_pot: Box<Account<'info, TokenAccount>>,
+    pub reward_a_mint: Box<Account<'info, Mint>>,
+    #[account(mut,
+        associated_token::mint = reward_a_mint,
+        associated_token::authority = identity)]
+    pub reward_a_destination: Box<Account<'info, TokenAccount>>,
+    #[account(mut, token::mint = reward_a_mint)]
+    pub reward_a_tip_destination: Box<Account<'info, TokenAccount>>,
+
+    // reward b
+    #[account(mut, seeds = [
+            b"reward_pot".as_ref(),
+            farm.key().as_ref(),
+            reward_b_mint.key().as_ref(),
+        ],
+        bump = bump_pot_b,
+        address = farm.reward_b.reward_pot)]
+    pub reward_b_pot: Box<Account<'info, TokenAccount>>,
+    pub reward_b_mint: Box<Account<'info, Mint>>,
+    #[account(mut,
+        associated_token::mint = reward_b_mint,
+        associated_token::authority = identity)]
+    pub reward_b_destination: Box<Account<'info, TokenAccount>>,
+    #[account(mut, token::mint = reward_b_mint)]
+    pub reward_b_tip_destination: Box<Account<'info, TokenAccount>>,
+
+    // misc
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> CrankClaim<'info> {
+    fn transfer_ctx(
+        &self,
+        from: &Account<'info, TokenAccount>,
+        to: &Account<'info, TokenAccount>,
+    ) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        CpiContext::new(
+            self.token_program.to_account_info(),
+            Transfer {
+                from: from.to_account_info(),
+                to: to.to_account_info(),
+                authority: self.farm_authority.to_account_info(),
+            },
+        )
+    }
+
+    fn pay_out(
+        &self,
+        pot: &Account<'info, TokenAccount>,
+        destination: &Account<'info, TokenAccount>,
+        amount: u64,
+    ) -> Result<()> {
+        if amount > 0 {
+            token::transfer(
+                self.transfer_ctx(pot, destination)
+                    .with_signer(&[&self.farm.farm_seeds()]),
+                amount,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<CrankClaim>) -> Result<()> {
+    // update accrued rewards before claiming
+    let farm = &mut ctx.accounts.farm;
+    let farmer = &mut ctx.accounts.farmer;
+
+    let now_ts = now_ts()?;
+
+    farm.update_rewards(now_ts, Some(farmer), true)?;
+
+    // calculate claimed amounts (capped at what's available in the pot)
+    let to_claim_a = farm
+        .reward_a
+        .claim_reward(&mut farmer.reward_a, ctx.accounts.reward_a_pot.amount)?;
+    let to_claim_b = farm
+        .reward_b
+        .claim_reward(&mut farmer.reward_b, ctx.accounts.reward_b_pot.amount)?;
+
+    // the cranker's tip comes out of the claim itself
+    let tip_a = farm.crank_tip(to_claim_a)?;
+    let tip_b = farm.crank_tip(to_claim_b)?;
+
+    // do the transfers
+    let accs = &ctx.accounts;
+    accs.pay_out(
+        &accs.reward_a_pot,
+        &accs.reward_a_destination,
+        to_claim_a.try_sub(tip_a)?,
+    )?;
+    accs.pay_out(&accs.reward_a_pot, &accs.reward_a_tip_destination, tip_a)?;
+    accs.pay_out(
+        &accs.reward_b_pot,
+        &accs.reward_b_destination,
+        to_claim_b.try_sub(tip_b)?,
+    )?;
+    accs.pay_out(&accs.reward_b_pot, &accs.reward_b_tip_destination, tip_b)?;
+
+    emit!(RewardsClaimed {
+        farm: ctx.accounts.farm.key(),
+        farmer: ctx.accounts.farmer.key(),
+        claimed_by: ctx.accounts.cranker.key(),
+        amount_a: to_claim_a,
+        amount_b: to_claim_b,
+        tip_a,
+        tip_b,
+        ts: now_ts,
+    });
+
+    msg!(
+        "rewards cranked ({} A) and ({} B), tipped ({} A) and ({} B)",
+        to_claim_a,
+        to_claim_b,
+        tip_a,
+        tip_b
+    );
+    Ok(())
+}
@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use gem_common::*;
+use gem_common::{errors::ErrorCode, *};
 
 use crate::state::*;
 
@@ -7,7 +7,8 @@ use crate::state::*;
 #[instruction(bump: u8)]
 pub struct DeauthorizeFunder<'info> {
     // farm
-    #[account(mut, has_one = farm_manager)]
+    #[account(mut, has_one = farm_manager,
+        constraint = farm.version == LATEST_FARM_VERSION @ ErrorCode::OutdatedAccountVersion)]
     pub farm: Box<Account<'info, Farm>>,
     #[account(mut)]
     pub farm_manager: Signer<'info>,
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use gem_common::{errors::ErrorCode, *};
+
+use crate::state::*;
+
+#[derive(Accounts)]
+#[instruction(bump: u8)]
+pub struct DeauthorizeMigration<'info> {
+    // farm being left
+    #[account(mut, has_one = farm_manager,
+        constraint = farm.version == LATEST_FARM_VERSION @ ErrorCode::OutdatedAccountVersion)]
+    pub farm: Box<Account<'info, Farm>>,
+    #[account(mut)]
+    pub farm_manager: Signer<'info>,
+
+    // migration
+    /// CHECK:
+    pub to_farm: AccountInfo<'info>,
+    #[account(mut, constraint = migration_proof.from_farm == farm.key(), has_one = to_farm,
+        seeds = [
+            b"migration".as_ref(),
+            farm.key().as_ref(),
+            to_farm.key().as_ref(),
+        ],
+        bump = bump)]
+    migration_proof: Box<Account<'info, MigrationProof>>,
+
+    // misc
+    system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<DeauthorizeMigration>) -> Result<()> {
+    // close migration proof
+    close_account(
+        &mut ctx.accounts.migration_proof.to_account_info(),
+        &mut ctx.accounts.farm_manager.to_account_info(),
+    )?;
+
+    let farm = &mut ctx.accounts.farm;
+    farm.migration_proof_count.try_sub_assign(1)?;
+
+    msg!(
+        "migration DEauthorized: {} -> {}",
+        ctx.accounts.farm.key(),
+        ctx.accounts.to_farm.key()
+    );
+    Ok(())
+}
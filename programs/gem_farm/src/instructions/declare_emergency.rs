@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+use gem_common::{errors::ErrorCode, *};
+
+use crate::state::{Farm, LATEST_FARM_VERSION};
+
+#[derive(Accounts)]
+pub struct DeclareEmergency<'info> {
+    // farm
+    #[account(mut, has_one = farm_manager,
+        constraint = farm.version == LATEST_FARM_VERSION @ ErrorCode::OutdatedAccountVersion)]
+    pub farm: Box<Account<'info, Farm>>,
+    pub farm_manager: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<DeclareEmergency>) -> Result<()> {
+    let farm = &mut ctx.accounts.farm;
+
+    farm.declare_emergency(now_ts()?)?;
+
+    msg!("farm {} is now in emergency mode", farm.key());
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{program::invoke_signed, system_instruction},
+};
+use gem_common::{errors::ErrorCode, *};
+
+use crate::state::*;
+
+#[derive(Accounts)]
+#[instruction(bump_treasury: u8)]
+pub struct DistributeTreasury<'info> {
+    // farm
+    #[account(has_one = farm_treasury,
+        constraint = farm.version == LATEST_FARM_VERSION @ ErrorCode::OutdatedAccountVersion)]
+    pub farm: Box<Account<'info, Farm>>,
+    /// CHECK:
+    #[account(mut, seeds = [b"treasury".as_ref(), farm.key().as_ref()], bump = bump_treasury)]
+    pub farm_treasury: AccountInfo<'info>,
+
+    // misc
+    pub system_program: Program<'info, System>,
+    //
+    // remaining accounts, in the same order as the farm's treasury splits:
+    //   #[account(mut)]
+    //   pub recipient: AccountInfo<'info>,
+}
+
+/// permissionless - pays out everything above rent exemption, according to the farm's splits
+pub fn handler<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, DistributeTreasury<'info>>,
+    bump_treasury: u8,
+) -> Result<()> {
+    let farm = &ctx.accounts.farm;
+    let farm_treasury = &ctx.accounts.farm_treasury;
+    let splits = Farm::extension(&farm.to_account_info())?.active_treasury_splits();
+
+    if splits.is_empty() || splits.len() != ctx.remaining_accounts.len() {
+        return Err(error!(ErrorCode::InvalidTreasurySplits));
+    }
+
+    let rent = Rent::get()?;
+    let distributable = farm_treasury
+        .lamports()
+        .saturating_sub(rent.minimum_balance(0));
+    let mut distributed: u64 = 0;
+
+    for (split, recipient) in splits.iter().zip(ctx.remaining_accounts.iter()) {
+        if recipient.key() != split.recipient {
+            return Err(error!(ErrorCode::InvalidTreasurySplits));
+        }
+
+        let share = split.share_of(distributable)?;
+        if share == 0 {
+            continue;
+        }
+
+        // the transfer would fail if it left the recipient below rent exemption (eg a new
+        // account getting a small share), blocking everyone else - so the share stays in the
+        // treasury instead, and goes out with a later distribution once it's large enough
+        let recipient_lamports = recipient.lamports().try_add(share)?;
+        if !rent.is_exempt(recipient_lamports, recipient.data_len()) {
+            continue;
+        }
+
+        invoke_signed(
+            &system_instruction::transfer(farm_treasury.key, recipient.key, share),
+            &[
+                farm_treasury.clone(),
+                recipient.clone(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+            &[&[b"treasury".as_ref(), farm.key().as_ref(), &[bump_treasury]]],
+        )?;
+        distributed.try_add_assign(share)?;
+    }
+
+    msg!("{} lamports distributed from treasury", distributed);
+    Ok(())
+}
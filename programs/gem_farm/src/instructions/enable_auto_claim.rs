@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+use gem_common::errors::ErrorCode;
+
+use crate::state::*;
+
+#[derive(Accounts)]
+#[instruction(bump_farmer: u8)]
+pub struct EnableAutoClaim<'info> {
+    // farmer
+    #[account(mut, has_one = identity, seeds = [
+            b"farmer".as_ref(),
+            farmer.farm.as_ref(),
+            identity.key().as_ref(),
+        ],
+        bump = bump_farmer,
+        constraint = Farmer::version(&farmer.to_account_info())? == LATEST_FARMER_VERSION @ ErrorCode::OutdatedAccountVersion)]
+    pub farmer: Box<Account<'info, Farmer>>,
+    pub identity: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<EnableAutoClaim>) -> Result<()> {
+    let farmer_info = ctx.accounts.farmer.to_account_info();
+    let mut extension = Farmer::extension(&farmer_info)?;
+    extension.auto_claim = true;
+    Farmer::set_extension(&farmer_info, &extension)?;
+
+    msg!("auto claim enabled for: {}", farmer_info.key());
+    Ok(())
+}
@@ -1,5 +1,3 @@
-use std::str::FromStr;
-
 use anchor_lang::{
     prelude::*,
     solana_program::{program::invoke, system_instruction},
@@ -12,18 +10,20 @@ use gem_bank::{
     program::GemBank,
     state::{Bank, Vault},
 };
-use gem_common::*;
-
-use crate::{instructions::FEE_WALLET, state::*};
+use gem_common::{errors::ErrorCode, *};
 
-const FEE_LAMPORTS: u64 = 2_000_000; // 0.002 SOL per stake/unstake
-const FD_FEE_LAMPORTS: u64 = 1_000_000; // half of that for FDs
+use crate::{
+    events::*,
+    instructions::{collect_token_fee, split_token_fee_accounts},
+    state::*,
+};
 
 #[derive(Accounts)]
 #[instruction(bump_farmer: u8)]
 pub struct FlashDeposit<'info> {
     // farm
-    #[account(mut, has_one = farm_authority)]
+    #[account(mut, has_one = farm_authority,
+        constraint = farm.version == LATEST_FARM_VERSION @ ErrorCode::OutdatedAccountVersion)]
     pub farm: Box<Account<'info, Farm>>,
     //skipping seeds verification to save compute budget, has_one check above should be enough
     /// CHECK:
@@ -36,7 +36,8 @@ pub struct FlashDeposit<'info> {
             farm.key().as_ref(),
             identity.key().as_ref(),
         ],
-        bump = bump_farmer)]
+        bump = bump_farmer,
+        constraint = Farmer::version(&farmer.to_account_info())? == LATEST_FARMER_VERSION @ ErrorCode::OutdatedAccountVersion)]
     pub farmer: Box<Account<'info, Farmer>>,
     #[account(mut)]
     pub identity: Signer<'info>,
@@ -64,14 +65,22 @@ pub struct FlashDeposit<'info> {
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
     pub gem_bank: Program<'info, GemBank>,
+    // only one can ever exist (see init_protocol_config), so no need to check seeds
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
     /// CHECK:
-    #[account(mut, address = Pubkey::from_str(FEE_WALLET).unwrap())]
+    #[account(mut, address = protocol_config.fee_wallet)]
     pub fee_acc: AccountInfo<'info>,
+    // only exists if the farm doesn't pay protocol fees (see add_fee_exemption)
+    /// CHECK:
+    #[account(seeds = [b"fee_exemption".as_ref(), farm.key().as_ref()], bump)]
+    pub fee_exemption: AccountInfo<'info>,
     //
     // remaining accounts could be passed, in this order:
     // - mint_whitelist_proof
     // - gem_metadata <- if we got to this point we can assume gem = NFT, not a fungible token
     // - creator_whitelist_proof
+    // followed by the token fee accounts (see collect_token_fee), if the farm charges a
+    // stake fee and the farmer isn't staked yet
 }
 
 impl<'info> FlashDeposit<'info> {
@@ -107,6 +116,11 @@ impl<'info> FlashDeposit<'info> {
     }
 
     fn transfer_fee(&self, fee: u64) -> Result<()> {
+        let fee = self.protocol_config.fee_for(&self.fee_exemption, fee);
+        if fee == 0 {
+            return Ok(());
+        }
+
         invoke(
             &system_instruction::transfer(self.identity.key, self.fee_acc.key, fee),
             &[
@@ -125,6 +139,18 @@ pub fn handler<'a, 'b, 'c, 'info>(
     bump_rarity: u8,
     amount: u64,
 ) -> Result<()> {
+    // the farm's token fee is only due when this begins the stake, as in stake
+    let stake_fee = match ctx.accounts.farmer.gems_staked {
+        0 => {
+            Farm::extension(&ctx.accounts.farm.to_account_info())?
+                .token_fees
+                .stake_fee
+        }
+        _ => 0,
+    };
+    let (deposit_accounts, token_fee_accounts) =
+        split_token_fee_accounts(ctx.remaining_accounts, stake_fee)?;
+
     // flash deposit a gem into a locked vault
     gem_bank::cpi::set_vault_lock(
         ctx.accounts
@@ -136,7 +162,7 @@ pub fn handler<'a, 'b, 'c, 'info>(
     gem_bank::cpi::deposit_gem(
         ctx.accounts
             .deposit_gem_ctx()
-            .with_remaining_accounts(ctx.remaining_accounts.to_vec()),
+            .with_remaining_accounts(deposit_accounts.to_vec()),
         bump_vault_auth,
         bump_rarity,
         amount,
@@ -159,7 +185,10 @@ pub fn handler<'a, 'b, 'c, 'info>(
     ctx.accounts.vault.reload()?;
 
     // in case the command is used BEFORE farmer staked
+    let farmer_info = farmer.to_account_info();
     if farmer.gems_staked == 0 {
+        // anything still pending withdrawal goes back into the stake with the rest of the vault
+        Farmer::set_pending_withdrawal(&farmer_info, PendingWithdrawal::default())?;
         farm.begin_staking(
             now_ts,
             ctx.accounts.vault.gem_count,
@@ -167,21 +196,38 @@ pub fn handler<'a, 'b, 'c, 'info>(
             farmer,
         )?;
         //collect a fee for staking
-        ctx.accounts.transfer_fee(FEE_LAMPORTS)?;
+        ctx.accounts
+            .transfer_fee(ctx.accounts.protocol_config.fees.stake_lamp)?;
     } else {
+        // gems pending withdrawal are still in the vault, but no longer staked
+        let pending = Farmer::pending_withdrawal(&farmer_info)?;
         let extra_rarity = calc_rarity_points(&ctx.accounts.gem_rarity, amount)?;
         farm.stake_extra_gems(
             now_ts,
-            ctx.accounts.vault.gem_count,
-            ctx.accounts.vault.rarity_points,
+            ctx.accounts.vault.gem_count.try_sub(pending.amount)?,
+            ctx.accounts
+                .vault
+                .rarity_points
+                .try_sub(pending.rarity_points)?,
             amount,
             extra_rarity,
             farmer,
         )?;
         //collect a fee for staking
-        ctx.accounts.transfer_fee(FD_FEE_LAMPORTS)?;
+        ctx.accounts
+            .transfer_fee(ctx.accounts.protocol_config.fees.flash_deposit_lamp)?;
     }
 
+    //collect the farm's own token fee, if any
+    collect_token_fee(
+        &ctx.accounts.farm,
+        stake_fee,
+        &ctx.accounts.identity.to_account_info(),
+        token_fee_accounts,
+    )?;
+
+    emit!(FarmerStaked::new(&ctx.accounts.farmer, now_ts));
+
     // msg!("{} extra gems staked for {}", amount, farmer.key());
     Ok(())
 }
@@ -0,0 +1,159 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Mint, Token},
+};
+use gem_bank::{
+    self,
+    cpi::accounts::{SetVaultLock, WithdrawGem},
+    program::GemBank,
+    state::{Bank, Vault},
+};
+use gem_common::{errors::ErrorCode, *};
+
+use crate::state::*;
+
+/// bumps of the gem_bank accounts passed on to withdraw_gem
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Copy, Clone)]
+pub struct FlashWithdrawBumps {
+    pub vault_auth: u8,
+
+    pub gem_box: u8,
+
+    pub gdr: u8,
+
+    pub rarity: u8,
+}
+
+#[derive(Accounts)]
+#[instruction(bump_farmer: u8)]
+pub struct FlashWithdraw<'info> {
+    // farm
+    #[account(has_one = farm_authority,
+        constraint = farm.version == LATEST_FARM_VERSION @ ErrorCode::OutdatedAccountVersion)]
+    pub farm: Box<Account<'info, Farm>>,
+    //skipping seeds verification to save compute budget, has_one check above should be enough
+    /// CHECK:
+    pub farm_authority: AccountInfo<'info>,
+
+    // farmer
+    #[account(mut, has_one = farm, has_one = identity, has_one = vault,
+        seeds = [
+            b"farmer".as_ref(),
+            farm.key().as_ref(),
+            identity.key().as_ref(),
+        ],
+        bump = bump_farmer,
+        constraint = Farmer::version(&farmer.to_account_info())? == LATEST_FARMER_VERSION @ ErrorCode::OutdatedAccountVersion)]
+    pub farmer: Box<Account<'info, Farmer>>,
+    #[account(mut)]
+    pub identity: Signer<'info>,
+
+    // cpi
+    pub bank: Box<Account<'info, Bank>>,
+    #[account(mut)]
+    pub vault: Box<Account<'info, Vault>>,
+    /// CHECK:
+    pub vault_authority: AccountInfo<'info>,
+    /// CHECK:
+    #[account(mut)]
+    pub gem_box: AccountInfo<'info>,
+    /// CHECK:
+    #[account(mut)]
+    pub gem_deposit_receipt: AccountInfo<'info>,
+    // trying to deserialize here leads to errors (might not exist yet)
+    /// CHECK:
+    #[account(mut)]
+    pub gem_destination: AccountInfo<'info>,
+    pub gem_mint: Box<Account<'info, Mint>>,
+    /// CHECK:
+    pub gem_rarity: AccountInfo<'info>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+    pub gem_bank: Program<'info, GemBank>,
+}
+
+impl<'info> FlashWithdraw<'info> {
+    fn set_lock_vault_ctx(&self) -> CpiContext<'_, '_, '_, 'info, SetVaultLock<'info>> {
+        CpiContext::new(
+            self.gem_bank.to_account_info(),
+            SetVaultLock {
+                bank: self.bank.to_account_info(),
+                vault: self.vault.to_account_info(),
+                bank_manager: self.farm_authority.clone(),
+            },
+        )
+    }
+
+    fn withdraw_gem_ctx(&self) -> CpiContext<'_, '_, '_, 'info, WithdrawGem<'info>> {
+        CpiContext::new(
+            self.gem_bank.to_account_info(),
+            WithdrawGem {
+                bank: self.bank.to_account_info(),
+                vault: self.vault.to_account_info(),
+                owner: self.identity.to_account_info(),
+                authority: self.vault_authority.clone(),
+                gem_box: self.gem_box.clone(),
+                gem_deposit_receipt: self.gem_deposit_receipt.clone(),
+                gem_destination: self.gem_destination.clone(),
+                gem_mint: self.gem_mint.to_account_info(),
+                gem_rarity: self.gem_rarity.clone(),
+                receiver: self.identity.to_account_info(),
+                token_program: self.token_program.to_account_info(),
+                associated_token_program: self.associated_token_program.to_account_info(),
+                system_program: self.system_program.to_account_info(),
+                rent: self.rent.to_account_info(),
+            },
+        )
+    }
+}
+
+/// hands over the gems set aside by begin_flash_withdraw, once their cooldown has passed
+pub fn handler(ctx: Context<FlashWithdraw>, bumps: FlashWithdrawBumps) -> Result<()> {
+    let farmer_info = ctx.accounts.farmer.to_account_info();
+    let pending = Farmer::pending_withdrawal(&farmer_info)?;
+
+    // once unstaked the vault is unlocked and whatever was pending can be withdrawn directly
+    if ctx.accounts.farmer.state == FarmerState::Unstaked
+        || pending.amount == 0
+        || pending.gem_mint != ctx.accounts.gem_mint.key()
+    {
+        return Err(error!(ErrorCode::NoPendingWithdrawal));
+    }
+
+    if now_ts()? < pending.releases_ts {
+        return Err(error!(ErrorCode::CooldownNotPassed));
+    }
+
+    // flash withdraw the gems from a locked vault
+    gem_bank::cpi::set_vault_lock(
+        ctx.accounts
+            .set_lock_vault_ctx()
+            .with_signer(&[&ctx.accounts.farm.farm_seeds()]),
+        false,
+    )?;
+
+    gem_bank::cpi::withdraw_gem(
+        ctx.accounts.withdraw_gem_ctx(),
+        bumps.vault_auth,
+        bumps.gem_box,
+        bumps.gdr,
+        bumps.rarity,
+        pending.amount,
+    )?;
+
+    gem_bank::cpi::set_vault_lock(
+        ctx.accounts
+            .set_lock_vault_ctx()
+            .with_signer(&[&ctx.accounts.farm.farm_seeds()]),
+        true,
+    )?;
+
+    // they were already taken out of the stake in begin_flash_withdraw
+    Farmer::set_pending_withdrawal(&farmer_info, PendingWithdrawal::default())?;
+
+    // msg!("{} gems withdrawn while staked for {}", pending.amount, farmer.key());
+    Ok(())
+}
@@ -0,0 +1,184 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Mint, Token},
+};
+use gem_bank::{
+    self,
+    cpi::accounts::{ProgNftShared, SetVaultLock, WithdrawGemPnft},
+    program::GemBank,
+    state::{Bank, Vault},
+};
+use gem_common::{errors::ErrorCode, *};
+
+use crate::{instructions::FlashWithdrawBumps, state::*};
+
+#[derive(Accounts)]
+#[instruction(bump_farmer: u8)]
+pub struct FlashWithdrawPnft<'info> {
+    // farm
+    #[account(has_one = farm_authority,
+        constraint = farm.version == LATEST_FARM_VERSION @ ErrorCode::OutdatedAccountVersion)]
+    pub farm: Box<Account<'info, Farm>>,
+    //skipping seeds verification to save compute budget, has_one check above should be enough
+    /// CHECK:
+    pub farm_authority: AccountInfo<'info>,
+
+    // farmer
+    #[account(mut, has_one = farm, has_one = identity, has_one = vault,
+        seeds = [
+            b"farmer".as_ref(),
+            farm.key().as_ref(),
+            identity.key().as_ref(),
+        ],
+        bump = bump_farmer,
+        constraint = Farmer::version(&farmer.to_account_info())? == LATEST_FARMER_VERSION @ ErrorCode::OutdatedAccountVersion)]
+    pub farmer: Box<Account<'info, Farmer>>,
+    #[account(mut)]
+    pub identity: Signer<'info>,
+
+    // cpi
+    pub bank: Box<Account<'info, Bank>>,
+    #[account(mut)]
+    pub vault: Box<Account<'info, Vault>>,
+    /// CHECK:
+    pub vault_authority: AccountInfo<'info>,
+    /// CHECK:
+    #[account(mut)]
+    pub gem_box: AccountInfo<'info>,
+    /// CHECK:
+    #[account(mut)]
+    pub gem_deposit_receipt: AccountInfo<'info>,
+    // trying to deserialize here leads to errors (might not exist yet)
+    /// CHECK:
+    #[account(mut)]
+    pub gem_destination: AccountInfo<'info>,
+    pub gem_mint: Box<Account<'info, Mint>>,
+    /// CHECK:
+    pub gem_rarity: AccountInfo<'info>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+    pub gem_bank: Program<'info, GemBank>,
+    ///CHECK: downstream
+    #[account(mut)]
+    pub gem_metadata: UncheckedAccount<'info>,
+    ///CHECK: downstream
+    pub gem_edition: UncheckedAccount<'info>,
+    ///CHECK: downstream
+    #[account(mut)]
+    pub owner_token_record: UncheckedAccount<'info>,
+    ///CHECK: downstream
+    #[account(mut)]
+    pub dest_token_record: UncheckedAccount<'info>,
+    ///CHECK: downstream
+    pub token_metadata_program: UncheckedAccount<'info>,
+    ///CHECK: downstream
+    pub instructions: UncheckedAccount<'info>,
+    ///CHECK: downstream
+    pub authorization_rules_program: UncheckedAccount<'info>,
+    //
+    // remaining accounts could be passed, in this order:
+    // - rules account
+}
+
+impl<'info> FlashWithdrawPnft<'info> {
+    fn set_lock_vault_ctx(&self) -> CpiContext<'_, '_, '_, 'info, SetVaultLock<'info>> {
+        CpiContext::new(
+            self.gem_bank.to_account_info(),
+            SetVaultLock {
+                bank: self.bank.to_account_info(),
+                vault: self.vault.to_account_info(),
+                bank_manager: self.farm_authority.clone(),
+            },
+        )
+    }
+
+    fn withdraw_gem_ctx(&self) -> CpiContext<'_, '_, '_, 'info, WithdrawGemPnft<'info>> {
+        CpiContext::new(
+            self.gem_bank.to_account_info(),
+            WithdrawGemPnft {
+                bank: self.bank.to_account_info(),
+                vault: self.vault.to_account_info(),
+                owner: self.identity.to_account_info(),
+                authority: self.vault_authority.clone(),
+                gem_box: self.gem_box.clone(),
+                gem_deposit_receipt: self.gem_deposit_receipt.clone(),
+                gem_destination: self.gem_destination.clone(),
+                gem_mint: self.gem_mint.to_account_info(),
+                gem_rarity: self.gem_rarity.clone(),
+                receiver: self.identity.to_account_info(),
+                token_program: self.token_program.to_account_info(),
+                associated_token_program: self.associated_token_program.to_account_info(),
+                system_program: self.system_program.to_account_info(),
+                rent: self.rent.to_account_info(),
+                gem_metadata: self.gem_metadata.to_account_info(),
+                gem_edition: self.gem_edition.to_account_info(),
+                owner_token_record: self.owner_token_record.to_account_info(),
+                dest_token_record: self.dest_token_record.to_account_info(),
+                pnft_shared: ProgNftShared {
+                    token_metadata_program: self.token_metadata_program.to_account_info(),
+                    instructions: self.instructions.to_account_info(),
+                    authorization_rules_program: self.authorization_rules_program.to_account_info(),
+                },
+            },
+        )
+    }
+}
+
+/// pNFT version of flash_withdraw
+pub fn handler<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, FlashWithdrawPnft<'info>>,
+    bumps: FlashWithdrawBumps,
+    rules_acc_present: bool,
+) -> Result<()> {
+    let farmer_info = ctx.accounts.farmer.to_account_info();
+    let pending = Farmer::pending_withdrawal(&farmer_info)?;
+
+    // once unstaked the vault is unlocked and whatever was pending can be withdrawn directly
+    if ctx.accounts.farmer.state == FarmerState::Unstaked
+        || pending.amount == 0
+        || pending.gem_mint != ctx.accounts.gem_mint.key()
+    {
+        return Err(error!(ErrorCode::NoPendingWithdrawal));
+    }
+
+    if now_ts()? < pending.releases_ts {
+        return Err(error!(ErrorCode::CooldownNotPassed));
+    }
+
+    // flash withdraw the gems from a locked vault
+    gem_bank::cpi::set_vault_lock(
+        ctx.accounts
+            .set_lock_vault_ctx()
+            .with_signer(&[&ctx.accounts.farm.farm_seeds()]),
+        false,
+    )?;
+
+    gem_bank::cpi::withdraw_gem_pnft(
+        ctx.accounts
+            .withdraw_gem_ctx()
+            .with_remaining_accounts(ctx.remaining_accounts.to_vec()),
+        bumps.vault_auth,
+        bumps.gem_box,
+        bumps.gdr,
+        bumps.rarity,
+        pending.amount,
+        None,
+        rules_acc_present,
+    )?;
+
+    gem_bank::cpi::set_vault_lock(
+        ctx.accounts
+            .set_lock_vault_ctx()
+            .with_signer(&[&ctx.accounts.farm.farm_seeds()]),
+        true,
+    )?;
+
+    // they were already taken out of the stake in begin_flash_withdraw
+    Farmer::set_pending_withdrawal(&farmer_info, PendingWithdrawal::default())?;
+
+    // msg!("{} gems withdrawn while staked for {}", pending.amount, farmer.key());
+    Ok(())
+}
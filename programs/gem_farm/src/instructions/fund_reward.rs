@@ -1,18 +1,19 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
-use gem_common::*;
+use gem_common::{errors::ErrorCode, *};
 
-use crate::state::*;
+use crate::{events::*, state::*};
 
 #[derive(Accounts)]
 #[instruction(bump_proof: u8, bump_pot: u8)]
 pub struct FundReward<'info> {
     // farm
-    #[account(mut)]
+    #[account(mut,
+        constraint = farm.version == LATEST_FARM_VERSION @ ErrorCode::OutdatedAccountVersion)]
     pub farm: Box<Account<'info, Farm>>,
 
     // funder
-    #[account(has_one = farm, has_one = authorized_funder, seeds = [
+    #[account(mut, has_one = farm, has_one = authorized_funder, seeds = [
             b"authorization".as_ref(),
             farm.key().as_ref(),
             authorized_funder.key().as_ref(),
@@ -63,18 +64,23 @@ pub fn handler(
         fixed_rate_config.unwrap().amount
     };
 
-    // update existing rewards + record new ones
+    // enforce the funder's limits
     let farm = &mut ctx.accounts.farm;
     let now_ts = now_ts()?;
+    let reward_mint = ctx.accounts.reward_mint.key();
+    let is_reward_a = reward_mint == farm.reward_a.reward_mint;
+    let reward_type = farm.match_reward_by_mint(reward_mint)?.reward_type;
+
+    ctx.accounts
+        .authorization_proof
+        .use_allowance(now_ts, is_reward_a, reward_type, amount)?;
+
+    // update existing rewards + record new ones
 
     farm.update_rewards(now_ts, None, true)?;
 
-    farm.fund_reward_by_mint(
-        now_ts,
-        ctx.accounts.reward_mint.key(),
-        variable_rate_config,
-        fixed_rate_config,
-    )?;
+    farm.fund_reward_by_mint(now_ts, reward_mint, variable_rate_config, fixed_rate_config)?;
+    let reward_end_ts = farm.match_reward_by_mint(reward_mint)?.times.reward_end_ts;
 
     // do the transfer
     token::transfer(
@@ -84,6 +90,15 @@ pub fn handler(
         amount,
     )?;
 
+    emit!(RewardFunded {
+        farm: ctx.accounts.farm.key(),
+        reward_mint,
+        funder: ctx.accounts.authorized_funder.key(),
+        amount,
+        reward_end_ts,
+        ts: now_ts,
+    });
+
     msg!(
         "{} reward tokens deposited into {} pot",
         amount,
@@ -2,19 +2,15 @@ use anchor_lang::prelude::*;
 use anchor_lang::solana_program::{program::invoke, system_instruction};
 use anchor_spl::token::{Mint, Token, TokenAccount};
 use gem_bank::{self, cpi::accounts::InitBank, program::GemBank};
-use gem_common::errors::ErrorCode;
-use std::str::FromStr;
+use gem_common::{errors::ErrorCode, *};
 
-use crate::state::*;
-
-pub const FEE_WALLET: &str = "2xhBxVVuXkdq2MRKerE9mr2s1szfHSedy21MVqf8gPoM"; //5th
-const FEE_LAMPORTS: u64 = 2_500_000_000; // 2.5 SOL per farm
+use crate::{events::*, state::*};
 
 #[derive(Accounts)]
 #[instruction(bump_auth: u8, bump_treasury: u8)]
 pub struct InitFarm<'info> {
     // farm
-    #[account(init, payer = payer, space = 8 + std::mem::size_of::<Farm>())]
+    #[account(init, payer = payer, space = Farm::SPACE)]
     pub farm: Box<Account<'info, Farm>>,
     pub farm_manager: Signer<'info>,
     /// CHECK:
@@ -55,9 +51,15 @@ pub struct InitFarm<'info> {
     // misc
     #[account(mut)]
     pub payer: Signer<'info>,
+    // only one can ever exist (see init_protocol_config), so no need to check seeds
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
     /// CHECK:
-    #[account(mut, address = Pubkey::from_str(FEE_WALLET).unwrap())]
+    #[account(mut, address = protocol_config.fee_wallet)]
     pub fee_acc: AccountInfo<'info>,
+    // only exists if the farm doesn't pay protocol fees (see add_fee_exemption)
+    /// CHECK:
+    #[account(seeds = [b"fee_exemption".as_ref(), farm.key().as_ref()], bump)]
+    pub fee_exemption: AccountInfo<'info>,
     pub rent: Sysvar<'info, Rent>,
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
@@ -80,8 +82,16 @@ impl<'info> InitFarm<'info> {
     }
 
     fn transfer_fee(&self) -> Result<()> {
+        let fee = self.protocol_config.fee_for(
+            &self.fee_exemption,
+            self.protocol_config.fees.init_farm_lamp,
+        );
+        if fee == 0 {
+            return Ok(());
+        }
+
         invoke(
-            &system_instruction::transfer(self.payer.key, self.fee_acc.key, FEE_LAMPORTS),
+            &system_instruction::transfer(self.payer.key, self.fee_acc.key, fee),
             &[
                 self.payer.to_account_info(),
                 self.fee_acc.clone(),
@@ -110,10 +120,7 @@ pub fn handler(
         return Err(error!(ErrorCode::InvalidParameter));
     }
 
-    //ensure unstaking fee does not violate solana v1.9.5 rent requirements
-    if farm_config.unstaking_fee_lamp > 0 && farm_config.unstaking_fee_lamp < 890880 {
-        return Err(error!(ErrorCode::InvalidUnstakingFee));
-    }
+    farm_config.assert_valid()?;
 
     //record new farm details
     let farm = &mut ctx.accounts.farm;
@@ -137,6 +144,10 @@ pub fn handler(
     farm.reward_b.reward_type = reward_type_b;
     farm.reward_b.fixed_rate.schedule = FixedRateSchedule::default(); //denom to 1
 
+    // nothing has been credited yet, so the farm's liability is exact from the start
+    farm.reward_a.liability_tracked = true;
+    farm.reward_b.liability_tracked = true;
+
     if let Some(max_counts) = max_counts {
         farm.max_counts = max_counts;
     }
@@ -151,6 +162,15 @@ pub fn handler(
     //collect a fee for starting a farm
     ctx.accounts.transfer_fee()?;
 
+    emit!(FarmInitialized {
+        farm: ctx.accounts.farm.key(),
+        farm_manager: ctx.accounts.farm_manager.key(),
+        bank: ctx.accounts.bank.key(),
+        reward_a_mint: ctx.accounts.reward_a_mint.key(),
+        reward_b_mint: ctx.accounts.reward_b_mint.key(),
+        ts: now_ts()?,
+    });
+
     msg!("new farm initialized");
     Ok(())
 }
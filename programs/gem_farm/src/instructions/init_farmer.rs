@@ -1,18 +1,15 @@
-use crate::instructions::FEE_WALLET;
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::{program::invoke, system_instruction};
 use gem_bank::{self, cpi::accounts::InitVault, program::GemBank, state::Bank};
-use gem_common::*;
-use std::str::FromStr;
+use gem_common::{errors::ErrorCode, *};
 
 use crate::state::*;
 
-const FEE_LAMPORTS: u64 = 10_000_000; // 0.01 SOL per farmer
-
 #[derive(Accounts)]
 pub struct InitFarmer<'info> {
     // farm
-    #[account(mut, has_one = bank)]
+    #[account(mut, has_one = bank,
+        constraint = farm.version == LATEST_FARM_VERSION @ ErrorCode::OutdatedAccountVersion)]
     pub farm: Box<Account<'info, Farm>>,
 
     // farmer
@@ -23,7 +20,7 @@ pub struct InitFarmer<'info> {
         ],
         bump,
         payer = payer,
-        space = 8 + std::mem::size_of::<Farmer>())]
+        space = Farmer::SPACE)]
     pub farmer: Box<Account<'info, Farmer>>,
     pub identity: Signer<'info>,
 
@@ -39,9 +36,15 @@ pub struct InitFarmer<'info> {
     // misc
     #[account(mut)]
     pub payer: Signer<'info>,
+    // only one can ever exist (see init_protocol_config), so no need to check seeds
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
     /// CHECK:
-    #[account(mut, address = Pubkey::from_str(FEE_WALLET).unwrap())]
+    #[account(mut, address = protocol_config.fee_wallet)]
     pub fee_acc: AccountInfo<'info>,
+    // only exists if the farm doesn't pay protocol fees (see add_fee_exemption)
+    /// CHECK:
+    #[account(seeds = [b"fee_exemption".as_ref(), farm.key().as_ref()], bump)]
+    pub fee_exemption: AccountInfo<'info>,
     pub system_program: Program<'info, System>,
 }
 
@@ -61,8 +64,16 @@ impl<'info> InitFarmer<'info> {
     }
 
     fn transfer_fee(&self) -> Result<()> {
+        let fee = self.protocol_config.fee_for(
+            &self.fee_exemption,
+            self.protocol_config.fees.init_farmer_lamp,
+        );
+        if fee == 0 {
+            return Ok(());
+        }
+
         invoke(
-            &system_instruction::transfer(self.payer.key, self.fee_acc.key, FEE_LAMPORTS),
+            &system_instruction::transfer(self.payer.key, self.fee_acc.key, fee),
             &[
                 self.payer.to_account_info(),
                 self.fee_acc.clone(),
@@ -82,6 +93,11 @@ pub fn handler(ctx: Context<InitFarmer>) -> Result<()> {
     farmer.vault = ctx.accounts.vault.key();
     farmer.reward_a.fixed_rate.promised_schedule = FixedRateSchedule::default(); //denom to 1
     farmer.reward_b.fixed_rate.promised_schedule = FixedRateSchedule::default(); //denom to 1
+    store_extension(
+        &farmer.to_account_info(),
+        Farmer::EXTENSION_OFFSET,
+        &FarmerExtension::latest(),
+    )?;
 
     // update farm
     let farm = &mut ctx.accounts.farm;
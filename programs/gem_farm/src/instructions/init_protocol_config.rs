@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::{program::GemFarm, state::*};
+
+#[derive(Accounts)]
+pub struct InitProtocolConfig<'info> {
+    #[account(init, seeds = [b"protocol_config".as_ref()],
+        bump,
+        payer = admin,
+        space = 8 + std::mem::size_of::<ProtocolConfig>())]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    // only the program's upgrade authority can set up the config
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, GemFarm>,
+    #[account(constraint = program_data.upgrade_authority_address == Some(admin.key()))]
+    pub program_data: Account<'info, ProgramData>,
+
+    // misc
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitProtocolConfig>, fee_wallet: Pubkey, fees: FeeTable) -> Result<()> {
+    let config = &mut ctx.accounts.protocol_config;
+
+    config.admin = ctx.accounts.admin.key();
+    config.fee_wallet = fee_wallet;
+    config.fees = fees;
+
+    msg!("protocol config initialized, fees go to {}", fee_wallet);
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use gem_common::{errors::ErrorCode, *};
+
+use crate::state::*;
+
+#[derive(Accounts)]
+#[instruction(bump_auth: u8)]
+pub struct InitTokenTreasury<'info> {
+    // farm
+    #[account(mut, has_one = farm_authority,
+        constraint = farm.version == LATEST_FARM_VERSION @ ErrorCode::OutdatedAccountVersion)]
+    pub farm: Box<Account<'info, Farm>>,
+    /// CHECK:
+    #[account(seeds = [farm.key().as_ref()], bump = bump_auth)]
+    pub farm_authority: AccountInfo<'info>,
+
+    // treasury
+    #[account(init, seeds = [
+            b"token_treasury".as_ref(),
+            farm.key().as_ref(),
+            fee_mint.key().as_ref(),
+        ],
+        bump,
+        token::mint = fee_mint,
+        token::authority = farm_authority,
+        payer = payer)]
+    pub token_treasury: Box<Account<'info, TokenAccount>>,
+    pub fee_mint: Box<Account<'info, Mint>>,
+
+    // misc
+    // permissionless - token fees can't be collected until the treasury exists
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub rent: Sysvar<'info, Rent>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitTokenTreasury>) -> Result<()> {
+    // so close_farm knows how many are still open
+    ctx.accounts.farm.token_treasury_count.try_add_assign(1)?;
+
+    msg!(
+        "token treasury initialized for mint {}",
+        ctx.accounts.fee_mint.key()
+    );
+    Ok(())
+}
@@ -0,0 +1,183 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{program::invoke, system_instruction},
+};
+use gem_bank::{
+    self,
+    cpi::accounts::SetVaultLock,
+    program::GemBank,
+    state::{Bank, Vault},
+};
+use gem_common::{errors::ErrorCode, *};
+
+use crate::{events::*, instructions::collect_token_fee, state::*};
+
+#[derive(Accounts)]
+#[instruction(bump_auth: u8, bump_treasury: u8, bump_farmer: u8)]
+pub struct InstantUnstake<'info> {
+    // farm
+    #[account(mut, has_one = farm_authority, has_one = farm_treasury, has_one = bank,
+        constraint = farm.version == LATEST_FARM_VERSION @ ErrorCode::OutdatedAccountVersion)]
+    pub farm: Box<Account<'info, Farm>>,
+    /// CHECK:
+    #[account(seeds = [farm.key().as_ref()], bump = bump_auth)]
+    pub farm_authority: AccountInfo<'info>,
+    /// CHECK:
+    #[account(mut, seeds = [b"treasury".as_ref(), farm.key().as_ref()], bump = bump_treasury)]
+    pub farm_treasury: AccountInfo<'info>,
+
+    // farmer
+    #[account(mut, has_one = farm, has_one = identity, has_one = vault,
+        seeds = [
+            b"farmer".as_ref(),
+            farm.key().as_ref(),
+            identity.key().as_ref(),
+        ],
+        bump = bump_farmer,
+        constraint = Farmer::version(&farmer.to_account_info())? == LATEST_FARMER_VERSION @ ErrorCode::OutdatedAccountVersion)]
+    pub farmer: Box<Account<'info, Farmer>>,
+    #[account(mut)]
+    pub identity: Signer<'info>,
+
+    // cpi
+    #[account(constraint = bank.bank_manager == farm_authority.key())]
+    pub bank: Box<Account<'info, Bank>>,
+    #[account(mut)]
+    pub vault: Box<Account<'info, Vault>>,
+    pub gem_bank: Program<'info, GemBank>,
+
+    //misc
+    // only one can ever exist (see init_protocol_config), so no need to check seeds
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+    /// CHECK:
+    #[account(mut, address = protocol_config.fee_wallet)]
+    pub fee_acc: AccountInfo<'info>,
+    // only exists if the farm doesn't pay protocol fees (see add_fee_exemption)
+    /// CHECK:
+    #[account(seeds = [b"fee_exemption".as_ref(), farm.key().as_ref()], bump)]
+    pub fee_exemption: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InstantUnstake<'info> {
+    fn set_lock_vault_ctx(&self) -> CpiContext<'_, '_, '_, 'info, SetVaultLock<'info>> {
+        CpiContext::new(
+            self.gem_bank.to_account_info(),
+            SetVaultLock {
+                bank: self.bank.to_account_info(),
+                vault: self.vault.to_account_info(),
+                bank_manager: self.farm_authority.clone(),
+            },
+        )
+    }
+
+    fn pay_treasury(&self, lamports: u64) -> Result<()> {
+        invoke(
+            &system_instruction::transfer(self.identity.key, self.farm_treasury.key, lamports),
+            &[
+                self.identity.to_account_info(),
+                self.farm_treasury.clone(),
+                self.system_program.to_account_info(),
+            ],
+        )
+        .map_err(Into::into)
+    }
+
+    /// the treasury has to end up rent exempt, so a small fee into an empty one is rounded up
+    fn rent_exempt_fee(&self, fee: u64) -> Result<u64> {
+        let min_balance = Rent::get()?.minimum_balance(0);
+        let missing = min_balance.saturating_sub(self.farm_treasury.lamports());
+        Ok(std::cmp::max(fee, missing))
+    }
+
+    /// returns the protocol fee actually charged
+    fn transfer_fee(&self) -> Result<u64> {
+        let fee = self.protocol_config.fee_for(
+            &self.fee_exemption,
+            self.protocol_config.fees.instant_unstake_lamp,
+        );
+        if fee == 0 {
+            return Ok(0);
+        }
+
+        invoke(
+            &system_instruction::transfer(self.identity.key, self.fee_acc.key, fee),
+            &[
+                self.identity.to_account_info(),
+                self.fee_acc.clone(),
+                self.system_program.to_account_info(),
+            ],
+        )?;
+
+        Ok(fee)
+    }
+}
+
+pub fn handler<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, InstantUnstake<'info>>,
+) -> Result<()> {
+    // a staked farmer would have paid this on their way into cooldown
+    let farm = &ctx.accounts.farm;
+    let in_emergency = farm.is_in_emergency();
+
+    let unstaking_fee = if !in_emergency && ctx.accounts.farmer.state == FarmerState::Staked {
+        farm.config.unstaking_fee_lamp
+    } else {
+        0
+    };
+    if unstaking_fee > 0 {
+        ctx.accounts.pay_treasury(unstaking_fee)?
+    }
+
+    // and the farm's own token fee, if any
+    if !in_emergency && ctx.accounts.farmer.state == FarmerState::Staked {
+        let unstake_fee = Farm::extension(&farm.to_account_info())?
+            .token_fees
+            .unstake_fee;
+        collect_token_fee(
+            farm,
+            unstake_fee,
+            &ctx.accounts.identity.to_account_info(),
+            ctx.remaining_accounts,
+        )?;
+    }
+
+    // update accrued rewards BEFORE we decrement the stake
+    let farm = &mut ctx.accounts.farm;
+    let farmer = &mut ctx.accounts.farmer;
+    let now_ts = now_ts()?;
+
+    farm.update_rewards(now_ts, Some(farmer), false)?;
+
+    // skip min staking / cooldown, the fee scales with how much of it is being skipped
+    let mut exit_fee = farm.end_staking_instantly(now_ts, farmer)?;
+
+    if exit_fee > 0 {
+        exit_fee = ctx.accounts.rent_exempt_fee(exit_fee)?;
+        ctx.accounts.pay_treasury(exit_fee)?;
+    }
+
+    // unlock the vault so the user can withdraw their gems
+    gem_bank::cpi::set_vault_lock(
+        ctx.accounts
+            .set_lock_vault_ctx()
+            .with_signer(&[&ctx.accounts.farm.farm_seeds()]),
+        false,
+    )?;
+
+    //collect a fee for unstaking
+    let protocol_fee = if !in_emergency {
+        ctx.accounts.transfer_fee()?
+    } else {
+        0
+    };
+
+    emit!(FarmerUnstaked::new(
+        &ctx.accounts.farmer,
+        unstaking_fee.try_add(exit_fee)?.try_add(protocol_fee)?,
+        now_ts
+    ));
+
+    msg!("instantly unstaked, paid {} lamports exit fee", exit_fee);
+    Ok(())
+}
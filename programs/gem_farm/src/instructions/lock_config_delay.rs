@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+use gem_common::errors::ErrorCode;
+
+use crate::state::{Farm, LATEST_FARM_VERSION};
+
+#[derive(Accounts)]
+pub struct LockConfigDelay<'info> {
+    // farm
+    #[account(mut, has_one = farm_manager,
+        constraint = farm.version == LATEST_FARM_VERSION @ ErrorCode::OutdatedAccountVersion)]
+    pub farm: Box<Account<'info, Farm>>,
+    pub farm_manager: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<LockConfigDelay>) -> Result<()> {
+    let farm = &mut ctx.accounts.farm;
+
+    farm.config_delay_locked = true;
+
+    msg!("config delay locked at {}s", farm.config_delay_sec);
+    Ok(())
+}
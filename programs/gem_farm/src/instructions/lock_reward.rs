@@ -1,13 +1,20 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::Mint;
+use gem_common::{errors::ErrorCode, *};
 
-use crate::state::Farm;
+use crate::{
+    events::*,
+    state::{Farm, FarmRole, LATEST_FARM_VERSION},
+};
 
 #[derive(Accounts)]
 pub struct LockReward<'info> {
     // farm
-    #[account(mut, has_one = farm_manager)]
+    #[account(mut,
+        constraint = farm.version == LATEST_FARM_VERSION @ ErrorCode::OutdatedAccountVersion,
+        constraint = farm.has_role(&farm.to_account_info(), FarmRole::RewardAdmin, farm_manager.key())? @ ErrorCode::MissingRole)]
     pub farm: Box<Account<'info, Farm>>,
+    // the farm manager, or the reward admin
     #[account(mut)]
     pub farm_manager: Signer<'info>,
 
@@ -18,7 +25,16 @@ pub struct LockReward<'info> {
 pub fn handler(ctx: Context<LockReward>) -> Result<()> {
     let farm = &mut ctx.accounts.farm;
 
-    farm.lock_reward_by_mint(ctx.accounts.reward_mint.key())?;
+    let reward_mint = ctx.accounts.reward_mint.key();
+
+    farm.lock_reward_by_mint(reward_mint)?;
+
+    emit!(RewardLocked {
+        farm: farm.key(),
+        reward_mint,
+        lock_end_ts: farm.match_reward_by_mint(reward_mint)?.times.lock_end_ts,
+        ts: now_ts()?,
+    });
 
     Ok(())
 }
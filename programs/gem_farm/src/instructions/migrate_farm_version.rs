@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+use gem_common::*;
+
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct MigrateFarmVersion<'info> {
+    // farm
+    #[account(mut)]
+    pub farm: Box<Account<'info, Farm>>,
+
+    // misc
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// permissionless - anyone willing to pay the extra rent can bring a farm up to date
+pub fn handler(ctx: Context<MigrateFarmVersion>) -> Result<()> {
+    let farm = &mut ctx.accounts.farm;
+
+    if farm.version == 0 {
+        realloc_account(
+            &farm.to_account_info(),
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            Farm::SPACE,
+        )?;
+        farm.version = 1;
+    }
+
+    // farms that haven't accrued anything yet can still track their liability exactly
+    farm.reward_a.track_liability();
+    farm.reward_b.track_liability();
+
+    msg!("farm {} at version {}", farm.key(), farm.version);
+    Ok(())
+}
@@ -0,0 +1,232 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{program::invoke, system_instruction},
+};
+use gem_bank::{
+    self,
+    cpi::accounts::TransferVault,
+    program::GemBank,
+    state::{Bank, Vault},
+};
+use gem_common::{errors::ErrorCode, *};
+
+use crate::{
+    events::*,
+    instructions::{collect_token_fee, split_token_fee_accounts},
+    state::*,
+};
+
+#[derive(Accounts)]
+#[instruction(bump_from_auth: u8, bump_to_auth: u8, bump_from_farmer: u8, bump_proof: u8)]
+pub struct MigrateFarmer<'info> {
+    // farm being left
+    #[account(mut, constraint = from_farm.farm_authority == from_farm_authority.key(),
+        constraint = from_farm.bank == from_bank.key(),
+        constraint = from_farm.version == LATEST_FARM_VERSION @ ErrorCode::OutdatedAccountVersion)]
+    pub from_farm: Box<Account<'info, Farm>>,
+    /// CHECK:
+    #[account(seeds = [from_farm.key().as_ref()], bump = bump_from_auth)]
+    pub from_farm_authority: AccountInfo<'info>,
+    #[account(mut, has_one = identity, has_one = vault,
+        constraint = from_farmer.farm == from_farm.key(),
+        seeds = [
+            b"farmer".as_ref(),
+            from_farm.key().as_ref(),
+            identity.key().as_ref(),
+        ],
+        bump = bump_from_farmer,
+        constraint = Farmer::version(&from_farmer.to_account_info())? == LATEST_FARMER_VERSION @ ErrorCode::OutdatedAccountVersion)]
+    pub from_farmer: Box<Account<'info, Farmer>>,
+
+    // farm being joined
+    #[account(mut, constraint = to_farm.farm_authority == to_farm_authority.key(),
+        constraint = to_farm.bank == to_bank.key(),
+        constraint = to_farm.version == LATEST_FARM_VERSION @ ErrorCode::OutdatedAccountVersion)]
+    pub to_farm: Box<Account<'info, Farm>>,
+    /// CHECK:
+    #[account(seeds = [to_farm.key().as_ref()], bump = bump_to_auth)]
+    pub to_farm_authority: AccountInfo<'info>,
+    #[account(init, seeds = [
+            b"farmer".as_ref(),
+            to_farm.key().as_ref(),
+            identity.key().as_ref(),
+        ],
+        bump,
+        payer = authority,
+        space = Farmer::SPACE)]
+    pub to_farmer: Box<Account<'info, Farmer>>,
+
+    // migration
+    #[account(has_one = from_farm, has_one = to_farm,
+        seeds = [
+            b"migration".as_ref(),
+            from_farm.key().as_ref(),
+            to_farm.key().as_ref(),
+        ],
+        bump = bump_proof)]
+    pub migration_proof: Box<Account<'info, MigrationProof>>,
+    /// CHECK:
+    #[account(mut)] //receives the old farmer's rent, if it can be closed
+    pub identity: AccountInfo<'info>,
+    // either the farmer themselves, or the manager of the farm being left
+    #[account(mut, constraint = authority.key() == identity.key()
+        || authority.key() == from_farm.farm_manager)]
+    pub authority: Signer<'info>,
+
+    // cpi
+    // each farm is the manager of its own bank, so two farms can never share one
+    #[account(mut, constraint = from_bank.bank_manager == from_farm_authority.key())]
+    pub from_bank: Box<Account<'info, Bank>>,
+    #[account(mut, constraint = to_bank.bank_manager == to_farm_authority.key())]
+    pub to_bank: Box<Account<'info, Bank>>,
+    #[account(mut)]
+    pub vault: Box<Account<'info, Vault>>,
+    pub gem_bank: Program<'info, GemBank>,
+
+    // misc
+    // only one can ever exist (see init_protocol_config), so no need to check seeds
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+    /// CHECK:
+    #[account(mut, address = protocol_config.fee_wallet)]
+    pub fee_acc: AccountInfo<'info>,
+    // only exists if the farm doesn't pay protocol fees (see add_fee_exemption)
+    /// CHECK:
+    #[account(seeds = [b"fee_exemption".as_ref(), to_farm.key().as_ref()], bump)]
+    pub fee_exemption: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+    //
+    // remaining accounts, for EACH gem box in the vault:
+    // - gem_deposit_receipt
+    // - gem_rarity <- recorded on the bank being joined
+    // followed by the token fee accounts (see collect_token_fee), if the farm being joined
+    // charges a stake fee
+}
+
+impl<'info> MigrateFarmer<'info> {
+    fn transfer_vault_ctx(&self) -> CpiContext<'_, '_, '_, 'info, TransferVault<'info>> {
+        CpiContext::new(
+            self.gem_bank.to_account_info(),
+            TransferVault {
+                bank: self.from_bank.to_account_info(),
+                bank_manager: self.from_farm_authority.clone(),
+                new_bank: self.to_bank.to_account_info(),
+                new_bank_manager: self.to_farm_authority.clone(),
+                vault: self.vault.to_account_info(),
+            },
+        )
+    }
+
+    /// same as init_farmer + stake on the farm being joined
+    fn transfer_fee(&self) -> Result<()> {
+        let fees = &self.protocol_config.fees;
+        let fee = self.protocol_config.fee_for(
+            &self.fee_exemption,
+            fees.init_farmer_lamp.try_add(fees.stake_lamp)?,
+        );
+        if fee == 0 {
+            return Ok(());
+        }
+
+        invoke(
+            &system_instruction::transfer(self.authority.key, self.fee_acc.key, fee),
+            &[
+                self.authority.to_account_info(),
+                self.fee_acc.clone(),
+                self.system_program.to_account_info(),
+            ],
+        )
+        .map_err(Into::into)
+    }
+}
+
+pub fn handler<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, MigrateFarmer<'info>>,
+) -> Result<()> {
+    // gems pending withdrawal would be staked all over again on the farm being joined
+    if Farmer::pending_withdrawal(&ctx.accounts.from_farmer.to_account_info())?.amount > 0 {
+        return Err(error!(ErrorCode::WithdrawalPending));
+    }
+
+    let stake_fee = Farm::extension(&ctx.accounts.to_farm.to_account_info())?
+        .token_fees
+        .stake_fee;
+    let (vault_accounts, token_fee_accounts) =
+        split_token_fee_accounts(ctx.remaining_accounts, stake_fee)?;
+
+    // settle rewards on the farm being left, then take the farmer out without a cooldown
+    let from_farm = &mut ctx.accounts.from_farm;
+    let from_farmer = &mut ctx.accounts.from_farmer;
+    let now_ts = now_ts()?;
+
+    from_farm.update_rewards(now_ts, Some(from_farmer), false)?;
+    from_farm.migrate_staked_farmer(now_ts, from_farmer)?;
+
+    emit!(FarmerUnstaked::new(from_farmer, 0, now_ts));
+
+    // hand the vault over to the bank being joined (it stays locked throughout)
+    gem_bank::cpi::transfer_vault(
+        ctx.accounts
+            .transfer_vault_ctx()
+            .with_signer(&[
+                &ctx.accounts.from_farm.farm_seeds(),
+                &ctx.accounts.to_farm.farm_seeds(),
+            ])
+            .with_remaining_accounts(vault_accounts.to_vec()),
+    )?;
+
+    ctx.accounts.vault.reload()?;
+
+    // the old farmer no longer has a vault to close. If nothing is left to claim it goes now,
+    // otherwise close_farmer skips the vault once the farmer has claimed
+    if ctx.accounts.from_farmer.can_close()? {
+        ctx.accounts.from_farm.farmer_count.try_sub_assign(1)?;
+
+        close_account(
+            &mut ctx.accounts.from_farmer.to_account_info(),
+            &mut ctx.accounts.identity.to_account_info(),
+        )?;
+    }
+
+    // record new farmer details
+    let to_farmer = &mut ctx.accounts.to_farmer;
+
+    to_farmer.farm = ctx.accounts.to_farm.key();
+    to_farmer.identity = ctx.accounts.identity.key();
+    to_farmer.vault = ctx.accounts.vault.key();
+    to_farmer.reward_a.fixed_rate.promised_schedule = FixedRateSchedule::default(); //denom to 1
+    to_farmer.reward_b.fixed_rate.promised_schedule = FixedRateSchedule::default(); //denom to 1
+    store_extension(
+        &to_farmer.to_account_info(),
+        Farmer::EXTENSION_OFFSET,
+        &FarmerExtension::latest(),
+    )?;
+
+    // stake on the farm being joined
+    let to_farm = &mut ctx.accounts.to_farm;
+
+    to_farm.farmer_count.try_add_assign(1)?;
+    to_farm.begin_staking(
+        now_ts,
+        ctx.accounts.vault.gem_count,
+        ctx.accounts.vault.rarity_points,
+        to_farmer,
+    )?;
+
+    emit!(FarmerStaked::new(to_farmer, now_ts));
+
+    // the farmer joins the new farm same as through init_farmer + stake, so same fees
+    collect_token_fee(
+        &ctx.accounts.to_farm,
+        stake_fee,
+        &ctx.accounts.authority.to_account_info(),
+        token_fee_accounts,
+    )?;
+    ctx.accounts.transfer_fee()?;
+
+    msg!(
+        "{} gems migrated to farm {}",
+        ctx.accounts.vault.gem_count,
+        ctx.accounts.to_farm.key()
+    );
+    Ok(())
+}
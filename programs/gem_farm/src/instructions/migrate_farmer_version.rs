@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use gem_common::*;
+
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct MigrateFarmerVersion<'info> {
+    // farmer
+    #[account(mut)]
+    pub farmer: Box<Account<'info, Farmer>>,
+
+    // misc
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// permissionless - anyone willing to pay the extra rent can bring a farmer up to date
+pub fn handler(ctx: Context<MigrateFarmerVersion>) -> Result<()> {
+    let farmer = ctx.accounts.farmer.to_account_info();
+
+    if Farmer::version(&farmer)? == 0 {
+        realloc_account(
+            &farmer,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            Farmer::SPACE,
+        )?;
+        store_extension(
+            &farmer,
+            Farmer::EXTENSION_OFFSET,
+            &FarmerExtension::latest(),
+        )?;
+    }
+
+    msg!(
+        "farmer {} at version {}",
+        farmer.key(),
+        Farmer::version(&farmer)?
+    );
+    Ok(())
+}
@@ -1,44 +1,124 @@
+pub mod accept_farm_manager;
+pub mod add_fee_exemption;
 pub mod add_rarities_to_bank;
 pub mod add_to_bank_whitelist;
+pub mod apply_farm_config;
+pub mod audit_farm;
 pub mod authorize_funder;
+pub mod authorize_migration;
+pub mod begin_flash_withdraw;
+pub mod cancel_farm_config;
+pub mod cancel_farm_manager_transfer;
 pub mod cancel_reward;
 pub mod claim;
+pub mod claim_delegated;
+pub mod close_farm;
+pub mod close_farmer;
+pub mod crank_claim;
 pub mod deauthorize_funder;
+pub mod deauthorize_migration;
+pub mod declare_emergency;
+pub mod disable_auto_claim;
+pub mod distribute_treasury;
+pub mod enable_auto_claim;
 pub mod flash_deposit;
 pub mod flash_deposit_pnft;
+pub mod flash_withdraw;
+pub mod flash_withdraw_pnft;
 pub mod fund_reward;
 pub mod init_farm;
 pub mod init_farmer;
+pub mod init_protocol_config;
+pub mod init_token_treasury;
+pub mod instant_unstake;
+pub mod lock_config_delay;
 pub mod lock_reward;
+pub mod migrate_farm_version;
+pub mod migrate_farmer;
+pub mod migrate_farmer_version;
+pub mod propose_farm_config;
+pub mod quote_farmer_rewards;
+pub mod quote_fund_reward;
 pub mod refresh_farmer;
 pub mod refresh_farmer_signed;
+pub mod remove_fee_exemption;
 pub mod remove_from_bank_whitelist;
+pub mod rescue_surplus;
+pub mod roll_surplus;
+pub mod set_farm_role;
+pub mod set_funding_paused;
+pub mod set_treasury_splits;
 pub mod stake;
+pub mod sweep_dust;
+pub mod token_fee;
+pub mod token_treasury_payout;
 pub mod treasury_payout;
 pub mod unstake;
+pub mod update_claim_delegate;
 pub mod update_farm;
+pub mod update_protocol_config;
 
+pub use accept_farm_manager::*;
+pub use add_fee_exemption::*;
 pub use add_rarities_to_bank::*;
 pub use add_to_bank_whitelist::*;
 // have to duplicate or this won't show up in IDL
 use anchor_lang::prelude::*;
+pub use apply_farm_config::*;
+pub use audit_farm::*;
 pub use authorize_funder::*;
+pub use authorize_migration::*;
+pub use begin_flash_withdraw::*;
+pub use cancel_farm_config::*;
+pub use cancel_farm_manager_transfer::*;
 pub use cancel_reward::*;
 pub use claim::*;
+pub use claim_delegated::*;
+pub use close_farm::*;
+pub use close_farmer::*;
+pub use crank_claim::*;
 pub use deauthorize_funder::*;
+pub use deauthorize_migration::*;
+pub use declare_emergency::*;
+pub use disable_auto_claim::*;
+pub use distribute_treasury::*;
+pub use enable_auto_claim::*;
 pub use flash_deposit::*;
 pub use flash_deposit_pnft::*;
+pub use flash_withdraw::*;
+pub use flash_withdraw_pnft::*;
 pub use fund_reward::*;
 pub use init_farm::*;
 pub use init_farmer::*;
+pub use init_protocol_config::*;
+pub use init_token_treasury::*;
+pub use instant_unstake::*;
+pub use lock_config_delay::*;
 pub use lock_reward::*;
+pub use migrate_farm_version::*;
+pub use migrate_farmer::*;
+pub use migrate_farmer_version::*;
+pub use propose_farm_config::*;
+pub use quote_farmer_rewards::*;
+pub use quote_fund_reward::*;
 pub use refresh_farmer::*;
 pub use refresh_farmer_signed::*;
+pub use remove_fee_exemption::*;
 pub use remove_from_bank_whitelist::*;
+pub use rescue_surplus::*;
+pub use roll_surplus::*;
+pub use set_farm_role::*;
+pub use set_funding_paused::*;
+pub use set_treasury_splits::*;
 pub use stake::*;
+pub use sweep_dust::*;
+pub use token_fee::*;
+pub use token_treasury_payout::*;
 pub use treasury_payout::*;
 pub use unstake::*;
+pub use update_claim_delegate::*;
 pub use update_farm::*;
+pub use update_protocol_config::*;
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq)]
 pub struct RarityConfig {
     pub mint: Pubkey,
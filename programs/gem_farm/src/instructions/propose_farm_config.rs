@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+use gem_common::{errors::ErrorCode, *};
+
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct ProposeFarmConfig<'info> {
+    // farm
+    #[account(has_one = farm_manager,
+        constraint = farm.version == LATEST_FARM_VERSION @ ErrorCode::OutdatedAccountVersion)]
+    pub farm: Box<Account<'info, Farm>>,
+    #[account(mut)]
+    pub farm_manager: Signer<'info>,
+
+    // pending config
+    // only one proposal at a time - cancel_farm_config first to replace it
+    #[account(init, seeds = [
+            b"pending_config".as_ref(),
+            farm.key().as_ref(),
+        ],
+        bump,
+        payer = farm_manager,
+        space = 8 + std::mem::size_of::<PendingFarmConfig>())]
+    pub pending_farm_config: Box<Account<'info, PendingFarmConfig>>,
+
+    // misc
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<ProposeFarmConfig>,
+    config: Option<FarmConfig>,
+    max_counts: Option<MaxCounts>,
+    token_fees: Option<TokenFees>,
+    instant_unstake_fee_lamp_per_sec: Option<u64>,
+    config_delay_sec: Option<u64>,
+) -> Result<()> {
+    let farm = &ctx.accounts.farm;
+    let pending = &mut ctx.accounts.pending_farm_config;
+    let now_ts = now_ts()?;
+
+    // whatever isn't being changed is carried over as is
+    let config = config.unwrap_or(farm.config);
+    config.assert_valid()?;
+
+    pending.farm = farm.key();
+    pending.applies_ts = now_ts.try_add(farm.config_delay_sec)?;
+    pending.config = config;
+    pending.max_counts = max_counts.unwrap_or(farm.max_counts);
+
+    let token_fees = match token_fees {
+        Some(token_fees) => token_fees,
+        None => Farm::extension(&farm.to_account_info())?.token_fees,
+    };
+    token_fees.assert_valid()?;
+    pending.token_fees = token_fees;
+
+    pending.instant_unstake_fee_lamp_per_sec =
+        instant_unstake_fee_lamp_per_sec.unwrap_or(farm.instant_unstake_fee_lamp_per_sec);
+
+    // a locked delay can't be lowered (checked again when applied)
+    let config_delay_sec = config_delay_sec.unwrap_or(farm.config_delay_sec);
+    require!(
+        !farm.config_delay_locked || config_delay_sec >= farm.config_delay_sec,
+        ErrorCode::ConfigDelayLocked
+    );
+    pending.config_delay_sec = config_delay_sec;
+
+    msg!("farm config proposed, applies at {}", pending.applies_ts);
+    Ok(())
+}
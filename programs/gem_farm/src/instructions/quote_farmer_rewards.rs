@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+use gem_common::{errors::ErrorCode, *};
+
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct QuoteFarmerRewards<'info> {
+    // farm
+    #[account(constraint = farm.version == LATEST_FARM_VERSION @ ErrorCode::OutdatedAccountVersion)]
+    pub farm: Box<Account<'info, Farm>>,
+
+    // farmer
+    #[account(has_one = farm,
+        constraint = Farmer::version(&farmer.to_account_info())? == LATEST_FARMER_VERSION @ ErrorCode::OutdatedAccountVersion)]
+    pub farmer: Box<Account<'info, Farmer>>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct FarmerRewardQuote {
+    pub accrued_reward: u64,
+
+    pub paid_out_reward: u64,
+
+    /// what a claim would pay out right now, if the pot holds enough
+    pub outstanding_reward: u64,
+}
+
+impl FarmerRewardQuote {
+    fn new(reward: &FarmerReward) -> Result<Self> {
+        Ok(Self {
+            accrued_reward: reward.accrued_reward,
+            paid_out_reward: reward.paid_out_reward,
+            outstanding_reward: reward.outstanding_reward()?,
+        })
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct FarmerRewardsQuote {
+    pub ts: u64,
+
+    pub reward_a: FarmerRewardQuote,
+
+    pub reward_b: FarmerRewardQuote,
+}
+
+/// runs the same update a claim would, but against copies of the accounts,
+/// so nothing is written and the result comes back as return data
+pub fn handler(ctx: Context<QuoteFarmerRewards>) -> Result<FarmerRewardsQuote> {
+    let mut farm = ctx.accounts.farm.clone();
+    let farmer = &mut ctx.accounts.farmer.clone();
+    let now_ts = now_ts()?;
+
+    farm.update_rewards(now_ts, Some(farmer), true)?;
+
+    Ok(FarmerRewardsQuote {
+        ts: now_ts,
+        reward_a: FarmerRewardQuote::new(&farmer.reward_a)?,
+        reward_b: FarmerRewardQuote::new(&farmer.reward_b)?,
+    })
+}
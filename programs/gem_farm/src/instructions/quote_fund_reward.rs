@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+use gem_common::{errors::ErrorCode, *};
+
+use crate::{number128::Number128, state::*};
+
+#[derive(Accounts)]
+pub struct QuoteFundReward<'info> {
+    // farm
+    #[account(constraint = farm.version == LATEST_FARM_VERSION @ ErrorCode::OutdatedAccountVersion)]
+    pub farm: Box<Account<'info, Farm>>,
+
+    // reward
+    pub reward_mint: Box<Account<'info, Mint>>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct FundRewardQuote {
+    pub ts: u64,
+
+    pub reward_type: RewardType,
+
+    /// tokens/s, only meaningful for variable rate rewards
+    pub variable_reward_rate: Number128,
+
+    pub duration_sec: u64,
+
+    pub reward_end_ts: u64,
+
+    pub total_funded: u64,
+
+    /// funded, but not yet accrued to stakers nor refunded
+    pub pending_amount: u64,
+}
+
+/// runs the same update a fund_reward call would, but against a copy of the farm,
+/// so nothing is written and the result comes back as return data
+pub fn handler(
+    ctx: Context<QuoteFundReward>,
+    variable_rate_config: Option<VariableRateConfig>,
+    fixed_rate_config: Option<FixedRateConfig>,
+) -> Result<FundRewardQuote> {
+    let mut farm = ctx.accounts.farm.clone();
+    let now_ts = now_ts()?;
+    let reward_mint = ctx.accounts.reward_mint.key();
+
+    farm.update_rewards(now_ts, None, true)?;
+
+    farm.fund_reward_by_mint(now_ts, reward_mint, variable_rate_config, fixed_rate_config)?;
+
+    let reward = farm.match_reward_by_mint(reward_mint)?;
+
+    Ok(FundRewardQuote {
+        ts: now_ts,
+        reward_type: reward.reward_type,
+        variable_reward_rate: reward.variable_rate.reward_rate,
+        duration_sec: reward.times.duration_sec,
+        reward_end_ts: reward.times.reward_end_ts,
+        total_funded: reward.funds.total_funded,
+        pending_amount: reward.funds.pending_amount()?,
+    })
+}
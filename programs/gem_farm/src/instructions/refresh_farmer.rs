@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use gem_common::*;
+use gem_common::{errors::ErrorCode, *};
 
 use crate::state::*;
 
@@ -7,7 +7,8 @@ use crate::state::*;
 #[instruction(bump: u8)]
 pub struct RefreshFarmer<'info> {
     // farm
-    #[account(mut)]
+    #[account(mut,
+        constraint = farm.version == LATEST_FARM_VERSION @ ErrorCode::OutdatedAccountVersion)]
     pub farm: Box<Account<'info, Farm>>,
 
     // farmer
@@ -16,7 +17,8 @@ pub struct RefreshFarmer<'info> {
             farm.key().as_ref(),
             identity.key().as_ref(),
         ],
-        bump = bump)]
+        bump = bump,
+        constraint = Farmer::version(&farmer.to_account_info())? == LATEST_FARMER_VERSION @ ErrorCode::OutdatedAccountVersion)]
     pub farmer: Box<Account<'info, Farmer>>,
     //not a signer intentionally
     /// CHECK:
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+use gem_common::*;
+
+use crate::state::*;
+
+#[derive(Accounts)]
+#[instruction(bump: u8)]
+pub struct RemoveFeeExemption<'info> {
+    #[account(has_one = admin)]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    // farm
+    /// CHECK:
+    pub farm: AccountInfo<'info>,
+    #[account(mut, has_one = farm,
+        seeds = [
+            b"fee_exemption".as_ref(),
+            farm.key().as_ref(),
+        ],
+        bump = bump)]
+    fee_exemption: Box<Account<'info, FeeExemption>>,
+
+    // misc
+    system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<RemoveFeeExemption>) -> Result<()> {
+    // close fee exemption
+    close_account(
+        &mut ctx.accounts.fee_exemption.to_account_info(),
+        &mut ctx.accounts.admin.to_account_info(),
+    )?;
+
+    msg!("farm {} is no longer fee exempt", ctx.accounts.farm.key());
+    Ok(())
+}
@@ -5,14 +5,18 @@ use gem_bank::{
     program::GemBank,
     state::{Bank, WhitelistProof},
 };
+use gem_common::errors::ErrorCode;
 
 use crate::state::*;
 
 #[derive(Accounts)]
 #[instruction(bump_auth: u8)]
 pub struct RemoveFromBankWhitelist<'info> {
-    #[account(has_one = farm_manager, has_one = farm_authority, has_one = bank)]
+    #[account(has_one = farm_authority, has_one = bank,
+        constraint = farm.version == LATEST_FARM_VERSION @ ErrorCode::OutdatedAccountVersion,
+        constraint = farm.has_role(&farm.to_account_info(), FarmRole::WhitelistAdmin, farm_manager.key())? @ ErrorCode::MissingRole)]
     pub farm: Box<Account<'info, Farm>>,
+    // the farm manager, or the whitelist admin
     #[account(mut)]
     pub farm_manager: Signer<'info>,
     /// CHECK:
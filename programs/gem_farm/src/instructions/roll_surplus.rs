@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, TokenAccount};
+use gem_common::{errors::ErrorCode, *};
+
+use crate::{events::*, state::*};
+
+#[derive(Accounts)]
+#[instruction(bump_pot: u8)]
+pub struct RollSurplus<'info> {
+    // farm
+    #[account(mut,
+        constraint = farm.version == LATEST_FARM_VERSION @ ErrorCode::OutdatedAccountVersion,
+        constraint = farm.has_role(&farm.to_account_info(), FarmRole::RewardAdmin, farm_manager.key())? @ ErrorCode::MissingRole)]
+    pub farm: Box<Account<'info, Farm>>,
+    // the farm manager, or the reward admin
+    pub farm_manager: Signer<'info>,
+
+    // reward
+    #[account(seeds = [
+            b"reward_pot".as_ref(),
+            farm.key().as_ref(),
+            reward_mint.key().as_ref(),
+        ],
+        bump = bump_pot)]
+    pub reward_pot: Box<Account<'info, TokenAccount>>,
+    pub reward_mint: Box<Account<'info, Mint>>,
+}
+
+/// like rescue_surplus, but the surplus stays in the pot and is recorded as funding for the
+/// reward instead (see FarmReward::fund_surplus)
+pub fn handler(ctx: Context<RollSurplus>) -> Result<()> {
+    // update existing rewards
+    let farm = &mut ctx.accounts.farm;
+    let now_ts = now_ts()?;
+    let reward_mint = ctx.accounts.reward_mint.key();
+
+    farm.update_rewards(now_ts, None, true)?;
+
+    // anything the reward's accounting doesn't know about
+    let surplus = farm
+        .match_reward_by_mint(reward_mint)?
+        .surplus(ctx.accounts.reward_pot.amount)?;
+
+    farm.fund_surplus_by_mint(now_ts, reward_mint, surplus)?;
+
+    emit!(SurplusRescued {
+        farm: farm.key(),
+        reward_mint,
+        amount: surplus,
+        rolled_into_funding: true,
+        ts: now_ts,
+    });
+
+    msg!(
+        "{} surplus of {} tokens rolled into funding",
+        reward_mint,
+        surplus
+    );
+    Ok(())
+}
@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+use gem_common::errors::ErrorCode;
+
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct SetFarmRole<'info> {
+    // farm
+    #[account(mut, has_one = farm_manager,
+        constraint = farm.version == LATEST_FARM_VERSION @ ErrorCode::OutdatedAccountVersion)]
+    pub farm: Box<Account<'info, Farm>>,
+    pub farm_manager: Signer<'info>,
+}
+
+/// pass the default Pubkey as admin to revoke the role
+pub fn handler(ctx: Context<SetFarmRole>, role: FarmRole, admin: Pubkey) -> Result<()> {
+    let farm_info = ctx.accounts.farm.to_account_info();
+
+    let mut extension = Farm::extension(&farm_info)?;
+    *extension.role_admin(role) = admin;
+    Farm::set_extension(&farm_info, &extension)?;
+
+    msg!("{:?} set to: {}", role, admin);
+    Ok(())
+}
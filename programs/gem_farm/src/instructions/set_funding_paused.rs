@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+use gem_common::{errors::ErrorCode, *};
+
+use crate::{
+    events::*,
+    state::{Farm, FarmRole, LATEST_FARM_VERSION},
+};
+
+#[derive(Accounts)]
+pub struct SetFundingPaused<'info> {
+    // farm
+    #[account(mut,
+        constraint = farm.version == LATEST_FARM_VERSION @ ErrorCode::OutdatedAccountVersion,
+        constraint = farm.has_role(&farm.to_account_info(), FarmRole::RewardAdmin, farm_manager.key())? @ ErrorCode::MissingRole)]
+    pub farm: Box<Account<'info, Farm>>,
+    // the farm manager, or the reward admin
+    pub farm_manager: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetFundingPaused>, paused: bool) -> Result<()> {
+    let farm = &mut ctx.accounts.farm;
+
+    farm.funding_paused = paused;
+
+    emit!(FundingPauseUpdated {
+        farm: farm.key(),
+        paused,
+        audit_error_code: 0,
+        ts: now_ts()?,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+use gem_common::errors::ErrorCode;
+
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct SetTreasurySplits<'info> {
+    // farm
+    #[account(mut,
+        constraint = farm.version == LATEST_FARM_VERSION @ ErrorCode::OutdatedAccountVersion,
+        constraint = farm.has_role(&farm.to_account_info(), FarmRole::TreasuryAdmin, farm_manager.key())? @ ErrorCode::MissingRole)]
+    pub farm: Box<Account<'info, Farm>>,
+    // the farm manager, or the treasury admin
+    pub farm_manager: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetTreasurySplits>, splits: Vec<TreasurySplit>) -> Result<()> {
+    let farm_info = ctx.accounts.farm.to_account_info();
+
+    let mut extension = Farm::extension(&farm_info)?;
+    extension.set_treasury_splits(&splits)?;
+    Farm::set_extension(&farm_info, &extension)?;
+
+    msg!("treasury split between {} recipients", splits.len());
+    Ok(())
+}
@@ -1,4 +1,3 @@
-use crate::instructions::FEE_WALLET;
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::program::invoke;
 use anchor_lang::solana_program::system_instruction;
@@ -9,17 +8,15 @@ use gem_bank::{
     state::{Bank, Vault},
 };
 use gem_common::{errors::ErrorCode, *};
-use std::str::FromStr;
 
-use crate::state::*;
-
-const FEE_LAMPORTS: u64 = 2_000_000; // 0.002 SOL per stake/unstake
+use crate::{events::*, instructions::collect_token_fee, state::*};
 
 #[derive(Accounts)]
 #[instruction(bump_auth: u8, bump_farmer: u8)]
 pub struct Stake<'info> {
     // farm
-    #[account(mut, has_one = farm_authority, has_one = bank)]
+    #[account(mut, has_one = farm_authority, has_one = bank,
+        constraint = farm.version == LATEST_FARM_VERSION @ ErrorCode::OutdatedAccountVersion)]
     pub farm: Box<Account<'info, Farm>>,
     /// CHECK:
     #[account(seeds = [farm.key().as_ref()], bump = bump_auth)]
@@ -32,7 +29,8 @@ pub struct Stake<'info> {
             farm.key().as_ref(),
             identity.key().as_ref(),
         ],
-        bump = bump_farmer)]
+        bump = bump_farmer,
+        constraint = Farmer::version(&farmer.to_account_info())? == LATEST_FARMER_VERSION @ ErrorCode::OutdatedAccountVersion)]
     pub farmer: Box<Account<'info, Farmer>>,
     #[account(mut)]
     pub identity: Signer<'info>,
@@ -44,9 +42,15 @@ pub struct Stake<'info> {
     pub vault: Box<Account<'info, Vault>>,
     pub gem_bank: Program<'info, GemBank>,
 
+    // only one can ever exist (see init_protocol_config), so no need to check seeds
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
     /// CHECK:
-    #[account(mut, address = Pubkey::from_str(FEE_WALLET).unwrap())]
+    #[account(mut, address = protocol_config.fee_wallet)]
     pub fee_acc: AccountInfo<'info>,
+    // only exists if the farm doesn't pay protocol fees (see add_fee_exemption)
+    /// CHECK:
+    #[account(seeds = [b"fee_exemption".as_ref(), farm.key().as_ref()], bump)]
+    pub fee_exemption: AccountInfo<'info>,
     pub system_program: Program<'info, System>,
 }
 
@@ -63,8 +67,15 @@ impl<'info> Stake<'info> {
     }
 
     fn transfer_fee(&self) -> Result<()> {
+        let fee = self
+            .protocol_config
+            .fee_for(&self.fee_exemption, self.protocol_config.fees.stake_lamp);
+        if fee == 0 {
+            return Ok(());
+        }
+
         invoke(
-            &system_instruction::transfer(self.identity.key, self.fee_acc.key, FEE_LAMPORTS),
+            &system_instruction::transfer(self.identity.key, self.fee_acc.key, fee),
             &[
                 self.identity.to_account_info(),
                 self.fee_acc.clone(),
@@ -75,7 +86,7 @@ impl<'info> Stake<'info> {
     }
 }
 
-pub fn handler(ctx: Context<Stake>) -> Result<()> {
+pub fn handler<'a, 'b, 'c, 'info>(ctx: Context<'a, 'b, 'c, 'info, Stake<'info>>) -> Result<()> {
     if ctx.accounts.vault.gem_count == 0 {
         return Err(error!(ErrorCode::VaultIsEmpty));
     }
@@ -97,8 +108,23 @@ pub fn handler(ctx: Context<Stake>) -> Result<()> {
     farm.update_rewards(now_ts, Some(farmer), true)?;
 
     // begin staking
+    // (anything still pending withdrawal from an earlier stake is staked again with the rest)
+    Farmer::set_pending_withdrawal(&farmer.to_account_info(), PendingWithdrawal::default())?;
     farm.begin_staking(now_ts, vault.gem_count, vault.rarity_points, farmer)?;
 
+    emit!(FarmerStaked::new(farmer, now_ts));
+
+    //collect the farm's own token fee, if any
+    let stake_fee = Farm::extension(&ctx.accounts.farm.to_account_info())?
+        .token_fees
+        .stake_fee;
+    collect_token_fee(
+        &ctx.accounts.farm,
+        stake_fee,
+        &ctx.accounts.identity.to_account_info(),
+        ctx.remaining_accounts,
+    )?;
+
     //collect a fee for staking
     ctx.accounts.transfer_fee()?;
 
@@ -0,0 +1,97 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Mint, Token, TokenAccount, Transfer},
+};
+use gem_common::{errors::ErrorCode, *};
+
+use crate::{events::*, state::*};
+
+#[derive(Accounts)]
+#[instruction(bump_auth: u8, bump_pot: u8)]
+pub struct SweepDust<'info> {
+    // farm
+    #[account(mut, has_one = farm_authority,
+        constraint = farm.version == LATEST_FARM_VERSION @ ErrorCode::OutdatedAccountVersion,
+        constraint = farm.has_role(&farm.to_account_info(), FarmRole::RewardAdmin, farm_manager.key())? @ ErrorCode::MissingRole)]
+    pub farm: Box<Account<'info, Farm>>,
+    // the farm manager, or the reward admin
+    #[account(mut)]
+    pub farm_manager: Signer<'info>,
+    /// CHECK:
+    #[account(seeds = [farm.key().as_ref()], bump = bump_auth)]
+    pub farm_authority: AccountInfo<'info>,
+
+    // reward
+    #[account(mut, seeds = [
+            b"reward_pot".as_ref(),
+            farm.key().as_ref(),
+            reward_mint.key().as_ref(),
+        ],
+        bump = bump_pot)]
+    pub reward_pot: Box<Account<'info, TokenAccount>>,
+    #[account(init_if_needed,
+        associated_token::mint = reward_mint,
+        associated_token::authority = receiver,
+        payer = farm_manager)]
+    pub reward_destination: Box<Account<'info, TokenAccount>>,
+    pub reward_mint: Box<Account<'info, Mint>>,
+    // same as with cancellation, dust can be sent anywhere
+    /// CHECK:
+    #[account(mut)]
+    pub receiver: AccountInfo<'info>,
+
+    // misc
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> SweepDust<'info> {
+    fn transfer_ctx(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        CpiContext::new(
+            self.token_program.to_account_info(),
+            Transfer {
+                from: self.reward_pot.to_account_info(),
+                to: self.reward_destination.to_account_info(),
+                authority: self.farm_authority.to_account_info(),
+            },
+        )
+    }
+}
+
+pub fn handler(ctx: Context<SweepDust>) -> Result<()> {
+    // update existing rewards
+    let farm = &mut ctx.accounts.farm;
+    let now_ts = now_ts()?;
+
+    farm.update_rewards(now_ts, None, true)?;
+
+    // calculate dust amount while recording the sweep
+    let dust_amount = farm.sweep_dust_by_mint(now_ts, ctx.accounts.reward_mint.key())?;
+
+    // do the transfer
+    if dust_amount > 0 {
+        token::transfer(
+            ctx.accounts
+                .transfer_ctx()
+                .with_signer(&[&ctx.accounts.farm.farm_seeds()]),
+            dust_amount,
+        )?;
+    }
+
+    emit!(DustSwept {
+        farm: ctx.accounts.farm.key(),
+        reward_mint: ctx.accounts.reward_mint.key(),
+        amount: dust_amount,
+        ts: now_ts,
+    });
+
+    msg!(
+        "{} dust swept, {} tokens",
+        ctx.accounts.reward_mint.key(),
+        dust_amount,
+    );
+    Ok(())
+}
@@ -0,0 +1,117 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Transfer};
+use gem_common::errors::ErrorCode;
+
+use crate::state::*;
+
+/// Token fees are optional, so their accounts are passed as remaining accounts, in order:
+///   fee_source (mut) - the payer's token account for the fee mint
+///   token_treasury (mut) - the farm's token treasury for the fee mint
+///   token_program
+/// Nothing is expected if the fee is 0.
+pub fn collect_token_fee<'info>(
+    farm: &Account<'info, Farm>,
+    fee: u64,
+    payer: &AccountInfo<'info>,
+    remaining_accounts: &[AccountInfo<'info>],
+) -> Result<()> {
+    if fee == 0 {
+        return Ok(());
+    }
+
+    let fee_mint = Farm::extension(&farm.to_account_info())?
+        .token_fees
+        .fee_mint;
+
+    let accounts = &mut remaining_accounts.iter();
+    let fee_source = next_account_info(accounts)?;
+    let token_treasury = next_account_info(accounts)?;
+    let token_program = next_account_info(accounts)?;
+
+    let (token_treasury_key, _bump) = Pubkey::find_program_address(
+        &[
+            b"token_treasury".as_ref(),
+            farm.key().as_ref(),
+            fee_mint.as_ref(),
+        ],
+        &crate::ID,
+    );
+    if token_treasury.key() != token_treasury_key || token_program.key() != token::ID {
+        return Err(error!(ErrorCode::InvalidTokenFeeAccounts));
+    }
+
+    // the token program makes sure fee_source holds the same mint as the treasury
+    token::transfer(
+        CpiContext::new(
+            token_program.clone(),
+            Transfer {
+                from: fee_source.clone(),
+                to: token_treasury.clone(),
+                authority: payer.clone(),
+            },
+        ),
+        fee,
+    )
+}
+
+/// For instructions that forward their remaining accounts to a gem bank CPI, the token fee
+/// accounts (when the fee isn't 0) come LAST, after whatever the CPI needs.
+/// Returns (accounts for the cpi, token fee accounts).
+#[allow(clippy::type_complexity)]
+pub fn split_token_fee_accounts<'a, 'info>(
+    remaining_accounts: &'a [AccountInfo<'info>],
+    fee: u64,
+) -> Result<(&'a [AccountInfo<'info>], &'a [AccountInfo<'info>])> {
+    if fee == 0 {
+        return Ok((remaining_accounts, &[]));
+    }
+
+    let split = remaining_accounts
+        .len()
+        .checked_sub(3)
+        .ok_or_else(|| error!(ErrorCode::InvalidTokenFeeAccounts))?;
+    Ok(remaining_accounts.split_at(split))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_token_fee_accounts() {
+        let keys: Vec<Pubkey> = (0..5).map(|_| Pubkey::new_unique()).collect();
+        let owner = Pubkey::default();
+        let mut lamports = [0; 5];
+        let mut data = vec![vec![]; 5];
+        let accounts: Vec<AccountInfo> = keys
+            .iter()
+            .zip(lamports.iter_mut())
+            .zip(data.iter_mut())
+            .map(|((key, lamports), data)| {
+                AccountInfo::new(key, false, true, lamports, data, &owner, false, 0)
+            })
+            .collect();
+        let keys_of = |accounts: &[AccountInfo]| -> Vec<Pubkey> {
+            accounts.iter().map(|a| a.key()).collect()
+        };
+
+        // no fee - everything goes to the cpi, even if it looks like fee accounts
+        let (cpi, fee) = split_token_fee_accounts(&accounts, 0).unwrap();
+        assert_eq!(keys, keys_of(cpi));
+        assert!(fee.is_empty());
+
+        // fee - the last 3 are the fee accounts
+        let (cpi, fee) = split_token_fee_accounts(&accounts, 100).unwrap();
+        assert_eq!(keys[..2], keys_of(cpi)[..]);
+        assert_eq!(keys[2..], keys_of(fee)[..]);
+
+        // nothing left for the cpi
+        let (cpi, fee) = split_token_fee_accounts(&accounts[..3], 100).unwrap();
+        assert!(cpi.is_empty());
+        assert_eq!(keys[..3], keys_of(fee)[..]);
+
+        // fee accounts missing
+        assert!(split_token_fee_accounts(&accounts[..2], 100).is_err());
+        assert!(split_token_fee_accounts(&[], 100).is_err());
+    }
+}
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use gem_common::errors::ErrorCode;
+
+use crate::state::*;
+
+#[derive(Accounts)]
+#[instruction(bump_auth: u8, bump_treasury: u8)]
+pub struct TokenTreasuryPayout<'info> {
+    // farm
+    #[account(has_one = farm_authority,
+        constraint = farm.version == LATEST_FARM_VERSION @ ErrorCode::OutdatedAccountVersion,
+        constraint = farm.has_role(&farm.to_account_info(), FarmRole::TreasuryAdmin, farm_manager.key())? @ ErrorCode::MissingRole)]
+    pub farm: Box<Account<'info, Farm>>,
+    // the farm manager, or the treasury admin
+    pub farm_manager: Signer<'info>,
+    /// CHECK:
+    #[account(seeds = [farm.key().as_ref()], bump = bump_auth)]
+    pub farm_authority: AccountInfo<'info>,
+    #[account(mut, seeds = [
+            b"token_treasury".as_ref(),
+            farm.key().as_ref(),
+            token_treasury.mint.as_ref(),
+        ],
+        bump = bump_treasury)]
+    pub token_treasury: Box<Account<'info, TokenAccount>>,
+
+    // destination
+    #[account(mut)]
+    pub destination: Box<Account<'info, TokenAccount>>,
+
+    // misc
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> TokenTreasuryPayout<'info> {
+    fn transfer_ctx(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        CpiContext::new(
+            self.token_program.to_account_info(),
+            Transfer {
+                from: self.token_treasury.to_account_info(),
+                to: self.destination.to_account_info(),
+                authority: self.farm_authority.clone(),
+            },
+        )
+    }
+}
+
+pub fn handler(ctx: Context<TokenTreasuryPayout>, amount: u64) -> Result<()> {
+    token::transfer(
+        ctx.accounts
+            .transfer_ctx()
+            .with_signer(&[&ctx.accounts.farm.farm_seeds()]),
+        amount,
+    )?;
+
+    msg!(
+        "{} tokens paid out from token treasury {}",
+        amount,
+        ctx.accounts.token_treasury.key()
+    );
+    Ok(())
+}
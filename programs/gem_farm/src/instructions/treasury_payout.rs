@@ -2,6 +2,7 @@ use anchor_lang::{
     prelude::*,
     solana_program::{program::invoke_signed, system_instruction},
 };
+use gem_common::errors::ErrorCode;
 
 use crate::state::*;
 
@@ -9,8 +10,11 @@ use crate::state::*;
 #[instruction(bump_auth: u8, bump_treasury: u8)]
 pub struct TreasuryPayout<'info> {
     // farm
-    #[account(mut, has_one = farm_authority, has_one = farm_manager, has_one = farm_treasury)]
+    #[account(mut, has_one = farm_authority, has_one = farm_treasury,
+        constraint = farm.version == LATEST_FARM_VERSION @ ErrorCode::OutdatedAccountVersion,
+        constraint = farm.has_role(&farm.to_account_info(), FarmRole::TreasuryAdmin, farm_manager.key())? @ ErrorCode::MissingRole)]
     pub farm: Box<Account<'info, Farm>>,
+    // the farm manager, or the treasury admin
     pub farm_manager: Signer<'info>,
     /// CHECK:
     #[account(seeds = [farm.key().as_ref()], bump = bump_auth)]
@@ -1,4 +1,3 @@
-use crate::instructions::FEE_WALLET;
 use anchor_lang::{
     prelude::*,
     solana_program::{program::invoke, system_instruction},
@@ -9,18 +8,16 @@ use gem_bank::{
     program::GemBank,
     state::{Bank, Vault},
 };
-use gem_common::*;
-use std::str::FromStr;
+use gem_common::{errors::ErrorCode, *};
 
-use crate::state::*;
-
-const FEE_LAMPORTS: u64 = 1_000_000; // 0.002 SOL per entire unstake op (charged twice, so 0.001 2x)
+use crate::{events::*, instructions::collect_token_fee, state::*};
 
 #[derive(Accounts)]
 #[instruction(bump_auth: u8, bump_treasury: u8, bump_farmer: u8)]
 pub struct Unstake<'info> {
     // farm
-    #[account(mut, has_one = farm_authority, has_one = farm_treasury, has_one = bank)]
+    #[account(mut, has_one = farm_authority, has_one = farm_treasury, has_one = bank,
+        constraint = farm.version == LATEST_FARM_VERSION @ ErrorCode::OutdatedAccountVersion)]
     pub farm: Box<Account<'info, Farm>>,
     /// CHECK:
     #[account(seeds = [farm.key().as_ref()], bump = bump_auth)]
@@ -36,7 +33,8 @@ pub struct Unstake<'info> {
             farm.key().as_ref(),
             identity.key().as_ref(),
         ],
-        bump = bump_farmer)]
+        bump = bump_farmer,
+        constraint = Farmer::version(&farmer.to_account_info())? == LATEST_FARMER_VERSION @ ErrorCode::OutdatedAccountVersion)]
     pub farmer: Box<Account<'info, Farmer>>,
     #[account(mut)]
     pub identity: Signer<'info>,
@@ -49,9 +47,15 @@ pub struct Unstake<'info> {
     pub gem_bank: Program<'info, GemBank>,
 
     //misc
+    // only one can ever exist (see init_protocol_config), so no need to check seeds
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
     /// CHECK:
-    #[account(mut, address = Pubkey::from_str(FEE_WALLET).unwrap())]
+    #[account(mut, address = protocol_config.fee_wallet)]
     pub fee_acc: AccountInfo<'info>,
+    // only exists if the farm doesn't pay protocol fees (see add_fee_exemption)
+    /// CHECK:
+    #[account(seeds = [b"fee_exemption".as_ref(), farm.key().as_ref()], bump)]
+    pub fee_exemption: AccountInfo<'info>,
     pub system_program: Program<'info, System>,
 }
 
@@ -79,28 +83,61 @@ impl<'info> Unstake<'info> {
         .map_err(Into::into)
     }
 
-    fn transfer_fee(&self) -> Result<()> {
+    /// returns the protocol fee actually charged
+    fn transfer_fee(&self) -> Result<u64> {
+        let fee = self
+            .protocol_config
+            .fee_for(&self.fee_exemption, self.protocol_config.fees.unstake_lamp);
+        if fee == 0 {
+            return Ok(0);
+        }
+
         invoke(
-            &system_instruction::transfer(self.identity.key, self.fee_acc.key, FEE_LAMPORTS),
+            &system_instruction::transfer(self.identity.key, self.fee_acc.key, fee),
             &[
                 self.identity.to_account_info(),
                 self.fee_acc.clone(),
                 self.system_program.to_account_info(),
             ],
-        )
-        .map_err(Into::into)
+        )?;
+
+        Ok(fee)
     }
 }
 
-pub fn handler(ctx: Context<Unstake>, skip_rewards: bool) -> Result<()> {
-    // collect any unstaking fee
+pub fn handler<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, Unstake<'info>>,
+    skip_rewards: bool,
+) -> Result<()> {
+    // in an emergency farmers exit in a single call and don't pay any fees
     let farm = &ctx.accounts.farm;
+    let in_emergency = farm.is_in_emergency();
+
+    // collect any unstaking fee
+    let unstaking_fee = if !in_emergency && ctx.accounts.farmer.state == FarmerState::Staked {
+        farm.config.unstaking_fee_lamp
+    } else {
+        0
+    };
+    if unstaking_fee > 0 {
+        ctx.accounts.pay_treasury(unstaking_fee)?
+    }
 
-    if ctx.accounts.farmer.state == FarmerState::Staked && farm.config.unstaking_fee_lamp > 0 {
-        ctx.accounts.pay_treasury(farm.config.unstaking_fee_lamp)?
+    // and the farm's own token fee, if any
+    if !in_emergency && ctx.accounts.farmer.state == FarmerState::Staked {
+        let unstake_fee = Farm::extension(&farm.to_account_info())?
+            .token_fees
+            .unstake_fee;
+        collect_token_fee(
+            farm,
+            unstake_fee,
+            &ctx.accounts.identity.to_account_info(),
+            ctx.remaining_accounts,
+        )?;
     }
 
     // update accrued rewards BEFORE we decrement the stake
+    // (in an emergency, only up to the moment it was declared)
     let farm = &mut ctx.accounts.farm;
     let farmer = &mut ctx.accounts.farmer;
     let now_ts = now_ts()?;
@@ -125,7 +162,17 @@ pub fn handler(ctx: Context<Unstake>, skip_rewards: bool) -> Result<()> {
     }
 
     //collect a fee for unstaking
-    ctx.accounts.transfer_fee()?;
+    let protocol_fee = if !in_emergency {
+        ctx.accounts.transfer_fee()?
+    } else {
+        0
+    };
+
+    emit!(FarmerUnstaked::new(
+        &ctx.accounts.farmer,
+        unstaking_fee.try_add(protocol_fee)?,
+        now_ts
+    ));
 
     Ok(())
 }
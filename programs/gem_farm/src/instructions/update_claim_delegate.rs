@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use gem_common::errors::ErrorCode;
+
+use crate::state::*;
+
+#[derive(Accounts)]
+#[instruction(bump_farmer: u8)]
+pub struct UpdateClaimDelegate<'info> {
+    // farmer
+    #[account(mut, has_one = identity, seeds = [
+            b"farmer".as_ref(),
+            farmer.farm.as_ref(),
+            identity.key().as_ref(),
+        ],
+        bump = bump_farmer,
+        constraint = Farmer::version(&farmer.to_account_info())? == LATEST_FARMER_VERSION @ ErrorCode::OutdatedAccountVersion)]
+    pub farmer: Box<Account<'info, Farmer>>,
+    pub identity: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<UpdateClaimDelegate>,
+    claim_delegate: Pubkey,
+    claim_destination_a: Pubkey,
+    claim_destination_b: Pubkey,
+) -> Result<()> {
+    let farmer = &mut ctx.accounts.farmer;
+
+    // Pubkey::default() resets each of these
+    farmer.claim_delegate = claim_delegate;
+    farmer.reward_a.claim_destination = claim_destination_a;
+    farmer.reward_b.claim_destination = claim_destination_b;
+
+    msg!("claim delegate updated to: {}", claim_delegate);
+    Ok(())
+}
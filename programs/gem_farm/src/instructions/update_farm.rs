@@ -1,33 +1,41 @@
 use anchor_lang::prelude::*;
+use gem_common::errors::ErrorCode;
 
 use crate::state::*;
 
 #[derive(Accounts)]
 pub struct UpdateFarm<'info> {
     // farm
-    #[account(mut, has_one = farm_manager)]
+    #[account(mut, has_one = farm_manager,
+        constraint = farm.version == LATEST_FARM_VERSION @ ErrorCode::OutdatedAccountVersion)]
     pub farm: Box<Account<'info, Farm>>,
     pub farm_manager: Signer<'info>,
 }
 
+/// config, max counts and fees go through propose_farm_config instead, so farmers get a heads up
 pub fn handler(
     ctx: Context<UpdateFarm>,
-    config: Option<FarmConfig>,
     manager: Option<Pubkey>,
-    max_counts: Option<MaxCounts>,
+    crank_tip_bps: Option<u16>,
+    config_delay_sec: Option<u64>,
 ) -> Result<()> {
     let farm = &mut ctx.accounts.farm;
 
-    if let Some(config) = config {
-        farm.config = config;
+    // only proposes the new manager, who then has to accept_farm_manager
+    if let Some(manager) = manager {
+        let farm_info = farm.to_account_info();
+        let mut extension = Farm::extension(&farm_info)?;
+        extension.pending_farm_manager = manager;
+        Farm::set_extension(&farm_info, &extension)?;
     }
 
-    if let Some(manager) = manager {
-        farm.farm_manager = manager;
+    if let Some(crank_tip_bps) = crank_tip_bps {
+        farm.set_crank_tip_bps(crank_tip_bps)?;
     }
 
-    if let Some(max_counts) = max_counts {
-        farm.max_counts = max_counts;
+    // lowering it goes through propose_farm_config, or it could skip its own delay
+    if let Some(config_delay_sec) = config_delay_sec {
+        farm.increase_config_delay_sec(config_delay_sec)?;
     }
 
     msg!("updated farm");
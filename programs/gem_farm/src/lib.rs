@@ -3,6 +3,7 @@ use gem_bank::instructions::record_rarity_points::RarityConfig;
 use instructions::*;
 use state::*;
 
+pub mod events;
 pub mod instructions;
 pub mod number128;
 pub mod state;
@@ -39,11 +40,74 @@ pub mod gem_farm {
 
     pub fn update_farm(
         ctx: Context<UpdateFarm>,
-        config: Option<FarmConfig>,
         manager: Option<Pubkey>,
+        crank_tip_bps: Option<u16>,
+        config_delay_sec: Option<u64>,
+    ) -> Result<()> {
+        instructions::update_farm::handler(ctx, manager, crank_tip_bps, config_delay_sec)
+    }
+
+    pub fn propose_farm_config(
+        ctx: Context<ProposeFarmConfig>,
+        config: Option<FarmConfig>,
         max_counts: Option<MaxCounts>,
+        token_fees: Option<TokenFees>,
+        instant_unstake_fee_lamp_per_sec: Option<u64>,
+        config_delay_sec: Option<u64>,
     ) -> Result<()> {
-        instructions::update_farm::handler(ctx, config, manager, max_counts)
+        msg!("propose farm config");
+        instructions::propose_farm_config::handler(
+            ctx,
+            config,
+            max_counts,
+            token_fees,
+            instant_unstake_fee_lamp_per_sec,
+            config_delay_sec,
+        )
+    }
+
+    pub fn apply_farm_config(ctx: Context<ApplyFarmConfig>, _bump: u8) -> Result<()> {
+        msg!("apply farm config");
+        instructions::apply_farm_config::handler(ctx)
+    }
+
+    pub fn cancel_farm_config(ctx: Context<CancelFarmConfig>, _bump: u8) -> Result<()> {
+        msg!("cancel farm config");
+        instructions::cancel_farm_config::handler(ctx)
+    }
+
+    pub fn lock_config_delay(ctx: Context<LockConfigDelay>) -> Result<()> {
+        msg!("lock config delay");
+        instructions::lock_config_delay::handler(ctx)
+    }
+
+    pub fn accept_farm_manager(ctx: Context<AcceptFarmManager>) -> Result<()> {
+        msg!("accept farm manager");
+        instructions::accept_farm_manager::handler(ctx)
+    }
+
+    pub fn cancel_farm_manager_transfer(ctx: Context<CancelFarmManagerTransfer>) -> Result<()> {
+        msg!("cancel farm manager transfer");
+        instructions::cancel_farm_manager_transfer::handler(ctx)
+    }
+
+    pub fn set_farm_role(ctx: Context<SetFarmRole>, role: FarmRole, admin: Pubkey) -> Result<()> {
+        msg!("set farm role");
+        instructions::set_farm_role::handler(ctx, role, admin)
+    }
+
+    pub fn declare_emergency(ctx: Context<DeclareEmergency>) -> Result<()> {
+        msg!("declare emergency");
+        instructions::declare_emergency::handler(ctx)
+    }
+
+    pub fn close_farm<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, CloseFarm<'info>>,
+        _bump_auth: u8,
+        bump_treasury: u8,
+    ) -> Result<()> {
+        msg!("close farm");
+        instructions::close_farm::handler(ctx, bump_treasury)
     }
 
     pub fn payout_from_treasury(
@@ -56,6 +120,37 @@ pub mod gem_farm {
         instructions::treasury_payout::handler(ctx, bump_treasury, lamports)
     }
 
+    pub fn set_treasury_splits(
+        ctx: Context<SetTreasurySplits>,
+        splits: Vec<TreasurySplit>,
+    ) -> Result<()> {
+        msg!("set treasury splits");
+        instructions::set_treasury_splits::handler(ctx, splits)
+    }
+
+    pub fn distribute_treasury<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, DistributeTreasury<'info>>,
+        bump_treasury: u8,
+    ) -> Result<()> {
+        msg!("distribute treasury");
+        instructions::distribute_treasury::handler(ctx, bump_treasury)
+    }
+
+    pub fn init_token_treasury(ctx: Context<InitTokenTreasury>, _bump_auth: u8) -> Result<()> {
+        msg!("init token treasury");
+        instructions::init_token_treasury::handler(ctx)
+    }
+
+    pub fn payout_from_token_treasury(
+        ctx: Context<TokenTreasuryPayout>,
+        _bump_auth: u8,
+        _bump_treasury: u8,
+        amount: u64,
+    ) -> Result<()> {
+        msg!("token payout");
+        instructions::token_treasury_payout::handler(ctx, amount)
+    }
+
     pub fn add_to_bank_whitelist(
         ctx: Context<AddToBankWhitelist>,
         _bump_auth: u8,
@@ -81,13 +176,22 @@ pub mod gem_farm {
         instructions::init_farmer::handler(ctx)
     }
 
-    pub fn stake(ctx: Context<Stake>, _bump_auth: u8, _bump_farmer: u8) -> Result<()> {
+    pub fn close_farmer(ctx: Context<CloseFarmer>, _bump_farmer: u8) -> Result<()> {
+        msg!("close farmer");
+        instructions::close_farmer::handler(ctx)
+    }
+
+    pub fn stake<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, Stake<'info>>,
+        _bump_auth: u8,
+        _bump_farmer: u8,
+    ) -> Result<()> {
         msg!("stake");
         instructions::stake::handler(ctx)
     }
 
-    pub fn unstake(
-        ctx: Context<Unstake>,
+    pub fn unstake<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, Unstake<'info>>,
         _bump_auth: u8,
         _bump_treasury: u8,
         _bump_farmer: u8,
@@ -97,6 +201,16 @@ pub mod gem_farm {
         instructions::unstake::handler(ctx, skip_rewards)
     }
 
+    pub fn instant_unstake<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, InstantUnstake<'info>>,
+        _bump_auth: u8,
+        _bump_treasury: u8,
+        _bump_farmer: u8,
+    ) -> Result<()> {
+        msg!("instant unstake");
+        instructions::instant_unstake::handler(ctx)
+    }
+
     pub fn claim(
         ctx: Context<Claim>,
         _bump_auth: u8,
@@ -108,6 +222,54 @@ pub mod gem_farm {
         instructions::claim::handler(ctx)
     }
 
+    pub fn claim_delegated(
+        ctx: Context<ClaimDelegated>,
+        _bump_auth: u8,
+        _bump_farmer: u8,
+        _bump_pot_a: u8,
+        _bump_pot_b: u8,
+    ) -> Result<()> {
+        msg!("claim delegated");
+        instructions::claim_delegated::handler(ctx)
+    }
+
+    pub fn update_claim_delegate(
+        ctx: Context<UpdateClaimDelegate>,
+        _bump_farmer: u8,
+        claim_delegate: Pubkey,
+        claim_destination_a: Pubkey,
+        claim_destination_b: Pubkey,
+    ) -> Result<()> {
+        msg!("update claim delegate");
+        instructions::update_claim_delegate::handler(
+            ctx,
+            claim_delegate,
+            claim_destination_a,
+            claim_destination_b,
+        )
+    }
+
+    pub fn crank_claim(
+        ctx: Context<CrankClaim>,
+        _bump_auth: u8,
+        _bump_farmer: u8,
+        _bump_pot_a: u8,
+        _bump_pot_b: u8,
+    ) -> Result<()> {
+        msg!("crank claim");
+        instructions::crank_claim::handler(ctx)
+    }
+
+    pub fn enable_auto_claim(ctx: Context<EnableAutoClaim>, _bump_farmer: u8) -> Result<()> {
+        msg!("enable auto claim");
+        instructions::enable_auto_claim::handler(ctx)
+    }
+
+    pub fn disable_auto_claim(ctx: Context<DisableAutoClaim>, _bump_farmer: u8) -> Result<()> {
+        msg!("disable auto claim");
+        instructions::disable_auto_claim::handler(ctx)
+    }
+
     pub fn flash_deposit<'a, 'b, 'c, 'info>(
         ctx: Context<'a, 'b, 'c, 'info, FlashDeposit<'info>>,
         _bump_farmer: u8,
@@ -119,6 +281,28 @@ pub mod gem_farm {
         instructions::flash_deposit::handler(ctx, bump_vault_auth, bump_rarity, amount)
     }
 
+    /// takes gems out of the stake, they stay in the vault for the farm's cooldown
+    pub fn begin_flash_withdraw<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, BeginFlashWithdraw<'info>>,
+        _bump_treasury: u8,
+        _bump_farmer: u8,
+        _bump_rarity: u8,
+        amount: u64,
+    ) -> Result<()> {
+        // msg!("begin flash withdraw"); //same compute budget concerns as for flash deposits
+        instructions::begin_flash_withdraw::handler(ctx, amount)
+    }
+
+    /// hands over what begin_flash_withdraw set aside, once the cooldown has passed
+    pub fn flash_withdraw(
+        ctx: Context<FlashWithdraw>,
+        _bump_farmer: u8,
+        bumps: FlashWithdrawBumps,
+    ) -> Result<()> {
+        // msg!("flash withdraw"); //same compute budget concerns as for flash deposits
+        instructions::flash_withdraw::handler(ctx, bumps)
+    }
+
     pub fn refresh_farmer(ctx: Context<RefreshFarmer>, _bump: u8) -> Result<()> {
         msg!("refresh farmer");
         instructions::refresh_farmer::handler(ctx)
@@ -136,11 +320,35 @@ pub mod gem_farm {
         instructions::refresh_farmer_signed::handler(ctx, reenroll)
     }
 
+    /// can be called either by the farmer or by the manager of the farm being left
+    pub fn migrate_farmer<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, MigrateFarmer<'info>>,
+        _bump_from_auth: u8,
+        _bump_to_auth: u8,
+        _bump_from_farmer: u8,
+        _bump_proof: u8,
+    ) -> Result<()> {
+        msg!("migrate farmer");
+        instructions::migrate_farmer::handler(ctx)
+    }
+
+    // --------------------------------------- migration ops
+
+    pub fn authorize_migration(ctx: Context<AuthorizeMigration>) -> Result<()> {
+        msg!("authorize migration");
+        instructions::authorize_migration::handler(ctx)
+    }
+
+    pub fn deauthorize_migration(ctx: Context<DeauthorizeMigration>, _bump: u8) -> Result<()> {
+        msg!("deauthorize migration");
+        instructions::deauthorize_migration::handler(ctx)
+    }
+
     // --------------------------------------- funder ops
 
-    pub fn authorize_funder(ctx: Context<AuthorizeFunder>) -> Result<()> {
+    pub fn authorize_funder(ctx: Context<AuthorizeFunder>, limits: FundingLimits) -> Result<()> {
         msg!("authorize funder");
-        instructions::authorize_funder::handler(ctx)
+        instructions::authorize_funder::handler(ctx, limits)
     }
 
     pub fn deauthorize_funder(ctx: Context<DeauthorizeFunder>, _bump: u8) -> Result<()> {
@@ -171,6 +379,38 @@ pub mod gem_farm {
         instructions::lock_reward::handler(ctx)
     }
 
+    pub fn sweep_dust(ctx: Context<SweepDust>, _bump_auth: u8, _bump_pot: u8) -> Result<()> {
+        msg!("sweep dust");
+        instructions::sweep_dust::handler(ctx)
+    }
+
+    pub fn rescue_surplus(
+        ctx: Context<RescueSurplus>,
+        _bump_auth: u8,
+        _bump_pot: u8,
+    ) -> Result<()> {
+        msg!("rescue surplus");
+        instructions::rescue_surplus::handler(ctx)
+    }
+
+    pub fn roll_surplus(ctx: Context<RollSurplus>, _bump_pot: u8) -> Result<()> {
+        msg!("roll surplus");
+        instructions::roll_surplus::handler(ctx)
+    }
+
+    pub fn set_funding_paused(ctx: Context<SetFundingPaused>, paused: bool) -> Result<()> {
+        msg!("set funding paused");
+        instructions::set_funding_paused::handler(ctx, paused)
+    }
+
+    pub fn audit_farm<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, AuditFarm<'info>>,
+        pause_on_failure: bool,
+    ) -> Result<()> {
+        msg!("audit farm");
+        instructions::audit_farm::handler(ctx, pause_on_failure)
+    }
+
     // --------------------------------------- rarities
 
     pub fn add_rarities_to_bank<'a, 'b, 'c, 'info>(
@@ -199,4 +439,71 @@ pub mod gem_farm {
             rules_acc_present,
         )
     }
+
+    pub fn flash_withdraw_pnft<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, FlashWithdrawPnft<'info>>,
+        _bump_farmer: u8,
+        bumps: FlashWithdrawBumps,
+        rules_acc_present: bool,
+    ) -> Result<()> {
+        // msg!("flash withdraw"); //same compute budget concerns as for flash deposits
+        instructions::flash_withdraw_pnft::handler(ctx, bumps, rules_acc_present)
+    }
+
+    // --------------------------------------- protocol ops
+
+    pub fn init_protocol_config(
+        ctx: Context<InitProtocolConfig>,
+        fee_wallet: Pubkey,
+        fees: FeeTable,
+    ) -> Result<()> {
+        msg!("init protocol config");
+        instructions::init_protocol_config::handler(ctx, fee_wallet, fees)
+    }
+
+    pub fn update_protocol_config(
+        ctx: Context<UpdateProtocolConfig>,
+        admin: Option<Pubkey>,
+        fee_wallet: Option<Pubkey>,
+        fees: Option<FeeTable>,
+    ) -> Result<()> {
+        msg!("update protocol config");
+        instructions::update_protocol_config::handler(ctx, admin, fee_wallet, fees)
+    }
+
+    pub fn add_fee_exemption(ctx: Context<AddFeeExemption>) -> Result<()> {
+        msg!("add fee exemption");
+        instructions::add_fee_exemption::handler(ctx)
+    }
+
+    pub fn remove_fee_exemption(ctx: Context<RemoveFeeExemption>, _bump: u8) -> Result<()> {
+        msg!("remove fee exemption");
+        instructions::remove_fee_exemption::handler(ctx)
+    }
+
+    // --------------------------------------- quotes (simulate only)
+
+    pub fn quote_farmer_rewards(ctx: Context<QuoteFarmerRewards>) -> Result<FarmerRewardsQuote> {
+        instructions::quote_farmer_rewards::handler(ctx)
+    }
+
+    pub fn quote_fund_reward(
+        ctx: Context<QuoteFundReward>,
+        variable_rate_config: Option<VariableRateConfig>,
+        fixed_rate_config: Option<FixedRateConfig>,
+    ) -> Result<FundRewardQuote> {
+        instructions::quote_fund_reward::handler(ctx, variable_rate_config, fixed_rate_config)
+    }
+
+    // --------------------------------------- versioning
+
+    pub fn migrate_farm_version(ctx: Context<MigrateFarmVersion>) -> Result<()> {
+        msg!("migrate farm version");
+        instructions::migrate_farm_version::handler(ctx)
+    }
+
+    pub fn migrate_farmer_version(ctx: Context<MigrateFarmerVersion>) -> Result<()> {
+        msg!("migrate farmer version");
+        instructions::migrate_farmer_version::handler(ctx)
+    }
 }
@@ -1,4 +1,32 @@
 use anchor_lang::prelude::*;
+use gem_common::{errors::ErrorCode, *};
+
+use crate::state::RewardType;
+
+bitflags::bitflags! {
+    /// restrictions on what the funder can do - none set = unlimited, permanent funding rights
+    pub struct FunderFlags: u8 {
+        const CAPPED_A = 1 << 0;
+        const CAPPED_B = 1 << 1;
+        const NO_VARIABLE_RATE = 1 << 2;
+        const NO_FIXED_RATE = 1 << 3;
+    }
+}
+
+/// passed by the farm manager when (re-)authorizing a funder
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
+pub struct FundingLimits {
+    /// 0 = never expires
+    pub expiry_ts: u64,
+
+    /// cumulative amount the funder can put into each reward, None = uncapped
+    pub cap_a: Option<u64>,
+
+    pub cap_b: Option<u64>,
+
+    /// None = both
+    pub reward_type: Option<RewardType>,
+}
 
 /// if this PDA exists, this means the funder recorded below has been authorized by the
 /// farm recorded below to fund rewards
@@ -10,6 +38,133 @@ pub struct AuthorizationProof {
 
     pub farm: Pubkey,
 
+    /// 0 = never expires
+    pub expiry_ts: u64,
+
+    /// how much the funder can still put into each reward, only enforced if capped (see flags)
+    pub remaining_a: u64,
+
+    pub remaining_b: u64,
+
+    pub flags: u8,
+
     /// reserved for future updates, has to be /8
-    _reserved: [u8; 32],
+    _reserved: [u8; 7],
+}
+
+impl AuthorizationProof {
+    pub fn read_flags(&self) -> FunderFlags {
+        FunderFlags::from_bits_truncate(self.flags)
+    }
+
+    /// replaces any previous limits, including what's left of previous caps
+    pub fn set_limits(&mut self, limits: FundingLimits) {
+        let mut flags = FunderFlags::empty();
+
+        flags.set(FunderFlags::CAPPED_A, limits.cap_a.is_some());
+        flags.set(FunderFlags::CAPPED_B, limits.cap_b.is_some());
+        match limits.reward_type {
+            Some(RewardType::Variable) => flags.insert(FunderFlags::NO_FIXED_RATE),
+            Some(RewardType::Fixed) => flags.insert(FunderFlags::NO_VARIABLE_RATE),
+            None => {}
+        }
+
+        self.expiry_ts = limits.expiry_ts;
+        self.remaining_a = limits.cap_a.unwrap_or(0);
+        self.remaining_b = limits.cap_b.unwrap_or(0);
+        self.flags = flags.bits();
+    }
+
+    /// checks the funding against the limits and records it against the cap, if any
+    pub fn use_allowance(
+        &mut self,
+        now_ts: u64,
+        is_reward_a: bool,
+        reward_type: RewardType,
+        amount: u64,
+    ) -> Result<()> {
+        let flags = self.read_flags();
+
+        if self.expiry_ts > 0 && now_ts >= self.expiry_ts {
+            return Err(error!(ErrorCode::FundingNotAllowed));
+        }
+
+        let type_forbidden = match reward_type {
+            RewardType::Variable => flags.contains(FunderFlags::NO_VARIABLE_RATE),
+            RewardType::Fixed => flags.contains(FunderFlags::NO_FIXED_RATE),
+        };
+        if type_forbidden {
+            return Err(error!(ErrorCode::FundingNotAllowed));
+        }
+
+        let (capped, remaining) = if is_reward_a {
+            (flags.contains(FunderFlags::CAPPED_A), &mut self.remaining_a)
+        } else {
+            (flags.contains(FunderFlags::CAPPED_B), &mut self.remaining_b)
+        };
+        if capped {
+            *remaining = remaining
+                .try_sub(amount)
+                .map_err(|_| error!(ErrorCode::FundingAllowanceExceeded))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn proof(limits: FundingLimits) -> AuthorizationProof {
+        let mut proof = AuthorizationProof {
+            authorized_funder: Pubkey::default(),
+            farm: Pubkey::default(),
+            expiry_ts: 0,
+            remaining_a: 0,
+            remaining_b: 0,
+            flags: 0,
+            _reserved: [0; 7],
+        };
+        proof.set_limits(limits);
+        proof
+    }
+
+    #[test]
+    fn test_use_allowance_unlimited() {
+        let mut p = proof(FundingLimits::default());
+
+        p.use_allowance(100, true, RewardType::Fixed, u64::MAX)
+            .unwrap();
+        p.use_allowance(100, false, RewardType::Variable, u64::MAX)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_use_allowance_limits() {
+        let mut p = proof(FundingLimits {
+            expiry_ts: 100,
+            cap_a: Some(1000),
+            cap_b: None,
+            reward_type: Some(RewardType::Variable),
+        });
+
+        // cap
+        p.use_allowance(50, true, RewardType::Variable, 600)
+            .unwrap();
+        assert_eq!(p.remaining_a, 400);
+        assert!(p
+            .use_allowance(50, true, RewardType::Variable, 401)
+            .is_err());
+        p.use_allowance(50, false, RewardType::Variable, 5000)
+            .unwrap();
+
+        // type
+        assert!(p.use_allowance(50, false, RewardType::Fixed, 1).is_err());
+
+        // expiry
+        assert!(p
+            .use_allowance(100, false, RewardType::Variable, 1)
+            .is_err());
+    }
 }
@@ -3,7 +3,15 @@ use gem_common::{errors::ErrorCode, *};
 
 use crate::state::*;
 
-pub const LATEST_FARM_VERSION: u16 = 0;
+pub const LATEST_FARM_VERSION: u16 = 1;
+
+/// room for growth allocated after the Farm struct since v1, see gem_common::load_extension
+pub const FARM_EXTENSION_SPACE: usize = 512;
+
+/// max share of a cranked claim that can go to the cranker, 5%
+pub const MAX_CRANK_TIP_BPS: u16 = 500;
+
+pub const MAX_TREASURY_SPLITS: usize = 5;
 
 #[proc_macros::assert_size(24)]
 #[repr(C)]
@@ -18,6 +26,38 @@ pub struct FarmConfig {
     pub unstaking_fee_lamp: u64,
 }
 
+impl FarmConfig {
+    pub fn assert_valid(&self) -> Result<()> {
+        //ensure unstaking fee does not violate solana v1.9.5 rent requirements
+        if self.unstaking_fee_lamp > 0 && self.unstaking_fee_lamp < 890880 {
+            return Err(error!(ErrorCode::InvalidUnstakingFee));
+        }
+        Ok(())
+    }
+}
+
+/// optional fees in an arbitrary SPL token, paid into the farm's token treasury for that mint
+#[proc_macros::assert_size(48)]
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default, AnchorSerialize, AnchorDeserialize, PartialEq)]
+pub struct TokenFees {
+    /// default Pubkey = no token fees
+    pub fee_mint: Pubkey,
+
+    pub stake_fee: u64,
+
+    pub unstake_fee: u64,
+}
+
+impl TokenFees {
+    pub fn assert_valid(&self) -> Result<()> {
+        if self.fee_mint == Pubkey::default() && (self.stake_fee > 0 || self.unstake_fee > 0) {
+            return Err(error!(ErrorCode::InvalidParameter));
+        }
+        Ok(())
+    }
+}
+
 /// refers to staked counts
 #[proc_macros::assert_size(12)]
 #[repr(C)]
@@ -30,6 +70,100 @@ pub struct MaxCounts {
     pub max_rarity_points: u32,
 }
 
+/// parts of the farm manager's job that can be handed to a separate key
+#[derive(Debug, Copy, Clone, AnchorSerialize, AnchorDeserialize, PartialEq)]
+pub enum FarmRole {
+    /// adds / removes bank whitelist entries
+    WhitelistAdmin,
+    /// records gem rarities
+    RarityAdmin,
+    /// cancels / locks rewards
+    RewardAdmin,
+    /// pays out of the farm treasury
+    TreasuryAdmin,
+}
+
+/// share of the treasury paid to a recipient by distribute_treasury
+#[derive(Debug, Copy, Clone, Default, AnchorSerialize, AnchorDeserialize, PartialEq)]
+pub struct TreasurySplit {
+    pub recipient: Pubkey,
+
+    pub bps: u16,
+}
+
+impl TreasurySplit {
+    /// rounded down, any dust stays in the treasury
+    pub fn share_of(&self, amount: u64) -> Result<u64> {
+        (amount as u128)
+            .try_mul(self.bps as u128)?
+            .try_div(10_000)?
+            .try_cast()
+    }
+}
+
+/// stored after the Farm struct, see gem_common::load_extension. Anything not yet used of
+/// FARM_EXTENSION_SPACE is zeroed, so new fields can simply be appended
+#[derive(Debug, Copy, Clone, Default, AnchorSerialize, AnchorDeserialize)]
+pub struct FarmExtension {
+    /// proposed by the current manager, takes over once it calls accept_farm_manager
+    pub pending_farm_manager: Pubkey,
+
+    // ----------------- roles, default Pubkey = unassigned (the farm manager can always act)
+    pub whitelist_admin: Pubkey,
+
+    pub rarity_admin: Pubkey,
+
+    pub reward_admin: Pubkey,
+
+    pub treasury_admin: Pubkey,
+
+    pub token_fees: TokenFees,
+
+    /// unused slots have a default recipient
+    pub treasury_splits: [TreasurySplit; MAX_TREASURY_SPLITS],
+}
+
+impl FarmExtension {
+    pub fn role_admin(&mut self, role: FarmRole) -> &mut Pubkey {
+        match role {
+            FarmRole::WhitelistAdmin => &mut self.whitelist_admin,
+            FarmRole::RarityAdmin => &mut self.rarity_admin,
+            FarmRole::RewardAdmin => &mut self.reward_admin,
+            FarmRole::TreasuryAdmin => &mut self.treasury_admin,
+        }
+    }
+
+    /// pass an empty list to remove all splits
+    pub fn set_treasury_splits(&mut self, splits: &[TreasurySplit]) -> Result<()> {
+        if !splits.is_empty() {
+            let mut total_bps: u64 = 0;
+            for (i, split) in splits.iter().enumerate() {
+                let is_duplicate = splits[..i].iter().any(|s| s.recipient == split.recipient);
+                if split.recipient == Pubkey::default() || split.bps == 0 || is_duplicate {
+                    return Err(error!(ErrorCode::InvalidTreasurySplits));
+                }
+                total_bps.try_add_assign(split.bps as u64)?;
+            }
+            require!(
+                splits.len() <= MAX_TREASURY_SPLITS && total_bps == 10_000,
+                ErrorCode::InvalidTreasurySplits
+            );
+        }
+
+        self.treasury_splits = [TreasurySplit::default(); MAX_TREASURY_SPLITS];
+        self.treasury_splits[..splits.len()].copy_from_slice(splits);
+        Ok(())
+    }
+
+    pub fn active_treasury_splits(&self) -> Vec<TreasurySplit> {
+        self.treasury_splits
+            .iter()
+            .filter(|s| s.recipient != Pubkey::default())
+            .copied()
+            .collect()
+    }
+}
+
 #[proc_macros::assert_size(1000)] // + 5 to make it /8
 #[repr(C)]
 #[account]
@@ -38,7 +172,7 @@ pub struct Farm {
     pub version: u16,
 
     /// authorizes funders, whitelists mints/creators, sets farm config params
-    /// can update itself to another Pubkey
+    /// can hand itself over to another Pubkey, which then has to accept
     pub farm_manager: Pubkey,
 
     /// used for collecting any fees earned by the farm
@@ -81,13 +215,63 @@ pub struct Farm {
     // ----------------- extra
     pub max_counts: MaxCounts,
 
+    /// set when the farm manager declares an emergency, 0 otherwise. Can't be unset:
+    /// rewards stop accruing at this ts and farmers can exit without fees or cooldown
+    pub emergency_ts: u64,
+
+    /// price of skipping what's left of min staking + cooldown via instant_unstake, per second.
+    /// 0 = instant unstaking is disabled
+    pub instant_unstake_fee_lamp_per_sec: u64,
+
+    /// min time between propose_farm_config and apply_farm_config
+    pub config_delay_sec: u64,
+
+    /// migration proofs from this farm that are still open, have to be closed with the farm
+    pub migration_proof_count: u32,
+
+    /// token treasuries opened for this farm, have to be emptied and closed with the farm
+    pub token_treasury_count: u32,
+
+    /// reserved for future updates
+    _reserved: [u8; 8],
+
+    /// share of each crank_claim paid to whoever cranked it, in bps
+    pub crank_tip_bps: u16,
+
+    /// (!) once set can't be unset - from then on config_delay_sec can only be increased
+    pub config_delay_locked: bool,
+
+    /// set by a failed audit_farm (or the reward admin), blocks any new funding until cleared
+    pub funding_paused: bool,
+
     /// reserved for future updates, has to be /8
-    _reserved: [u8; 32],
-    _reserved2: [u8; 16],
-    _reserved3: [u8; 4],
+    _reserved2: [u8; 4],
 }
 
 impl Farm {
+    pub const EXTENSION_OFFSET: usize = 8 + std::mem::size_of::<Farm>();
+
+    pub const SPACE: usize = Self::EXTENSION_OFFSET + FARM_EXTENSION_SPACE;
+
+    pub fn extension(farm: &AccountInfo) -> Result<FarmExtension> {
+        load_extension(farm, Self::EXTENSION_OFFSET)?
+            .ok_or_else(|| error!(ErrorCode::OutdatedAccountVersion))
+    }
+
+    pub fn set_extension(farm: &AccountInfo, extension: &FarmExtension) -> Result<()> {
+        store_extension(farm, Self::EXTENSION_OFFSET, extension)
+    }
+
+    /// the farm manager holds every role, admins only the one assigned to them
+    pub fn has_role(&self, farm: &AccountInfo, role: FarmRole, signer: Pubkey) -> Result<bool> {
+        if signer == self.farm_manager {
+            return Ok(true);
+        }
+
+        let admin = *Self::extension(farm)?.role_admin(role);
+        Ok(admin != Pubkey::default() && admin == signer)
+    }
+
     fn assert_valid_max_counts(&self) -> Result<()> {
         self.assert_not_too_many_farmers()?;
         self.assert_not_too_many_gems()?;
@@ -125,6 +309,75 @@ impl Farm {
         Ok(())
     }
 
+    pub fn is_in_emergency(&self) -> bool {
+        self.emergency_ts > 0
+    }
+
+    fn assert_not_in_emergency(&self) -> Result<()> {
+        require!(!self.is_in_emergency(), ErrorCode::FarmInEmergency);
+        Ok(())
+    }
+
+    /// once in emergency, nothing accrues past the moment the emergency was declared
+    fn reward_cutoff_ts(&self, now_ts: u64) -> u64 {
+        if self.is_in_emergency() {
+            return std::cmp::min(now_ts, self.emergency_ts);
+        }
+        now_ts
+    }
+
+    /// (!) THIS OPERATION IS IRREVERSIBLE
+    /// settles farm-level accruals up to now and ends both rewards, so that they can later be
+    /// cancelled and refunded. Farmers are settled individually as they unstake
+    pub fn declare_emergency(&mut self, now_ts: u64) -> Result<()> {
+        self.assert_not_in_emergency()?;
+
+        self.update_rewards(now_ts, None, false)?;
+        self.reward_a.times.end_reward(now_ts)?;
+        self.reward_b.times.end_reward(now_ts)?;
+
+        self.emergency_ts = now_ts;
+
+        Ok(())
+    }
+
+    pub fn set_crank_tip_bps(&mut self, crank_tip_bps: u16) -> Result<()> {
+        require!(
+            crank_tip_bps <= MAX_CRANK_TIP_BPS,
+            ErrorCode::InvalidCrankTip
+        );
+        self.crank_tip_bps = crank_tip_bps;
+        Ok(())
+    }
+
+    /// takes effect right away, so it can only make farmers wait longer
+    pub fn increase_config_delay_sec(&mut self, config_delay_sec: u64) -> Result<()> {
+        require!(
+            config_delay_sec >= self.config_delay_sec,
+            ErrorCode::ConfigDelayDecrease
+        );
+        self.config_delay_sec = config_delay_sec;
+        Ok(())
+    }
+
+    /// through apply_farm_config - once locked it can't go down anymore
+    pub fn set_config_delay_sec(&mut self, config_delay_sec: u64) -> Result<()> {
+        require!(
+            !self.config_delay_locked || config_delay_sec >= self.config_delay_sec,
+            ErrorCode::ConfigDelayLocked
+        );
+        self.config_delay_sec = config_delay_sec;
+        Ok(())
+    }
+
+    /// the cranker's cut of a claim, rounded down in favor of the farmer
+    pub fn crank_tip(&self, claimed: u64) -> Result<u64> {
+        (claimed as u128)
+            .try_mul(self.crank_tip_bps as u128)?
+            .try_div(10_000)?
+            .try_cast()
+    }
+
     pub fn farm_seeds(&self) -> [&[u8]; 2] {
         [
             self.farm_authority_seed.as_ref(),
@@ -143,6 +396,34 @@ impl Farm {
         }
     }
 
+    /// records tokens already sitting in the pot as funding, see FarmReward::surplus
+    pub fn fund_surplus_by_mint(
+        &mut self,
+        now_ts: u64,
+        reward_mint: Pubkey,
+        surplus: u64,
+    ) -> Result<()> {
+        self.assert_not_in_emergency()?;
+        require!(!self.funding_paused, ErrorCode::FundingPaused);
+
+        let reward = self.match_reward_by_mint(reward_mint)?;
+        reward.fund_surplus(now_ts, surplus)
+    }
+
+    /// only once the reward has ended and everyone has unstaked - by then every farmer has been
+    /// settled for good, so whatever the farm accrued but never credited to anyone is dust
+    pub fn sweep_dust_by_mint(&mut self, now_ts: u64, reward_mint: Pubkey) -> Result<u64> {
+        let everyone_unstaked = self.staked_farmer_count == 0;
+
+        let reward = self.match_reward_by_mint(reward_mint)?;
+        require!(
+            everyone_unstaked && now_ts >= reward.times.reward_end_ts,
+            ErrorCode::RewardNotSettled
+        );
+
+        reward.sweep_dust()
+    }
+
     pub fn lock_reward_by_mint(&mut self, reward_mint: Pubkey) -> Result<()> {
         let reward = self.match_reward_by_mint(reward_mint)?;
         reward.lock_reward()
@@ -155,6 +436,9 @@ impl Farm {
         variable_rate_config: Option<VariableRateConfig>,
         fixed_rate_config: Option<FixedRateConfig>,
     ) -> Result<()> {
+        self.assert_not_in_emergency()?;
+        require!(!self.funding_paused, ErrorCode::FundingPaused);
+
         let reward = self.match_reward_by_mint(reward_mint)?;
         reward.fund_reward_by_type(now_ts, variable_rate_config, fixed_rate_config)
     }
@@ -170,6 +454,8 @@ impl Farm {
         mut farmer: Option<&mut Account<Farmer>>,
         reenroll: bool, //relevant for fixed only
     ) -> Result<()> {
+        let now_ts = self.reward_cutoff_ts(now_ts);
+
         // reward a
         let (farmer_points_staked, farmer_reward_a) = match farmer {
             Some(ref mut farmer) => (
@@ -179,7 +465,7 @@ impl Farm {
             None => (None, None),
         };
 
-        self.reward_a.update_accrued_reward_by_type(
+        self.reward_a.update_accrued_reward(
             now_ts,
             self.rarity_points_staked,
             farmer_points_staked,
@@ -193,7 +479,7 @@ impl Farm {
             None => None,
         };
 
-        self.reward_b.update_accrued_reward_by_type(
+        self.reward_b.update_accrued_reward(
             now_ts,
             self.rarity_points_staked,
             farmer_points_staked,
@@ -209,6 +495,8 @@ impl Farm {
         rarity_points_in_vault: u64,
         farmer: &mut Account<Farmer>,
     ) -> Result<()> {
+        self.assert_not_in_emergency()?;
+
         // update farmer
         farmer.begin_staking(
             self.config.min_staking_period_sec,
@@ -255,34 +543,99 @@ impl Farm {
         match farmer.state {
             FarmerState::Unstaked => Ok(msg!("already unstaked!")),
             FarmerState::Staked => {
-                // fixed-rate only - we need to do some extra book-keeping
-                // (!) MUST COME BEFORE FARMER IS UPDATED - WE NEED CURRENT RARITY POINTS AMOUNT
-                if self.reward_a.reward_type == RewardType::Fixed {
-                    self.reward_a
-                        .fixed_rate
-                        .graduate_farmer(farmer.rarity_points_staked, &mut farmer.reward_a)?;
-                }
+                // in an emergency there's no min staking period and no cooldown - straight out
+                let skip_cooldown = self.is_in_emergency();
+                self.remove_staked_farmer(now_ts, farmer, skip_cooldown)
+            }
+            FarmerState::PendingCooldown if self.is_in_emergency() => {
+                farmer.end_staking_immediately();
+                Ok(())
+            }
+            FarmerState::PendingCooldown => farmer.end_cooldown(now_ts),
+        }
+    }
 
-                if self.reward_b.reward_type == RewardType::Fixed {
-                    self.reward_b
-                        .fixed_rate
-                        .graduate_farmer(farmer.rarity_points_staked, &mut farmer.reward_b)?;
-                }
+    /// skips whatever is left of the min staking period and the cooldown in one go,
+    /// returns the fee the farmer owes the treasury for doing so
+    pub fn end_staking_instantly(
+        &mut self,
+        now_ts: u64,
+        farmer: &mut Account<Farmer>,
+    ) -> Result<u64> {
+        require!(
+            self.instant_unstake_fee_lamp_per_sec > 0,
+            ErrorCode::InstantUnstakeDisabled
+        );
+
+        // in an emergency everyone exits for free anyway
+        let fee = if self.is_in_emergency() {
+            0
+        } else {
+            farmer
+                .remaining_lockup_sec(now_ts, self.config.cooldown_period_sec)?
+                .try_mul(self.instant_unstake_fee_lamp_per_sec)?
+        };
 
-                // update farmer
-                let (gems_unstaked, rarity_points_unstaked) =
-                    farmer.end_staking_begin_cooldown(now_ts, self.config.cooldown_period_sec)?;
+        match farmer.state {
+            FarmerState::Unstaked => return Err(error!(ErrorCode::FarmerNotStaked)),
+            FarmerState::Staked => self.remove_staked_farmer(now_ts, farmer, true)?,
+            FarmerState::PendingCooldown => {
+                farmer.end_staking_immediately();
+            }
+        }
 
-                // update farm
-                self.staked_farmer_count.try_sub_assign(1)?;
-                self.rarity_points_staked
-                    .try_sub_assign(rarity_points_unstaked)?;
-                self.gems_staked.try_sub_assign(gems_unstaked)?;
+        Ok(fee)
+    }
 
-                Ok(())
-            }
-            FarmerState::PendingCooldown => farmer.end_cooldown(now_ts),
+    /// takes a staked farmer out of the farm without a cooldown, so that their (still locked)
+    /// gems can be staked on another farm straight away
+    pub fn migrate_staked_farmer(
+        &mut self,
+        now_ts: u64,
+        farmer: &mut Account<Farmer>,
+    ) -> Result<()> {
+        require!(
+            farmer.state == FarmerState::Staked,
+            ErrorCode::FarmerNotStaked
+        );
+
+        self.remove_staked_farmer(now_ts, farmer, true)
+    }
+
+    fn remove_staked_farmer(
+        &mut self,
+        now_ts: u64,
+        farmer: &mut Account<Farmer>,
+        skip_cooldown: bool,
+    ) -> Result<()> {
+        // fixed-rate only - we need to do some extra book-keeping
+        // (!) MUST COME BEFORE FARMER IS UPDATED - WE NEED CURRENT RARITY POINTS AMOUNT
+        if self.reward_a.reward_type == RewardType::Fixed {
+            self.reward_a
+                .fixed_rate
+                .graduate_farmer(farmer.rarity_points_staked, &mut farmer.reward_a)?;
         }
+
+        if self.reward_b.reward_type == RewardType::Fixed {
+            self.reward_b
+                .fixed_rate
+                .graduate_farmer(farmer.rarity_points_staked, &mut farmer.reward_b)?;
+        }
+
+        // update farmer
+        let (gems_unstaked, rarity_points_unstaked) = if skip_cooldown {
+            farmer.end_staking_immediately()
+        } else {
+            farmer.end_staking_begin_cooldown(now_ts, self.config.cooldown_period_sec)?
+        };
+
+        // update farm
+        self.staked_farmer_count.try_sub_assign(1)?;
+        self.rarity_points_staked
+            .try_sub_assign(rarity_points_unstaked)?;
+        self.gems_staked.try_sub_assign(gems_unstaked)?;
+
+        Ok(())
     }
 
     pub fn stake_extra_gems(
@@ -294,6 +647,8 @@ impl Farm {
         extra_rarity_points: u64,
         farmer: &mut Account<Farmer>,
     ) -> Result<()> {
+        self.assert_not_in_emergency()?;
+
         // update farmer
         let (_previous_gems, previous_rarity_points) = farmer.begin_staking(
             self.config.min_staking_period_sec,
@@ -309,6 +664,54 @@ impl Farm {
 
         self.assert_valid_max_counts()?;
 
+        self.reenroll_fixed_rate_farmer(now_ts, previous_rarity_points, farmer)
+    }
+
+    /// the opposite of stake_extra_gems - farmer stays staked with whatever is left in the vault
+    /// once the withdrawn gems are set aside (see PendingWithdrawal)
+    pub fn unstake_some_gems(
+        &mut self,
+        now_ts: u64,
+        gems_left_staked: u64,
+        rarity_points_left_staked: u64,
+        withdrawn_gems: u64,
+        withdrawn_rarity_points: u64,
+        farmer: &mut Account<Farmer>,
+    ) -> Result<()> {
+        self.assert_not_in_emergency()?;
+
+        require!(
+            farmer.state == FarmerState::Staked,
+            ErrorCode::FarmerNotStaked
+        );
+
+        // withdrawing everything is a regular unstake
+        if gems_left_staked == 0 {
+            return Err(error!(ErrorCode::InvalidParameter));
+        }
+
+        // update farmer
+        let (_previous_gems, previous_rarity_points) = farmer.end_staking_partially(
+            now_ts,
+            self.config.cooldown_period_sec,
+            gems_left_staked,
+            rarity_points_left_staked,
+        )?;
+
+        // update farm
+        self.gems_staked.try_sub_assign(withdrawn_gems)?;
+        self.rarity_points_staked
+            .try_sub_assign(withdrawn_rarity_points)?;
+
+        self.reenroll_fixed_rate_farmer(now_ts, previous_rarity_points, farmer)
+    }
+
+    fn reenroll_fixed_rate_farmer(
+        &mut self,
+        now_ts: u64,
+        previous_rarity_points: u64,
+        farmer: &mut Account<Farmer>,
+    ) -> Result<()> {
         // fixed-rate only - we need to do some extra book-keeping
         if self.reward_a.reward_type == RewardType::Fixed {
             // graduate with PREVIOUS rarity points count
@@ -363,7 +766,7 @@ pub enum RewardType {
 /// these numbers should only ever go up - ie they are cummulative
 #[proc_macros::assert_size(24)]
 #[repr(C)]
-#[derive(Debug, Copy, Clone, AnchorSerialize, AnchorDeserialize)]
+#[derive(Debug, Copy, Clone, Default, AnchorSerialize, AnchorDeserialize)]
 pub struct FundsTracker {
     pub total_funded: u64,
 
@@ -382,7 +785,7 @@ impl FundsTracker {
 
 #[proc_macros::assert_size(24)]
 #[repr(C)]
-#[derive(Debug, Copy, Clone, AnchorSerialize, AnchorDeserialize)]
+#[derive(Debug, Copy, Clone, Default, AnchorSerialize, AnchorDeserialize)]
 pub struct TimeTracker {
     /// total duration for which the reward has been funded
     /// updated with each new funding round
@@ -457,11 +860,71 @@ pub struct FarmReward {
 
     pub times: TimeTracker,
 
+    /// what farmers have actually claimed out of the pot
+    pub total_claimed: u64,
+
+    /// exactly what's been credited to farmers' accrued_reward and not claimed yet.
+    /// Only exact if liability_tracked - legacy farms start at 0, so it undercounts until
+    /// their earlier accruals have been claimed
+    pub outstanding_liability: u64,
+
+    /// set at farm init (or migration, if nothing had accrued yet), false for legacy farms
+    /// whose farmers were credited before outstanding_liability was tracked
+    pub liability_tracked: bool,
+
     /// reserved for future updates, has to be /8
-    _reserved: [u8; 32],
+    _reserved: [u8; 15],
 }
 
 impl FarmReward {
+    /// pays out whatever the farmer is owed, capped at what's in the pot
+    pub fn claim_reward(
+        &mut self,
+        farmer_reward: &mut FarmerReward,
+        pot_balance: u64,
+    ) -> Result<u64> {
+        let to_claim = farmer_reward.claim_reward(pot_balance)?;
+
+        self.total_claimed.try_add_assign(to_claim)?;
+        if self.liability_tracked {
+            self.outstanding_liability.try_sub_assign(to_claim)?;
+        } else {
+            // legacy farms: part of the claim may have been credited before it was tracked
+            self.outstanding_liability = self.outstanding_liability.saturating_sub(to_claim);
+        }
+
+        Ok(to_claim)
+    }
+
+    /// only safe while nothing has accrued yet, otherwise earlier credits would be missing
+    pub fn track_liability(&mut self) {
+        if self.funds.total_accrued_to_stakers == 0 {
+            self.liability_tracked = true;
+        }
+    }
+
+    /// accrued at farm level and not claimed (or swept) yet - ie everything farmers could
+    /// still be owed. Legacy farms overcount this, as their earlier claims weren't tracked
+    fn unclaimed_accruals(&self) -> Result<u64> {
+        self.funds
+            .total_accrued_to_stakers
+            .try_sub(self.total_claimed)?
+            .try_sub(self.variable_rate.dust_swept)
+    }
+
+    /// accrued at farm level but not yet credited to any farmer - farmers only get credited
+    /// as they're updated, and the farm rounds up what it accrues (the difference is dust).
+    /// Always 0 for legacy farms, which can't tell this apart from what was credited before
+    /// outstanding_liability was tracked
+    pub fn unsettled_accruals(&self) -> Result<u64> {
+        if !self.liability_tracked {
+            return Ok(0);
+        }
+
+        self.unclaimed_accruals()?
+            .try_sub(self.outstanding_liability)
+    }
+
     /// (!) THIS OPERATION IS IRREVERSIBLE
     /// locking ensures the committed reward cannot be withdrawn/changed by a malicious farm operator
     /// once locked, any funding / cancellation ixs become non executable until reward_ned_ts is reached
@@ -502,6 +965,50 @@ impl FarmReward {
         }
     }
 
+    /// what's in the pot beyond pending funds and everything owed to farmers (credited or not),
+    /// ie tokens that were sent to the pot directly instead of through fund_reward.
+    /// Legacy farms undercount this, never overcount
+    pub fn surplus(&self, pot_balance: u64) -> Result<u64> {
+        let accounted_for = self
+            .funds
+            .pending_amount()?
+            .try_add(self.unclaimed_accruals()?)?;
+
+        Ok(pot_balance.saturating_sub(accounted_for))
+    }
+
+    /// fixed rate: more room for enrollments, on the existing schedule.
+    /// variable rate: spread over what's left of the current reward, same as a merged funding
+    fn fund_surplus(&mut self, now_ts: u64, surplus: u64) -> Result<()> {
+        if self.is_locked(now_ts) {
+            return Err(error!(ErrorCode::RewardLocked));
+        }
+
+        match self.reward_type {
+            RewardType::Variable => {
+                let remaining_duration = self.times.remaining_duration(now_ts)?;
+                require!(remaining_duration > 0, ErrorCode::RewardNotActive);
+
+                self.variable_rate.fund_reward(
+                    now_ts,
+                    &mut self.times,
+                    &mut self.funds,
+                    VariableRateConfig {
+                        amount: surplus,
+                        duration_sec: remaining_duration,
+                    },
+                )
+            }
+            RewardType::Fixed => self.funds.total_funded.try_add_assign(surplus),
+        }
+    }
+
+    /// fixed rate rewards don't round, so there's never anything to sweep
+    fn sweep_dust(&mut self) -> Result<u64> {
+        let unsettled_accruals = self.unsettled_accruals()?;
+        self.variable_rate.sweep_dust(unsettled_accruals)
+    }
+
     fn cancel_reward_by_type(&mut self, now_ts: u64) -> Result<u64> {
         if self.is_locked(now_ts) {
             return Err(error!(ErrorCode::RewardLocked));
@@ -519,6 +1026,33 @@ impl FarmReward {
         }
     }
 
+    fn update_accrued_reward(
+        &mut self,
+        now_ts: u64,
+        farm_rarity_points_staked: u64,
+        farmer_rarity_points_staked: Option<u64>,
+        mut farmer_reward: Option<&mut FarmerReward>,
+        reenroll: bool,
+    ) -> Result<()> {
+        let accrued_before = farmer_reward.as_ref().map(|r| r.accrued_reward);
+
+        self.update_accrued_reward_by_type(
+            now_ts,
+            farm_rarity_points_staked,
+            farmer_rarity_points_staked,
+            farmer_reward.as_deref_mut(),
+            reenroll,
+        )?;
+
+        // whatever the farmer was just credited is now owed to them
+        if let (Some(farmer_reward), Some(accrued_before)) = (farmer_reward, accrued_before) {
+            self.outstanding_liability
+                .try_add_assign(farmer_reward.accrued_reward.try_sub(accrued_before)?)?;
+        }
+
+        Ok(())
+    }
+
     fn update_accrued_reward_by_type(
         &mut self,
         now_ts: u64,
@@ -558,6 +1092,7 @@ impl FarmReward {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::number128::Number128;
 
     #[test]
     fn test_time_tracker() {
@@ -604,4 +1139,228 @@ mod tests {
 
         assert_eq!(20, funds.pending_amount().unwrap());
     }
+
+    fn variable_reward() -> FarmReward {
+        FarmReward {
+            reward_mint: Pubkey::default(),
+            reward_pot: Pubkey::default(),
+            reward_type: RewardType::Variable,
+            fixed_rate: FixedRateReward::default(),
+            variable_rate: VariableRateReward::default(),
+            funds: FundsTracker::default(),
+            times: TimeTracker::default(),
+            total_claimed: 0,
+            outstanding_liability: 0,
+            liability_tracked: true,
+            _reserved: [0; 15],
+        }
+    }
+
+    #[test]
+    fn test_farm_reward_liabilities() {
+        let mut reward = variable_reward();
+        reward
+            .fund_reward_by_type(
+                0,
+                Some(VariableRateConfig {
+                    amount: 1000,
+                    duration_sec: 100,
+                }),
+                None,
+            )
+            .unwrap();
+
+        // farmer holds 3 of the 7 points staked
+        let mut farmer_reward = FarmerReward::default();
+        reward
+            .update_accrued_reward(0, 7, Some(3), Some(&mut farmer_reward), true)
+            .unwrap();
+        reward
+            .update_accrued_reward(50, 7, Some(3), Some(&mut farmer_reward), true)
+            .unwrap();
+        assert_eq!(farmer_reward.accrued_reward, 214);
+        assert_eq!(reward.outstanding_liability, 214);
+
+        // the other 4 points haven't been credited yet
+        assert_eq!(
+            reward.unsettled_accruals().unwrap(),
+            reward.funds.total_accrued_to_stakers - 214
+        );
+
+        // claim capped by the pot
+        assert_eq!(reward.claim_reward(&mut farmer_reward, 100).unwrap(), 100);
+        assert_eq!(reward.total_claimed, 100);
+        assert_eq!(reward.outstanding_liability, 114);
+        assert_eq!(
+            reward.outstanding_liability,
+            farmer_reward.outstanding_reward().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_farm_reward_legacy_liabilities() {
+        let mut reward = variable_reward();
+        reward.liability_tracked = false;
+        reward
+            .fund_reward_by_type(
+                0,
+                Some(VariableRateConfig {
+                    amount: 1000,
+                    duration_sec: 100,
+                }),
+                None,
+            )
+            .unwrap();
+
+        // 300 credited before outstanding_liability was tracked
+        let mut farmer_reward = FarmerReward::default();
+        reward
+            .update_accrued_reward(0, 10, Some(10), Some(&mut farmer_reward), true)
+            .unwrap();
+        reward
+            .update_accrued_reward(30, 10, Some(10), Some(&mut farmer_reward), true)
+            .unwrap();
+        reward.outstanding_liability = 0;
+
+        // too late to start tracking
+        reward.track_liability();
+        assert!(!reward.liability_tracked);
+
+        // the untracked 300 don't count as unsettled, so they can't be swept as dust
+        assert_eq!(reward.unsettled_accruals().unwrap(), 0);
+        assert_eq!(reward.sweep_dust().unwrap(), 0);
+
+        // and they still count against the surplus
+        assert_eq!(reward.surplus(1000).unwrap(), 0);
+        assert_eq!(reward.surplus(1100).unwrap(), 100);
+
+        // claiming more than tracked saturates instead of failing
+        assert_eq!(reward.claim_reward(&mut farmer_reward, 1000).unwrap(), 300);
+        assert_eq!(reward.outstanding_liability, 0);
+        assert_eq!(reward.surplus(700).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_farm_reward_tracked_liabilities() {
+        let mut reward = variable_reward();
+        reward.liability_tracked = false;
+
+        // nothing accrued yet, so tracking can start
+        reward.track_liability();
+        assert!(reward.liability_tracked);
+
+        // claiming more than tracked means the books are off, so it fails
+        let mut farmer_reward = FarmerReward {
+            accrued_reward: 100,
+            ..FarmerReward::default()
+        };
+        assert!(reward.claim_reward(&mut farmer_reward, 1000).is_err());
+    }
+
+    #[test]
+    fn test_farm_reward_dust() {
+        let mut reward = variable_reward();
+        reward
+            .fund_reward_by_type(
+                0,
+                Some(VariableRateConfig {
+                    amount: 1000,
+                    duration_sec: 100,
+                }),
+                None,
+            )
+            .unwrap();
+
+        // 3 + 4 points staked for 50s, then both leave
+        let mut farmer_a = FarmerReward::default();
+        let mut farmer_b = FarmerReward::default();
+        for now_ts in [0, 50] {
+            reward
+                .update_accrued_reward(now_ts, 7, Some(3), Some(&mut farmer_a), true)
+                .unwrap();
+            reward
+                .update_accrued_reward(now_ts, 7, Some(4), Some(&mut farmer_b), true)
+                .unwrap();
+        }
+
+        // 500 accrued at farm level, 214 + 285 credited
+        assert_eq!(reward.funds.total_accrued_to_stakers, 500);
+        assert_eq!(farmer_a.accrued_reward + farmer_b.accrued_reward, 499);
+        assert_eq!(reward.variable_rate.rounding_dust, Number128::from(1u64));
+        assert_eq!(reward.unsettled_accruals().unwrap(), 1);
+
+        assert_eq!(reward.sweep_dust().unwrap(), 1);
+        assert_eq!(reward.unsettled_accruals().unwrap(), 0);
+        assert_eq!(reward.sweep_dust().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_farm_reward_surplus() {
+        let mut reward = variable_reward();
+        reward
+            .fund_reward_by_type(
+                0,
+                Some(VariableRateConfig {
+                    amount: 1000,
+                    duration_sec: 100,
+                }),
+                None,
+            )
+            .unwrap();
+
+        let mut farmer_reward = FarmerReward::default();
+        reward
+            .update_accrued_reward(0, 10, Some(10), Some(&mut farmer_reward), true)
+            .unwrap();
+        reward
+            .update_accrued_reward(50, 10, Some(10), Some(&mut farmer_reward), true)
+            .unwrap();
+        reward.claim_reward(&mut farmer_reward, 1000).unwrap();
+
+        // 500 pending, 500 claimed - anything above 500 in the pot was sent in directly
+        assert_eq!(reward.surplus(500).unwrap(), 0);
+        assert_eq!(reward.surplus(450).unwrap(), 0);
+        assert_eq!(reward.surplus(700).unwrap(), 200);
+
+        // rolled into the remaining 50s
+        reward.fund_surplus(50, 200).unwrap();
+        assert_eq!(reward.surplus(700).unwrap(), 0);
+        assert_eq!(reward.funds.pending_amount().unwrap(), 700);
+        assert_eq!(reward.variable_rate.reward_rate, Number128::from(14u64));
+        assert_eq!(reward.times.reward_end_ts, 100);
+
+        // nothing left to roll into once the reward is over
+        assert!(reward.fund_surplus(100, 1).is_err());
+    }
+
+    #[test]
+    fn test_treasury_splits() {
+        let split = |bps| TreasurySplit {
+            recipient: Pubkey::new_unique(),
+            bps,
+        };
+        let mut extension = FarmExtension::default();
+
+        // have to add up to 100%
+        assert!(extension.set_treasury_splits(&[split(5000)]).is_err());
+        assert!(extension
+            .set_treasury_splits(&[split(5000), split(5001)])
+            .is_err());
+
+        // no duplicates
+        let a = split(5000);
+        assert!(extension.set_treasury_splits(&[a, a]).is_err());
+
+        extension
+            .set_treasury_splits(&[split(7000), split(3000)])
+            .unwrap();
+        let splits = extension.active_treasury_splits();
+        assert_eq!(splits.len(), 2);
+        assert_eq!(splits[0].share_of(999).unwrap(), 699);
+        assert_eq!(splits[1].share_of(999).unwrap(), 299);
+
+        // clearing
+        extension.set_treasury_splits(&[]).unwrap();
+        assert!(extension.active_treasury_splits().is_empty());
+    }
 }
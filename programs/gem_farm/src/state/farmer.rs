@@ -3,6 +3,8 @@ use gem_common::{errors::ErrorCode, *};
 
 use crate::{number128::Number128, state::FixedRateSchedule};
 
+pub const LATEST_FARMER_VERSION: u16 = 1;
+
 #[proc_macros::assert_size(4)]
 #[repr(C)]
 #[derive(Debug, Copy, Clone, AnchorSerialize, AnchorDeserialize, PartialEq)]
@@ -44,11 +46,89 @@ pub struct Farmer {
 
     pub reward_b: FarmerReward,
 
+    /// can claim rewards on behalf of the identity, see claim_delegated
+    /// Pubkey::default() = no delegate
+    pub claim_delegate: Pubkey,
+}
+
+/// gems taken out of the stake by begin_flash_withdraw. They stay in the (locked) vault until
+/// releases_ts, then flash_withdraw hands them over
+#[proc_macros::assert_size(56)]
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default, AnchorSerialize, AnchorDeserialize, PartialEq)]
+pub struct PendingWithdrawal {
+    pub gem_mint: Pubkey,
+
+    pub amount: u64,
+
+    pub rarity_points: u64,
+
+    pub releases_ts: u64,
+}
+
+/// Farmer ran out of reserved space before it got a version field, so since v1 both live in
+/// an extension stored after the Farmer struct, see gem_common::load_extension
+#[proc_macros::assert_size(128)]
+#[repr(C)]
+#[derive(Debug, Copy, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct FarmerExtension {
+    /// has to stay the first field, read on its own by Farmer::version()
+    pub version: u16,
+
+    /// opted in to having rewards claimed by anyone via crank_claim, for the farm's crank tip
+    pub auto_claim: bool,
+
+    /// reserved for future updates, has to be /8
+    _reserved: [u8; 5],
+
+    /// only ever set while the vault is locked - gone with the rest of the vault once unstaked
+    pub pending_withdrawal: PendingWithdrawal,
+
     /// reserved for future updates, has to be /8
-    _reserved: [u8; 32],
+    _reserved2: [u8; 64],
+}
+
+impl FarmerExtension {
+    pub fn latest() -> Self {
+        Self {
+            version: LATEST_FARMER_VERSION,
+            auto_claim: false,
+            _reserved: [0; 5],
+            pending_withdrawal: PendingWithdrawal::default(),
+            _reserved2: [0; 64],
+        }
+    }
 }
 
 impl Farmer {
+    pub const EXTENSION_OFFSET: usize = 8 + std::mem::size_of::<Farmer>();
+
+    pub const SPACE: usize = Self::EXTENSION_OFFSET + std::mem::size_of::<FarmerExtension>();
+
+    /// farmers without an extension predate versioning = v0
+    pub fn version(farmer: &AccountInfo) -> Result<u16> {
+        Ok(load_extension::<u16>(farmer, Self::EXTENSION_OFFSET)?.unwrap_or(0))
+    }
+
+    pub fn extension(farmer: &AccountInfo) -> Result<FarmerExtension> {
+        load_extension(farmer, Self::EXTENSION_OFFSET)?
+            .ok_or_else(|| error!(ErrorCode::OutdatedAccountVersion))
+    }
+
+    pub fn set_extension(farmer: &AccountInfo, extension: &FarmerExtension) -> Result<()> {
+        store_extension(farmer, Self::EXTENSION_OFFSET, extension)
+    }
+
+    pub fn pending_withdrawal(farmer: &AccountInfo) -> Result<PendingWithdrawal> {
+        Ok(Self::extension(farmer)?.pending_withdrawal)
+    }
+
+    pub fn set_pending_withdrawal(farmer: &AccountInfo, pending: PendingWithdrawal) -> Result<()> {
+        let mut extension = Self::extension(farmer)?;
+        extension.pending_withdrawal = pending;
+        Self::set_extension(farmer, &extension)
+    }
+
     pub fn begin_staking(
         &mut self,
         min_staking_period_sec: u64,
@@ -113,6 +193,65 @@ impl Farmer {
         Ok(())
     }
 
+    /// the farmer stays staked with what's left. The cooldown applies to the withdrawn part,
+    /// which stays in the vault until it passes - and so does the next partial withdrawal
+    pub fn end_staking_partially(
+        &mut self,
+        now_ts: u64,
+        cooldown_period_sec: u64,
+        gems_left_staked: u64,
+        rarity_points_left_staked: u64,
+    ) -> Result<(u64, u64)> {
+        if !self.can_end_staking(now_ts) {
+            return Err(error!(ErrorCode::MinStakingNotPassed));
+        }
+
+        if !self.can_end_cooldown(now_ts) {
+            return Err(error!(ErrorCode::CooldownNotPassed));
+        }
+
+        let previous_gems_staked = self.gems_staked;
+        let previous_rarity_points_staked = self.rarity_points_staked;
+        self.gems_staked = gems_left_staked;
+        self.rarity_points_staked = rarity_points_left_staked;
+        self.cooldown_ends_ts = now_ts.try_add(cooldown_period_sec)?;
+
+        Ok((previous_gems_staked, previous_rarity_points_staked))
+    }
+
+    /// skips both the min staking period and the cooldown, used when the farm is in emergency
+    pub fn end_staking_immediately(&mut self) -> (u64, u64) {
+        self.state = FarmerState::Unstaked;
+
+        let gems_unstaked = self.gems_staked;
+        let rarity_points_unstaked = self.rarity_points_staked;
+        self.gems_staked = 0;
+        self.rarity_points_staked = 0;
+        self.min_staking_ends_ts = 0;
+        self.cooldown_ends_ts = 0;
+
+        (gems_unstaked, rarity_points_unstaked)
+    }
+
+    /// how long until the farmer could withdraw their gems, were they to unstake now
+    pub fn remaining_lockup_sec(&self, now_ts: u64, cooldown_period_sec: u64) -> Result<u64> {
+        match self.state {
+            FarmerState::Unstaked => Ok(0),
+            FarmerState::Staked => self
+                .min_staking_ends_ts
+                .saturating_sub(now_ts)
+                .try_add(cooldown_period_sec),
+            FarmerState::PendingCooldown => Ok(self.cooldown_ends_ts.saturating_sub(now_ts)),
+        }
+    }
+
+    /// rent can only be reclaimed once the farmer has nothing left in the farm
+    pub fn can_close(&self) -> Result<bool> {
+        Ok(self.state == FarmerState::Unstaked
+            && self.reward_a.outstanding_reward()? == 0
+            && self.reward_b.outstanding_reward()? == 0)
+    }
+
     fn can_end_staking(&self, now_ts: u64) -> bool {
         now_ts >= self.min_staking_ends_ts
     }
@@ -126,7 +265,7 @@ impl Farmer {
 
 #[proc_macros::assert_size(216)]
 #[repr(C)]
-#[derive(Debug, Copy, Clone, AnchorSerialize, AnchorDeserialize)]
+#[derive(Debug, Copy, Clone, Default, AnchorSerialize, AnchorDeserialize)]
 pub struct FarmerReward {
     /// total, not per rarity point. Never goes down (ie is cumulative)
     pub paid_out_reward: u64,
@@ -139,11 +278,19 @@ pub struct FarmerReward {
 
     pub fixed_rate: FarmerFixedRateReward,
 
-    /// reserved for future updates, has to be /8
-    _reserved: [u8; 32],
+    /// token account delegated claims are paid into
+    /// Pubkey::default() = the identity's ATA
+    pub claim_destination: Pubkey,
 }
 
 impl FarmerReward {
+    pub fn claim_destination_or(&self, identity_ata: Pubkey) -> Pubkey {
+        if self.claim_destination == Pubkey::default() {
+            return identity_ata;
+        }
+        self.claim_destination
+    }
+
     pub fn outstanding_reward(&self) -> Result<u64> {
         self.accrued_reward.try_sub(self.paid_out_reward)
     }
@@ -183,7 +330,7 @@ impl FarmerReward {
 
 #[proc_macros::assert_size(32)]
 #[repr(C)]
-#[derive(Debug, Copy, Clone, AnchorSerialize, AnchorDeserialize)]
+#[derive(Debug, Copy, Clone, Default, AnchorSerialize, AnchorDeserialize)]
 pub struct FarmerVariableRateReward {
     /// used to keep track of how much of the variable reward has been updated for this farmer
     /// (read more in variable rate config)
@@ -316,11 +463,96 @@ mod tests {
                     _reserved: [0; 16],
                 },
                 fixed_rate: FarmerFixedRateReward::new(),
-                _reserved: [0; 32],
+                claim_destination: Pubkey::default(),
             }
         }
     }
 
+    fn staked_farmer() -> Farmer {
+        Farmer {
+            farm: Pubkey::default(),
+            identity: Pubkey::default(),
+            vault: Pubkey::default(),
+            state: FarmerState::Staked,
+            gems_staked: 3,
+            rarity_points_staked: 5,
+            min_staking_ends_ts: 200,
+            cooldown_ends_ts: 0,
+            reward_a: FarmerReward::new(),
+            reward_b: FarmerReward::new(),
+            claim_delegate: Pubkey::default(),
+        }
+    }
+
+    #[test]
+    fn test_farmer_end_staking_immediately() {
+        let mut f = staked_farmer();
+
+        // min staking period is ignored
+        assert_eq!((3, 5), f.end_staking_immediately());
+        assert_eq!(FarmerState::Unstaked, f.state);
+        assert_eq!(0, f.gems_staked);
+        assert_eq!(0, f.min_staking_ends_ts);
+
+        // so is the cooldown
+        let mut f = staked_farmer();
+        f.end_staking_begin_cooldown(250, 100).unwrap();
+        assert_eq!((0, 0), f.end_staking_immediately());
+        assert_eq!(FarmerState::Unstaked, f.state);
+        assert_eq!(0, f.cooldown_ends_ts);
+    }
+
+    #[test]
+    fn test_farmer_end_staking_partially() {
+        let mut f = staked_farmer();
+
+        // min staking period has to pass first
+        assert!(f.end_staking_partially(150, 100, 2, 3).is_err());
+
+        assert_eq!((3, 5), f.end_staking_partially(200, 100, 2, 3).unwrap());
+        assert_eq!(FarmerState::Staked, f.state);
+        assert_eq!(2, f.gems_staked);
+        assert_eq!(3, f.rarity_points_staked);
+        assert_eq!(300, f.cooldown_ends_ts);
+
+        // then the cooldown
+        assert!(f.end_staking_partially(250, 100, 1, 1).is_err());
+        assert_eq!((2, 3), f.end_staking_partially(300, 100, 1, 1).unwrap());
+    }
+
+    #[test]
+    fn test_farmer_remaining_lockup_sec() {
+        let mut f = staked_farmer();
+
+        // rest of min staking + the full cooldown
+        assert_eq!(150, f.remaining_lockup_sec(150, 100).unwrap());
+        assert_eq!(100, f.remaining_lockup_sec(250, 100).unwrap());
+
+        // rest of the cooldown only
+        f.end_staking_begin_cooldown(250, 100).unwrap();
+        assert_eq!(70, f.remaining_lockup_sec(280, 100).unwrap());
+        assert_eq!(0, f.remaining_lockup_sec(400, 100).unwrap());
+
+        f.end_cooldown(400).unwrap();
+        assert_eq!(0, f.remaining_lockup_sec(400, 100).unwrap());
+    }
+
+    #[test]
+    fn test_farmer_can_close() {
+        let mut f = staked_farmer();
+        assert!(!f.can_close().unwrap());
+
+        // unstaked, but still has rewards to claim
+        f.end_staking_immediately();
+        assert!(!f.can_close().unwrap());
+
+        f.reward_a.claim_reward(9999).unwrap();
+        assert!(!f.can_close().unwrap());
+
+        f.reward_b.claim_reward(9999).unwrap();
+        assert!(f.can_close().unwrap());
+    }
+
     #[test]
     fn test_farmer_fixed_rate_reward() {
         let r = FarmerFixedRateReward::new();
@@ -370,6 +602,16 @@ mod tests {
         assert_eq!(210, r.fixed_rate.last_updated_ts);
     }
 
+    #[test]
+    fn test_farmer_reward_claim_destination() {
+        let mut r = FarmerReward::new();
+        let ata = Pubkey::new_unique();
+        assert_eq!(ata, r.claim_destination_or(ata));
+
+        r.claim_destination = Pubkey::new_unique();
+        assert_eq!(r.claim_destination, r.claim_destination_or(ata));
+    }
+
     #[test]
     fn test_farmer_reward_claim() {
         let mut r = FarmerReward::new();
@@ -378,4 +620,38 @@ mod tests {
         r.claim_reward(100).unwrap();
         assert_eq!(23, r.outstanding_reward().unwrap());
     }
+
+    #[test]
+    fn test_farmer_extension_auto_claim() {
+        let mut e = FarmerExtension::latest();
+        assert!(!e.auto_claim);
+
+        // has to fit exactly into the space reserved after the Farmer
+        e.auto_claim = true;
+        let bytes = e.try_to_vec().unwrap();
+        assert_eq!(std::mem::size_of::<FarmerExtension>(), bytes.len());
+
+        let e = FarmerExtension::try_from_slice(&bytes).unwrap();
+        assert_eq!(LATEST_FARMER_VERSION, e.version);
+        assert!(e.auto_claim);
+        assert_eq!(PendingWithdrawal::default(), e.pending_withdrawal);
+    }
+
+    #[test]
+    fn test_farmer_extension_pending_withdrawal() {
+        let mut e = FarmerExtension::latest();
+        e.pending_withdrawal = PendingWithdrawal {
+            gem_mint: Pubkey::new_unique(),
+            amount: 2,
+            rarity_points: 30,
+            releases_ts: 400,
+        };
+
+        let bytes = e.try_to_vec().unwrap();
+        assert_eq!(std::mem::size_of::<FarmerExtension>(), bytes.len());
+
+        let decoded = FarmerExtension::try_from_slice(&bytes).unwrap();
+        assert_eq!(e.pending_withdrawal, decoded.pending_withdrawal);
+        assert!(!decoded.auto_claim);
+    }
 }
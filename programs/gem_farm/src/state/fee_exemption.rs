@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+/// if this PDA exists (seeds = [b"fee_exemption", farm]), the farm recorded below doesn't pay
+/// any protocol fees (see add_fee_exemption / remove_fee_exemption)
+#[proc_macros::assert_size(64)]
+#[repr(C)]
+#[account]
+pub struct FeeExemption {
+    pub farm: Pubkey,
+
+    /// reserved for future updates, has to be /8
+    _reserved: [u8; 32],
+}
+
+impl FeeExemption {
+    /// the account is passed unchecked so that non-exempt farms can pass the empty PDA,
+    /// seeds have to be checked by the caller
+    pub fn exists(fee_exemption: &AccountInfo) -> bool {
+        fee_exemption.owner == &crate::ID && fee_exemption.lamports() > 0
+    }
+}
@@ -243,7 +243,7 @@ impl FixedRateSchedule {
 
 #[proc_macros::assert_size(128)]
 #[repr(C)]
-#[derive(Debug, Copy, Clone, AnchorSerialize, AnchorDeserialize)]
+#[derive(Debug, Copy, Clone, Default, AnchorSerialize, AnchorDeserialize)]
 pub struct FixedRateReward {
     /// configured on funding
     pub schedule: FixedRateSchedule,
@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+/// if this PDA exists, this means both farms recorded below agreed that farmers can move their
+/// staked gems from the former to the latter, without going through unstaking (see migrate_farmer)
+#[proc_macros::assert_size(96)]
+#[repr(C)]
+#[account]
+pub struct MigrationProof {
+    pub from_farm: Pubkey,
+
+    pub to_farm: Pubkey,
+
+    /// reserved for future updates, has to be /8
+    _reserved: [u8; 32],
+}
@@ -1,11 +1,19 @@
 pub mod authorization_proof;
 pub mod farm;
 pub mod farmer;
+pub mod fee_exemption;
 pub mod fixed_rewards;
+pub mod migration_proof;
+pub mod pending_farm_config;
+pub mod protocol_config;
 pub mod variable_rewards;
 
 pub use authorization_proof::*;
 pub use farm::*;
 pub use farmer::*;
+pub use fee_exemption::*;
 pub use fixed_rewards::*;
+pub use migration_proof::*;
+pub use pending_farm_config::*;
+pub use protocol_config::*;
 pub use variable_rewards::*;
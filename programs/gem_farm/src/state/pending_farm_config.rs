@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{FarmConfig, MaxCounts, TokenFees};
+
+/// if this PDA exists, the farm manager has proposed a config change for the farm recorded
+/// below. Anyone can apply it once applies_ts is reached
+#[proc_macros::assert_size(160)]
+#[repr(C)]
+#[account]
+pub struct PendingFarmConfig {
+    pub farm: Pubkey,
+
+    /// proposal ts + the farm's config_delay_sec at the time of proposal
+    pub applies_ts: u64,
+
+    pub config: FarmConfig,
+
+    pub max_counts: MaxCounts,
+
+    /// reserved for future updates, has to be /8
+    _reserved: [u8; 4],
+
+    pub token_fees: TokenFees,
+
+    pub instant_unstake_fee_lamp_per_sec: u64,
+
+    pub config_delay_sec: u64,
+
+    /// reserved for future updates, has to be /8
+    _reserved2: [u8; 16],
+}
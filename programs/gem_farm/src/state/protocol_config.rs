@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+
+use crate::state::FeeExemption;
+
+/// protocol fees charged by each instruction, in lamports
+#[proc_macros::assert_size(56)]
+#[repr(C)]
+#[derive(Debug, Copy, Clone, AnchorSerialize, AnchorDeserialize, PartialEq)]
+pub struct FeeTable {
+    pub init_farm_lamp: u64,
+
+    pub init_farmer_lamp: u64,
+
+    /// also charged when a flash deposit is used to begin staking
+    pub stake_lamp: u64,
+
+    /// charged on each unstake call (so twice when going through cooldown)
+    pub unstake_lamp: u64,
+
+    pub flash_deposit_lamp: u64,
+
+    pub flash_withdraw_lamp: u64,
+
+    pub instant_unstake_lamp: u64,
+}
+
+/// singleton (seeds = [b"protocol_config"]), holds the fees collected by the protocol
+/// on top of whatever each farm charges its farmers
+#[proc_macros::assert_size(440)]
+#[repr(C)]
+#[account]
+#[derive(Debug)]
+pub struct ProtocolConfig {
+    /// can update the config, including itself
+    pub admin: Pubkey,
+
+    /// receives all protocol fees
+    pub fee_wallet: Pubkey,
+
+    pub fees: FeeTable,
+
+    /// used to hold the fee exempt farms, which are now FeeExemption PDAs
+    _reserved_fee_exempt: [u8; 256],
+
+    /// reserved for future updates, has to be /8
+    _reserved: [u8; 64],
+}
+
+impl ProtocolConfig {
+    /// the fee a given farm actually pays, fee_exemption = the farm's FeeExemption PDA
+    pub fn fee_for(&self, fee_exemption: &AccountInfo, fee: u64) -> u64 {
+        if FeeExemption::exists(fee_exemption) {
+            return 0;
+        }
+        fee
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn protocol_config() -> ProtocolConfig {
+        ProtocolConfig {
+            admin: Pubkey::default(),
+            fee_wallet: Pubkey::default(),
+            fees: FeeTable {
+                init_farm_lamp: 2_500_000_000,
+                init_farmer_lamp: 10_000_000,
+                stake_lamp: 2_000_000,
+                unstake_lamp: 1_000_000,
+                flash_deposit_lamp: 1_000_000,
+                flash_withdraw_lamp: 1_000_000,
+                instant_unstake_lamp: 1_000_000,
+            },
+            _reserved_fee_exempt: [0; 256],
+            _reserved: [0; 64],
+        }
+    }
+
+    #[test]
+    fn test_protocol_config_fee_exemptions() {
+        let c = protocol_config();
+        let key = Pubkey::new_unique();
+        let system_program = Pubkey::default();
+        let mut lamports = 0;
+        let mut data = vec![];
+
+        // PDA not created yet
+        let info = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &system_program,
+            false,
+            0,
+        );
+        assert_eq!(100, c.fee_for(&info, 100));
+
+        // PDA created by add_fee_exemption
+        let mut lamports = 1_000_000;
+        let mut data = vec![0; 8 + std::mem::size_of::<FeeExemption>()];
+        let info = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &crate::ID,
+            false,
+            0,
+        );
+        assert_eq!(0, c.fee_for(&info, 100));
+
+        // PDA closed by remove_fee_exemption earlier in the same tx
+        **info.lamports.borrow_mut() = 0;
+        assert_eq!(100, c.fee_for(&info, 100));
+    }
+}
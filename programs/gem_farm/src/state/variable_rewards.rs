@@ -16,7 +16,7 @@ pub struct VariableRateConfig {
 
 #[proc_macros::assert_size(72)]
 #[repr(C)]
-#[derive(Debug, Copy, Clone, AnchorSerialize, AnchorDeserialize)]
+#[derive(Debug, Copy, Clone, Default, AnchorSerialize, AnchorDeserialize)]
 pub struct VariableRateReward {
     /// in tokens/s, = calculated as total reward pot at initialization / reward duration
     pub reward_rate: Number128,
@@ -32,8 +32,14 @@ pub struct VariableRateReward {
     /// 3) update their record of flag position, so that next time we don't count this distance again
     pub accrued_reward_per_rarity_point: Number128,
 
+    /// fractions lost to rounding: the farm accrues rounded up, farmers get credited rounded down.
+    /// Sits in the pot unclaimable, until swept (see sweep_dust)
+    pub rounding_dust: Number128,
+
+    pub dust_swept: u64,
+
     /// reserved for future updates, has to be /8
-    _reserved: [u8; 32],
+    _reserved: [u8; 8],
 }
 
 impl VariableRateReward {
@@ -107,10 +113,15 @@ impl VariableRateReward {
             .try_add_assign(newly_accrued_reward_per_rarity_point)?;
 
         // update overall reward
-        funds.total_accrued_to_stakers.try_add_assign(
-            newly_accrued_reward_per_rarity_point
-                .try_mul(Number128::from(farm_rarity_points_staked))?
-                .as_u64_ceil(0)?, //overestimate at farm level
+        let newly_accrued_reward = newly_accrued_reward_per_rarity_point
+            .try_mul(Number128::from(farm_rarity_points_staked))?;
+        let newly_accrued_reward_ceil = newly_accrued_reward.as_u64_ceil(0)?; //overestimate at farm level
+
+        funds
+            .total_accrued_to_stakers
+            .try_add_assign(newly_accrued_reward_ceil)?;
+        self.rounding_dust.try_add_assign(
+            Number128::from(newly_accrued_reward_ceil).try_sub(newly_accrued_reward)?,
         )?;
 
         // update farmer, if one was passed
@@ -124,8 +135,13 @@ impl VariableRateReward {
                     )?,
                 )?;
 
+            let newly_accrued_to_farmer_floor = newly_accrued_to_farmer.as_u64(0)?; //underestimate at farmer level
+            self.rounding_dust.try_add_assign(
+                newly_accrued_to_farmer.try_sub(Number128::from(newly_accrued_to_farmer_floor))?,
+            )?;
+
             farmer_reward.update_variable_reward(
-                newly_accrued_to_farmer.as_u64(0)?, //underestimate at farmer level
+                newly_accrued_to_farmer_floor,
                 self.accrued_reward_per_rarity_point,
             )?;
         }
@@ -136,6 +152,17 @@ impl VariableRateReward {
         Ok(())
     }
 
+    /// whole tokens of dust, capped at what the farm hasn't credited to anyone.
+    /// Only safe to take out once every farmer has been settled for good
+    pub fn sweep_dust(&mut self, unsettled_accruals: u64) -> Result<u64> {
+        let dust = std::cmp::min(self.rounding_dust.as_u64(0)?, unsettled_accruals);
+
+        self.rounding_dust.try_sub_assign(Number128::from(dust))?;
+        self.dust_swept.try_add_assign(dust)?;
+
+        Ok(dust)
+    }
+
     fn newly_accrued_reward_per_rarity_point(
         &self,
         farm_rarity_points_staked: u64,
@@ -164,7 +191,9 @@ mod tests {
             reward_rate: Number128::from(10u64),
             reward_last_updated_ts: 200,
             accrued_reward_per_rarity_point: Number128::from(1234u64),
-            _reserved: [0; 32],
+            rounding_dust: Number128::ZERO,
+            dust_swept: 0,
+            _reserved: [0; 8],
         };
 
         let farm_points_staked = 25;
@@ -200,7 +229,9 @@ mod tests {
             reward_rate: Number128::from(10u64),
             reward_last_updated_ts: 0,
             accrued_reward_per_rarity_point: Number128::from(1234u64),
-            _reserved: [0; 32],
+            rounding_dust: Number128::ZERO,
+            dust_swept: 0,
+            _reserved: [0; 8],
         };
 
         var_reward
@@ -246,7 +277,9 @@ mod tests {
             reward_rate: Number128::from(10u64),
             reward_last_updated_ts: 0,
             accrued_reward_per_rarity_point: Number128::from(1234u64),
-            _reserved: [0; 32],
+            rounding_dust: Number128::ZERO,
+            dust_swept: 0,
+            _reserved: [0; 8],
         };
 
         var_reward
@@ -290,7 +323,9 @@ mod tests {
             reward_rate: Number128::from(10u64),
             reward_last_updated_ts: 0,
             accrued_reward_per_rarity_point: Number128::from(1234u64),
-            _reserved: [0; 32],
+            rounding_dust: Number128::ZERO,
+            dust_swept: 0,
+            _reserved: [0; 8],
         };
 
         var_reward
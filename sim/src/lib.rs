@@ -0,0 +1,13 @@
+//! offline reward simulator for gem_farm
+//!
+//! replays a scenario of stake / unstake / fund / claim events against the program's own
+//! reward code (FixedRateReward, VariableRateReward, TimeTracker, FundsTracker), so that
+//! reward managers can check accruals, runway and apr before committing any funds
+
+pub mod report;
+pub mod scenario;
+pub mod sim;
+
+pub use report::*;
+pub use scenario::*;
+pub use sim::*;
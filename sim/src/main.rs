@@ -0,0 +1,46 @@
+//! eg: gem-sim scenarios/fixed.json
+//!     gem-sim scenarios/fixed.json --json --out report.json
+//!
+//! the program's msg! logs end up on stdout too, so use --out for anything machine-read
+
+use std::{fs::File, path::PathBuf};
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use gem_sim::{run, Scenario};
+
+#[derive(Parser)]
+#[command(name = "gem-sim", about = "replay a gem_farm reward scenario offline")]
+struct Cli {
+    /// json scenario file
+    scenario: PathBuf,
+
+    /// print the report as json instead of text
+    #[arg(long)]
+    json: bool,
+
+    /// write the report here instead of stdout
+    #[arg(long)]
+    out: Option<PathBuf>,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    let file = File::open(&cli.scenario)
+        .with_context(|| format!("can't open {}", cli.scenario.display()))?;
+    let scenario: Scenario = serde_json::from_reader(file)?;
+
+    let report = run(&scenario)?;
+    let output = match cli.json {
+        true => serde_json::to_string_pretty(&report)?,
+        false => report.to_string(),
+    };
+
+    match cli.out {
+        Some(path) => std::fs::write(path, output)?,
+        None => print!("{}", output),
+    }
+
+    Ok(())
+}
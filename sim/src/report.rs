@@ -0,0 +1,302 @@
+use std::fmt;
+
+use gem_farm::state::RewardType;
+use serde::Serialize;
+
+use crate::{
+    scenario::{Event, Scenario},
+    sim::{SimError, SimResult, Simulator},
+};
+
+pub const SECS_PER_DAY: u64 = 24 * 60 * 60;
+
+pub const SECS_PER_YEAR: u64 = 365 * SECS_PER_DAY;
+
+#[derive(Serialize)]
+pub struct FarmerSnapshot {
+    pub farmer: String,
+
+    pub rarity_points: u64,
+
+    pub accrued_reward: u64,
+
+    pub paid_out_reward: u64,
+
+    pub outstanding_reward: u64,
+
+    /// over the time the farmer had something staked
+    pub reward_per_rarity_point_per_year: Option<f64>,
+
+    /// only if the scenario has prices
+    pub apr_pct: Option<f64>,
+}
+
+#[derive(Serialize)]
+pub struct Snapshot {
+    pub ts: u64,
+
+    pub total_funded: u64,
+
+    pub total_refunded: u64,
+
+    pub total_accrued_to_stakers: u64,
+
+    /// funded but not yet accrued or refunded - what new fixed rate enrollments are checked against
+    pub pending_amount: u64,
+
+    /// fixed rate only - promised to currently enrolled farmers
+    pub reserved_amount: u64,
+
+    /// variable rate only - whole tokens lost to rounding so far
+    pub rounding_dust: u64,
+
+    pub pot_balance: u64,
+
+    /// accrued to farmers but not claimed yet
+    pub outstanding_reward: u64,
+
+    pub reward_end_ts: u64,
+
+    /// at the current rate (fixed: current tier of each staked farmer)
+    pub reward_per_day: f64,
+
+    /// how long the pot lasts at reward_per_day, after paying what's outstanding
+    pub runway_sec: Option<u64>,
+
+    pub farmers: Vec<FarmerSnapshot>,
+}
+
+#[derive(Serialize)]
+pub struct Failure {
+    pub ts: u64,
+
+    pub action: String,
+
+    pub farmer: Option<String>,
+
+    pub error: String,
+
+    /// how much more funding would have let the event go through (RewardUnderfunded only)
+    pub shortfall: Option<u64>,
+}
+
+#[derive(Serialize)]
+pub struct SimReport {
+    pub snapshots: Vec<Snapshot>,
+
+    /// events that would have failed on chain - they're skipped and the replay carries on
+    pub failures: Vec<Failure>,
+}
+
+/// replays the whole scenario, snapshotting on every report event and at the end
+pub fn run(scenario: &Scenario) -> SimResult<SimReport> {
+    let mut sim = Simulator::new(
+        scenario.reward_type.into(),
+        scenario.schedule.map(Into::into),
+    );
+    let mut snapshots = vec![];
+    let mut failures = vec![];
+
+    for event in scenario.events.iter() {
+        match sim.apply(event) {
+            Ok(()) => {}
+            Err(e @ SimError::Scenario(_)) => return Err(e),
+            Err(e) => failures.push(failure(event, &e)),
+        }
+
+        if let crate::scenario::Action::Report = event.action {
+            snapshots.push(snapshot(&sim, event.ts, scenario)?);
+        }
+    }
+
+    let end_ts = scenario.end_ts.unwrap_or(sim.now_ts);
+    if end_ts < sim.now_ts {
+        return Err(SimError::Scenario(format!(
+            "end_ts {} is before the last event",
+            end_ts
+        )));
+    }
+    snapshots.push(snapshot(&sim, end_ts, scenario)?);
+
+    Ok(SimReport {
+        snapshots,
+        failures,
+    })
+}
+
+fn failure(event: &Event, e: &SimError) -> Failure {
+    Failure {
+        ts: event.ts,
+        action: event.action.name().to_string(),
+        farmer: event.action.farmer().map(str::to_string),
+        error: e.to_string(),
+        shortfall: e.shortfall(),
+    }
+}
+
+/// works on a copy, like quote_farmer_rewards - farmers are brought up to date but not re-enrolled
+pub fn snapshot(sim: &Simulator, ts: u64, scenario: &Scenario) -> SimResult<Snapshot> {
+    let mut sim = sim.clone();
+    sim.now_ts = ts;
+
+    let names: Vec<String> = sim.farmers.keys().cloned().collect();
+    for name in names.iter() {
+        sim.update_rewards(Some(name), false)?;
+    }
+    sim.update_rewards(None, false)?;
+
+    let mut farmers = vec![];
+    let mut outstanding_reward = 0;
+    for (name, farmer) in sim.farmers.iter() {
+        let reward = &farmer.reward;
+        let outstanding = reward.outstanding_reward()?;
+        outstanding_reward += outstanding;
+
+        let reward_per_rarity_point_per_year = match farmer.point_secs {
+            0 => None,
+            point_secs => {
+                Some(reward.accrued_reward as f64 / point_secs as f64 * SECS_PER_YEAR as f64)
+            }
+        };
+        let apr_pct = match (scenario.reward_token_price, scenario.rarity_point_value) {
+            (Some(price), Some(value)) if value > 0.0 => {
+                reward_per_rarity_point_per_year.map(|r| r * price / value * 100.0)
+            }
+            _ => None,
+        };
+
+        farmers.push(FarmerSnapshot {
+            farmer: name.clone(),
+            rarity_points: farmer.rarity_points,
+            accrued_reward: reward.accrued_reward,
+            paid_out_reward: reward.paid_out_reward,
+            outstanding_reward: outstanding,
+            reward_per_rarity_point_per_year,
+            apr_pct,
+        });
+    }
+
+    let reward_per_day = reward_per_day(&sim)?;
+    let unowed = sim.pot_balance().saturating_sub(outstanding_reward);
+    let runway_sec = match reward_per_day {
+        r if r > 0.0 => Some((unowed as f64 / r * SECS_PER_DAY as f64) as u64),
+        _ => None,
+    };
+
+    Ok(Snapshot {
+        ts,
+        total_funded: sim.funds.total_funded,
+        total_refunded: sim.funds.total_refunded,
+        total_accrued_to_stakers: sim.funds.total_accrued_to_stakers,
+        pending_amount: sim.funds.pending_amount()?,
+        reserved_amount: sim.fixed_rate.reserved_amount,
+        rounding_dust: sim.variable_rate.rounding_dust.as_u64(0)?,
+        pot_balance: sim.pot_balance(),
+        outstanding_reward,
+        reward_end_ts: sim.times.reward_end_ts,
+        reward_per_day,
+        runway_sec,
+        farmers,
+    })
+}
+
+fn reward_per_day(sim: &Simulator) -> SimResult<f64> {
+    let remaining_duration = sim.times.remaining_duration(sim.now_ts)?;
+    if remaining_duration == 0 || sim.rarity_points_staked == 0 {
+        return Ok(0.0);
+    }
+
+    match sim.reward_type {
+        // the variable rate spreads whatever is pending over the time that's left
+        RewardType::Variable => Ok(
+            sim.funds.pending_amount()? as f64 / remaining_duration as f64 * SECS_PER_DAY as f64,
+        ),
+        RewardType::Fixed => {
+            let mut total = 0;
+            for farmer in sim.farmers.values() {
+                let fixed_rate = &farmer.reward.fixed_rate;
+                if !fixed_rate.is_staked() {
+                    continue;
+                }
+                let start_from = sim.now_ts - fixed_rate.begin_staking_ts;
+                total += fixed_rate.promised_schedule.reward_amount(
+                    start_from,
+                    start_from + SECS_PER_DAY,
+                    farmer.rarity_points,
+                )?;
+            }
+            Ok(total as f64)
+        }
+    }
+}
+
+impl fmt::Display for SimReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for s in self.snapshots.iter() {
+            writeln!(f, "--- ts {}", s.ts)?;
+            writeln!(
+                f,
+                "funded {} / refunded {} / accrued {} / pending {} / reserved {} / dust {}",
+                s.total_funded,
+                s.total_refunded,
+                s.total_accrued_to_stakers,
+                s.pending_amount,
+                s.reserved_amount,
+                s.rounding_dust
+            )?;
+            writeln!(
+                f,
+                "pot {} (outstanding {}), reward ends {}, {:.2}/day, runway {}",
+                s.pot_balance,
+                s.outstanding_reward,
+                s.reward_end_ts,
+                s.reward_per_day,
+                match s.runway_sec {
+                    Some(sec) => format!("{:.1} days", sec as f64 / SECS_PER_DAY as f64),
+                    None => "n/a".to_string(),
+                }
+            )?;
+            for farmer in s.farmers.iter() {
+                write!(
+                    f,
+                    "  {}: {} points, accrued {}, paid {}, outstanding {}",
+                    farmer.farmer,
+                    farmer.rarity_points,
+                    farmer.accrued_reward,
+                    farmer.paid_out_reward,
+                    farmer.outstanding_reward
+                )?;
+                if let Some(r) = farmer.reward_per_rarity_point_per_year {
+                    write!(f, ", {:.2}/point/year", r)?;
+                }
+                if let Some(apr) = farmer.apr_pct {
+                    write!(f, ", apr {:.2}%", apr)?;
+                }
+                writeln!(f)?;
+            }
+        }
+
+        if !self.failures.is_empty() {
+            writeln!(f, "--- failed events")?;
+        }
+        for failure in self.failures.iter() {
+            write!(
+                f,
+                "  ts {} {}{}: {}",
+                failure.ts,
+                failure.action,
+                match &failure.farmer {
+                    Some(farmer) => format!(" ({})", farmer),
+                    None => String::new(),
+                },
+                failure.error
+            )?;
+            if let Some(shortfall) = failure.shortfall {
+                write!(f, " - fund at least {} more", shortfall)?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
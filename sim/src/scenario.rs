@@ -0,0 +1,143 @@
+use gem_farm::state::{FixedRateSchedule, RewardType, TierConfig};
+use serde::Deserialize;
+
+/// a single reward (a or b) and the events that happen to it, in chronological order
+#[derive(Deserialize)]
+pub struct Scenario {
+    pub reward_type: RewardTypeFile,
+
+    /// used by fixed rate fundings that don't bring their own schedule
+    #[serde(default)]
+    pub schedule: Option<ScheduleFile>,
+
+    /// when to take the final snapshot, defaults to the last event
+    #[serde(default)]
+    pub end_ts: Option<u64>,
+
+    /// both needed to turn reward/rarity point into an apr, in any common unit (eg usd)
+    #[serde(default)]
+    pub reward_token_price: Option<f64>,
+
+    #[serde(default)]
+    pub rarity_point_value: Option<f64>,
+
+    pub events: Vec<Event>,
+}
+
+#[derive(Deserialize)]
+pub struct Event {
+    pub ts: u64,
+
+    #[serde(flatten)]
+    pub action: Action,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum Action {
+    /// stakes a new farmer, or adds to an existing farmer's stake (flash deposit)
+    Stake {
+        farmer: String,
+        rarity_points: u64,
+    },
+
+    /// unstakes everything, cooldowns don't matter for rewards so aren't modelled
+    Unstake {
+        farmer: String,
+    },
+
+    Claim {
+        farmer: String,
+    },
+
+    Fund {
+        amount: u64,
+        duration_sec: u64,
+        #[serde(default)]
+        schedule: Option<ScheduleFile>,
+    },
+
+    Cancel,
+
+    /// takes a snapshot at this point in time
+    Report,
+}
+
+impl Action {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::Stake { .. } => "stake",
+            Action::Unstake { .. } => "unstake",
+            Action::Claim { .. } => "claim",
+            Action::Fund { .. } => "fund",
+            Action::Cancel => "cancel",
+            Action::Report => "report",
+        }
+    }
+
+    pub fn farmer(&self) -> Option<&str> {
+        match self {
+            Action::Stake { farmer, .. }
+            | Action::Unstake { farmer }
+            | Action::Claim { farmer } => Some(farmer),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RewardTypeFile {
+    Variable,
+    Fixed,
+}
+
+impl From<RewardTypeFile> for RewardType {
+    fn from(reward_type: RewardTypeFile) -> Self {
+        match reward_type {
+            RewardTypeFile::Variable => RewardType::Variable,
+            RewardTypeFile::Fixed => RewardType::Fixed,
+        }
+    }
+}
+
+// serde mirrors of the schedule, the program types only derive borsh
+
+#[derive(Clone, Copy, Deserialize)]
+pub struct TierFile {
+    pub reward_rate: u64,
+    pub required_tenure: u64,
+}
+
+impl From<TierFile> for TierConfig {
+    fn from(tier: TierFile) -> Self {
+        Self {
+            reward_rate: tier.reward_rate,
+            required_tenure: tier.required_tenure,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Deserialize)]
+pub struct ScheduleFile {
+    pub base_rate: u64,
+    #[serde(default)]
+    pub tier1: Option<TierFile>,
+    #[serde(default)]
+    pub tier2: Option<TierFile>,
+    #[serde(default)]
+    pub tier3: Option<TierFile>,
+    pub denominator: u64,
+}
+
+impl From<ScheduleFile> for FixedRateSchedule {
+    fn from(schedule: ScheduleFile) -> Self {
+        Self {
+            base_rate: schedule.base_rate,
+            tier1: schedule.tier1.map(Into::into),
+            tier2: schedule.tier2.map(Into::into),
+            tier3: schedule.tier3.map(Into::into),
+            denominator: schedule.denominator,
+        }
+    }
+}
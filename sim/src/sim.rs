@@ -0,0 +1,425 @@
+use std::{collections::BTreeMap, fmt};
+
+use gem_farm::state::{
+    FarmerReward, FixedRateConfig, FixedRateReward, FixedRateSchedule, FundsTracker, RewardType,
+    TimeTracker, VariableRateConfig, VariableRateReward,
+};
+
+use crate::scenario::{Action, Event};
+
+#[derive(Debug)]
+pub enum SimError {
+    /// same check as FixedRateReward::enroll_farmer, but with the numbers attached
+    Underfunded {
+        required: u64,
+        available: u64,
+    },
+
+    /// any other error returned by the program's reward code
+    Program(anchor_lang::error::Error),
+
+    Scenario(String),
+}
+
+impl SimError {
+    pub fn shortfall(&self) -> Option<u64> {
+        match self {
+            SimError::Underfunded {
+                required,
+                available,
+            } => Some(required - available),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for SimError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SimError::Underfunded {
+                required,
+                available,
+            } => write!(
+                f,
+                "RewardUnderfunded: enrolling needs {} reserved, only {} pending",
+                required, available
+            ),
+            SimError::Program(e) => write!(f, "{}", e),
+            SimError::Scenario(msg) => write!(f, "bad scenario: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for SimError {}
+
+impl From<anchor_lang::error::Error> for SimError {
+    fn from(e: anchor_lang::error::Error) -> Self {
+        SimError::Program(e)
+    }
+}
+
+pub type SimResult<T> = std::result::Result<T, SimError>;
+
+#[derive(Clone, Default)]
+pub struct SimFarmer {
+    pub rarity_points: u64,
+
+    pub reward: FarmerReward,
+
+    /// rarity points * seconds staked, what the apr is measured against
+    pub point_secs: u128,
+
+    last_ts: u64,
+}
+
+/// one reward of one farm, replayed using the program's own reward code
+/// the equivalent of Farm::update_rewards etc, minus the Account<> wrappers
+#[derive(Clone)]
+pub struct Simulator {
+    pub reward_type: RewardType,
+
+    /// default schedule for fixed rate fundings
+    pub schedule: Option<FixedRateSchedule>,
+
+    pub now_ts: u64,
+
+    pub times: TimeTracker,
+
+    pub funds: FundsTracker,
+
+    pub variable_rate: VariableRateReward,
+
+    pub fixed_rate: FixedRateReward,
+
+    pub rarity_points_staked: u64,
+
+    /// what actually left the pot via claims
+    pub paid_out: u64,
+
+    pub farmers: BTreeMap<String, SimFarmer>,
+}
+
+impl Simulator {
+    pub fn new(reward_type: RewardType, schedule: Option<FixedRateSchedule>) -> Self {
+        Self {
+            reward_type,
+            schedule,
+            now_ts: 0,
+            times: TimeTracker::default(),
+            funds: FundsTracker::default(),
+            variable_rate: VariableRateReward::default(),
+            fixed_rate: FixedRateReward::default(),
+            rarity_points_staked: 0,
+            paid_out: 0,
+            farmers: BTreeMap::new(),
+        }
+    }
+
+    /// what's physically left in the reward pot
+    pub fn pot_balance(&self) -> u64 {
+        self.funds.total_funded - self.funds.total_refunded - self.paid_out
+    }
+
+    /// like a transaction - if the event fails, none of its changes are kept
+    pub fn apply(&mut self, event: &Event) -> SimResult<()> {
+        if event.ts < self.now_ts {
+            return Err(SimError::Scenario(format!(
+                "events out of order ({} after {})",
+                event.ts, self.now_ts
+            )));
+        }
+
+        let mut next = self.clone();
+        next.now_ts = event.ts;
+
+        match &event.action {
+            Action::Stake {
+                farmer,
+                rarity_points,
+            } => next.stake(farmer, *rarity_points)?,
+            Action::Unstake { farmer } => next.unstake(farmer)?,
+            Action::Claim { farmer } => {
+                next.claim(farmer)?;
+            }
+            Action::Fund {
+                amount,
+                duration_sec,
+                schedule,
+            } => next.fund(*amount, *duration_sec, schedule.map(Into::into))?,
+            Action::Cancel => {
+                next.cancel()?;
+            }
+            Action::Report => {}
+        }
+
+        *self = next;
+        Ok(())
+    }
+
+    pub fn stake(&mut self, name: &str, rarity_points: u64) -> SimResult<()> {
+        self.farmers.entry(name.to_string()).or_default();
+        self.update_rewards(Some(name), true)?;
+
+        let previous_rarity_points = self.farmers[name].rarity_points;
+        self.farmers.get_mut(name).unwrap().rarity_points += rarity_points;
+        self.rarity_points_staked += rarity_points;
+
+        // fixed-rate only - same book-keeping as begin_staking / stake_extra_gems
+        if self.reward_type == RewardType::Fixed {
+            if previous_rarity_points == 0 {
+                self.enroll_farmer(name, None)?;
+            } else {
+                let farmer = self.farmers.get_mut(name).unwrap();
+                let original_begin_staking_ts = self
+                    .fixed_rate
+                    .graduate_farmer(previous_rarity_points, &mut farmer.reward)?;
+                self.enroll_farmer(name, Some(original_begin_staking_ts))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn unstake(&mut self, name: &str) -> SimResult<()> {
+        if self.farmers.get(name).map_or(0, |f| f.rarity_points) == 0 {
+            return Err(SimError::Scenario(format!("{} isn't staked", name)));
+        }
+
+        self.update_rewards(Some(name), false)?;
+
+        let farmer = self.farmers.get_mut(name).unwrap();
+        if self.reward_type == RewardType::Fixed {
+            self.fixed_rate
+                .graduate_farmer(farmer.rarity_points, &mut farmer.reward)?;
+        }
+
+        self.rarity_points_staked -= farmer.rarity_points;
+        farmer.rarity_points = 0;
+
+        Ok(())
+    }
+
+    /// returns the amount actually paid out, which is capped by the pot
+    pub fn claim(&mut self, name: &str) -> SimResult<u64> {
+        if !self.farmers.contains_key(name) {
+            return Err(SimError::Scenario(format!("unknown farmer {}", name)));
+        }
+
+        self.update_rewards(Some(name), true)?;
+
+        let pot_balance = self.pot_balance();
+        let farmer = self.farmers.get_mut(name).unwrap();
+        let amount = farmer.reward.claim_reward(pot_balance)?;
+        self.paid_out += amount;
+
+        Ok(amount)
+    }
+
+    pub fn fund(
+        &mut self,
+        amount: u64,
+        duration_sec: u64,
+        schedule: Option<FixedRateSchedule>,
+    ) -> SimResult<()> {
+        self.update_rewards(None, true)?;
+
+        match self.reward_type {
+            RewardType::Variable => self.variable_rate.fund_reward(
+                self.now_ts,
+                &mut self.times,
+                &mut self.funds,
+                VariableRateConfig {
+                    amount,
+                    duration_sec,
+                },
+            )?,
+            RewardType::Fixed => {
+                let schedule = schedule.or(self.schedule).ok_or_else(|| {
+                    SimError::Scenario("fixed rate funding without a schedule".to_string())
+                })?;
+                self.fixed_rate.fund_reward(
+                    self.now_ts,
+                    &mut self.times,
+                    &mut self.funds,
+                    FixedRateConfig {
+                        schedule,
+                        amount,
+                        duration_sec,
+                    },
+                )?
+            }
+        }
+
+        Ok(())
+    }
+
+    /// returns the refund
+    pub fn cancel(&mut self) -> SimResult<u64> {
+        self.update_rewards(None, true)?;
+
+        let refund = match self.reward_type {
+            RewardType::Variable => {
+                self.variable_rate
+                    .cancel_reward(self.now_ts, &mut self.times, &mut self.funds)?
+            }
+            RewardType::Fixed => {
+                self.fixed_rate
+                    .cancel_reward(self.now_ts, &mut self.times, &mut self.funds)?
+            }
+        };
+
+        Ok(refund)
+    }
+
+    /// mirrors Farm::update_rewards, except that re-enrolling fixed rate farmers goes through
+    /// enroll_farmer below, so that an underfunded reward reports by how much
+    pub fn update_rewards(&mut self, name: Option<&str>, reenroll: bool) -> SimResult<()> {
+        let now_ts = self.now_ts;
+
+        let farmer = match name {
+            Some(name) => {
+                let farmer = self
+                    .farmers
+                    .get_mut(name)
+                    .ok_or_else(|| SimError::Scenario(format!("unknown farmer {}", name)))?;
+                farmer.point_secs +=
+                    farmer.rarity_points as u128 * (now_ts - farmer.last_ts) as u128;
+                farmer.last_ts = now_ts;
+                Some(farmer)
+            }
+            None => None,
+        };
+
+        match self.reward_type {
+            RewardType::Variable => {
+                let (farmer_points, farmer_reward) = match farmer {
+                    Some(farmer) => (Some(farmer.rarity_points), Some(&mut farmer.reward)),
+                    None => (None, None),
+                };
+                self.variable_rate.update_accrued_reward(
+                    now_ts,
+                    &self.times,
+                    &mut self.funds,
+                    self.rarity_points_staked,
+                    farmer_points,
+                    farmer_reward,
+                )?;
+            }
+            RewardType::Fixed => {
+                // for fixed rewards we only update if a farmer has been passed
+                let farmer = match farmer {
+                    Some(farmer) => farmer,
+                    None => return Ok(()),
+                };
+
+                let graduating = farmer.reward.fixed_rate.is_staked()
+                    && farmer.reward.fixed_rate.is_time_to_graduate(now_ts)?;
+                let original_begin_staking_ts = farmer.reward.fixed_rate.begin_staking_ts;
+
+                self.fixed_rate.update_accrued_reward(
+                    now_ts,
+                    &mut self.times,
+                    &mut self.funds,
+                    farmer.rarity_points,
+                    &mut farmer.reward,
+                    false,
+                )?;
+
+                if graduating && reenroll {
+                    self.enroll_farmer(name.unwrap(), Some(original_begin_staking_ts))?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn enroll_farmer(&mut self, name: &str, original_staking_start: Option<u64>) -> SimResult<()> {
+        let now_ts = self.now_ts;
+        let farmer = self.farmers.get_mut(name).unwrap();
+
+        // same reserve calc as the program, done up front to capture the numbers
+        let remaining_duration = self.times.remaining_duration(now_ts)?;
+        let bonus_time = now_ts - original_staking_start.unwrap_or(now_ts);
+        let required = self.fixed_rate.schedule.reward_amount(
+            bonus_time,
+            remaining_duration + bonus_time,
+            farmer.rarity_points,
+        )?;
+        let available = self.funds.pending_amount()?;
+        if required > available {
+            return Err(SimError::Underfunded {
+                required,
+                available,
+            });
+        }
+
+        self.fixed_rate.enroll_farmer(
+            now_ts,
+            &mut self.times,
+            &mut self.funds,
+            farmer.rarity_points,
+            &mut farmer.reward,
+            original_staking_start,
+        )?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixed_sim() -> Simulator {
+        let schedule = FixedRateSchedule {
+            base_rate: 3,
+            ..Default::default()
+        };
+        let mut sim = Simulator::new(RewardType::Fixed, Some(schedule));
+        sim.fund(1000, 100, None).unwrap();
+        sim
+    }
+
+    #[test]
+    fn test_underfunded_stake_rolls_back() {
+        let mut sim = fixed_sim();
+
+        // 3 * 100s * 3 points = 900 reserved
+        sim.apply(&Event {
+            ts: 0,
+            action: Action::Stake {
+                farmer: "a".to_string(),
+                rarity_points: 3,
+            },
+        })
+        .unwrap();
+        assert_eq!(sim.fixed_rate.reserved_amount, 900);
+
+        // pending is still 1000, the program doesn't net off what's already reserved
+        let err = sim
+            .apply(&Event {
+                ts: 0,
+                action: Action::Stake {
+                    farmer: "b".to_string(),
+                    rarity_points: 4,
+                },
+            })
+            .unwrap_err();
+        assert_eq!(err.shortfall(), Some(200));
+        assert_eq!(sim.rarity_points_staked, 3);
+        assert!(!sim.farmers.contains_key("b"));
+    }
+
+    #[test]
+    fn test_claim_capped_by_pot() {
+        let mut sim = fixed_sim();
+        sim.now_ts = 0;
+        sim.stake("a", 3).unwrap();
+
+        sim.now_ts = 50;
+        assert_eq!(sim.claim("a").unwrap(), 450);
+        assert_eq!(sim.pot_balance(), 550);
+        assert_eq!(sim.fixed_rate.reserved_amount, 450);
+    }
+}
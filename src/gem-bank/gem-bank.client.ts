@@ -220,7 +220,7 @@ export class GemBankClient extends AccountUtils {
     const signers = [];
     if (isKp(bankManager)) signers.push(<Keypair>bankManager);
 
-    console.log('updating bank manager to', newManager.toBase58());
+    console.log('proposing new bank manager', newManager.toBase58());
     const txSig = await this.bankProgram.methods
       .updateBankManager(newManager)
       .accounts({
@@ -235,6 +235,47 @@ export class GemBankClient extends AccountUtils {
     return { txSig };
   }
 
+  async acceptBankManager(bank: PublicKey, newManager: PublicKey | Keypair) {
+    const signers = [];
+    if (isKp(newManager)) signers.push(<Keypair>newManager);
+
+    console.log('accepting bank manager role');
+    const txSig = await this.bankProgram.methods
+      .acceptBankManager()
+      .accounts({
+        bank,
+        newBankManager: isKp(newManager)
+          ? (<Keypair>newManager).publicKey
+          : newManager,
+      })
+      .signers(signers)
+      .rpc();
+
+    return { txSig };
+  }
+
+  async cancelBankManagerTransfer(
+    bank: PublicKey,
+    bankManager: PublicKey | Keypair
+  ) {
+    const signers = [];
+    if (isKp(bankManager)) signers.push(<Keypair>bankManager);
+
+    console.log('cancelling bank manager transfer');
+    const txSig = await this.bankProgram.methods
+      .cancelBankManagerTransfer()
+      .accounts({
+        bank,
+        bankManager: isKp(bankManager)
+          ? (<Keypair>bankManager).publicKey
+          : bankManager,
+      })
+      .signers(signers)
+      .rpc();
+
+    return { txSig };
+  }
+
   async initVault(
     bank: PublicKey,
     creator: PublicKey | Keypair,
@@ -278,7 +319,7 @@ export class GemBankClient extends AccountUtils {
     const signers = [];
     if (isKp(existingOwner)) signers.push(<Keypair>existingOwner);
 
-    console.log('updating vault owner to', newOwner.toBase58());
+    console.log('proposing new vault owner', newOwner.toBase58());
     const txSig = await this.bankProgram.methods
       .updateVaultOwner(newOwner)
       .accounts({
@@ -294,6 +335,50 @@ export class GemBankClient extends AccountUtils {
     return { txSig };
   }
 
+  async acceptVaultOwner(
+    bank: PublicKey,
+    vault: PublicKey,
+    newOwner: Keypair | PublicKey
+  ) {
+    const signers = [];
+    if (isKp(newOwner)) signers.push(<Keypair>newOwner);
+
+    console.log('accepting vault ownership');
+    const txSig = await this.bankProgram.methods
+      .acceptVaultOwner()
+      .accounts({
+        bank,
+        vault,
+        newOwner: isKp(newOwner) ? (<Keypair>newOwner).publicKey : newOwner,
+      })
+      .signers(signers)
+      .rpc();
+
+    return { txSig };
+  }
+
+  async cancelVaultOwnerTransfer(
+    vault: PublicKey,
+    existingOwner: Keypair | PublicKey
+  ) {
+    const signers = [];
+    if (isKp(existingOwner)) signers.push(<Keypair>existingOwner);
+
+    console.log('cancelling vault owner transfer');
+    const txSig = await this.bankProgram.methods
+      .cancelVaultOwnerTransfer()
+      .accounts({
+        vault,
+        owner: isKp(existingOwner)
+          ? (<Keypair>existingOwner).publicKey
+          : existingOwner,
+      })
+      .signers(signers)
+      .rpc();
+
+    return { txSig };
+  }
+
   async setVaultLock(
     bank: PublicKey,
     vault: PublicKey,
@@ -30,15 +30,26 @@ import {
   findFarmAuthorityPDA,
   findFarmerPDA,
   findFarmTreasuryPDA,
+  findFeeExemptionPDA,
+  findMigrationProofPDA,
+  findPendingFarmConfigPDA,
+  findProtocolConfigPDA,
   findRewardsPotPDA,
+  findTokenTreasuryPDA,
 } from './gem-farm.pda';
 import { PROGRAM_ID as AUTH_PROG_ID } from '@metaplex-foundation/mpl-token-auth-rules/dist/src/generated';
 import { PROGRAM_ID as TMETA_PROG_ID } from '@metaplex-foundation/mpl-token-metadata/dist/src/generated';
 
+//where protocol fees went before the ProtocolConfig account
+//(still the fee wallet the test validator's config is preloaded with)
 export const feeAccount = new PublicKey(
   '2xhBxVVuXkdq2MRKerE9mr2s1szfHSedy21MVqf8gPoM'
 );
 
+export const BPF_LOADER_UPGRADEABLE_PROGRAM_ID = new PublicKey(
+  'BPFLoaderUpgradeab1e11111111111111111111111'
+);
+
 //acts as an enum
 export const RewardType = {
   Variable: { variable: {} },
@@ -57,6 +68,28 @@ export interface MaxCounts {
   maxRarityPoints: number;
 }
 
+//replaces the funder's previous limits, including what's left of previous caps
+export interface FundingLimits {
+  expiryTs: BN; //0 = never expires
+  capA: BN | null; //cumulative, null = uncapped
+  capB: BN | null;
+  rewardType: any | null; //RewardType instance, null = both
+}
+
+export const noFundingLimits = <FundingLimits>{
+  expiryTs: new BN(0),
+  capA: null,
+  capB: null,
+  rewardType: null,
+};
+
+//default feeMint = no token fees
+export interface TokenFees {
+  feeMint: PublicKey;
+  stakeFee: BN;
+  unstakeFee: BN;
+}
+
 export interface TierConfig {
   rewardRate: BN;
   requiredTenure: BN;
@@ -81,11 +114,28 @@ export interface VariableRateConfig {
   durationSec: BN;
 }
 
+export interface FeeTable {
+  initFarmLamp: BN;
+  initFarmerLamp: BN;
+  stakeLamp: BN;
+  unstakeLamp: BN;
+  flashDepositLamp: BN;
+  flashWithdrawLamp: BN;
+  instantUnstakeLamp: BN;
+}
+
 export interface RarityConfig {
   mint: PublicKey;
   rarityPoints: number;
 }
 
+//the farm's token fee, only needed if the farm charges one for the ix
+export interface TokenFeeAccs {
+  feeMint: PublicKey;
+  //payer's token account for feeMint
+  feeSource: PublicKey;
+}
+
 export class GemFarmClient extends GemBankClient {
   farmProgram!: anchor.Program<GemFarm>;
 
@@ -116,6 +166,18 @@ export class GemFarmClient extends GemBankClient {
     }
   }
 
+  //in the order collect_token_fee expects them, always last in the remaining accounts
+  async tokenFeeRemainingAccounts(farm: PublicKey, tokenFee?: TokenFeeAccs) {
+    if (!tokenFee) return [];
+
+    const [tokenTreasury] = await findTokenTreasuryPDA(farm, tokenFee.feeMint);
+    return [
+      { pubkey: tokenFee.feeSource, isWritable: true, isSigner: false },
+      { pubkey: tokenTreasury, isWritable: true, isSigner: false },
+      { pubkey: TOKEN_PROGRAM_ID, isWritable: false, isSigner: false },
+    ];
+  }
+
   // --------------------------------------- fetch deserialized accounts
 
   async fetchFarmAcc(farm: PublicKey) {
@@ -132,6 +194,24 @@ export class GemFarmClient extends GemBankClient {
     );
   }
 
+  async fetchProtocolConfigAcc() {
+    const [protocolConfig] = await findProtocolConfigPDA();
+    return this.farmProgram.account.protocolConfig.fetch(protocolConfig);
+  }
+
+  //every fee-charging ix needs all 3, fee wallet is checked against the config
+  //and the exemption PDA only exists if the farm doesn't pay protocol fees
+  async findProtocolFeeAccs(farm: PublicKey) {
+    const [protocolConfig] = await findProtocolConfigPDA();
+    const protocolConfigAcc = await this.fetchProtocolConfigAcc();
+    const [feeExemption] = await findFeeExemptionPDA(farm);
+    return {
+      protocolConfig,
+      feeAcc: protocolConfigAcc.feeWallet,
+      feeExemption,
+    };
+  }
+
   async fetchTokenAcc(rewardMint: PublicKey, rewardAcc: PublicKey) {
     return this.deserializeTokenAccount(rewardMint, rewardAcc);
   }
@@ -233,6 +313,9 @@ export class GemFarmClient extends GemBankClient {
       rewardBMint
     );
 
+    const { protocolConfig, feeAcc, feeExemption } =
+      await this.findProtocolFeeAccs(farm.publicKey);
+
     const signers = [farm, bank];
     if (isKp(farmManager)) signers.push(<Keypair>farmManager);
 
@@ -255,7 +338,9 @@ export class GemFarmClient extends GemBankClient {
           : farmManager,
         farmAuthority: farmAuth,
         payer: isKp(payer) ? (<Keypair>payer).publicKey : farmManager,
-        feeAcc: feeAccount,
+        protocolConfig,
+        feeAcc,
+        feeExemption,
         rewardAPot,
         rewardAMint,
         rewardBPot,
@@ -282,19 +367,20 @@ export class GemFarmClient extends GemBankClient {
     };
   }
 
+  //config, max counts and fees go through proposeFarmConfig instead
   async updateFarm(
     farm: PublicKey,
     farmManager: PublicKey | Keypair,
-    config: FarmConfig | null = null,
     newManager: PublicKey | null = null,
-    maxCounts?: MaxCounts
+    crankTipBps: number | null = null,
+    configDelaySec: BN | null = null
   ) {
     const signers = [];
     if (isKp(farmManager)) signers.push(<Keypair>farmManager);
 
     console.log('updating farm');
     const txSig = await this.farmProgram.methods
-      .updateFarm(config, newManager, maxCounts ?? null)
+      .updateFarm(newManager, crankTipBps, configDelaySec)
       .accounts({
         farm,
         farmManager: isKp(farmManager)
@@ -307,6 +393,136 @@ export class GemFarmClient extends GemBankClient {
     return { txSig };
   }
 
+  async acceptFarmManager(farm: PublicKey, newManager: PublicKey | Keypair) {
+    const signers = [];
+    if (isKp(newManager)) signers.push(<Keypair>newManager);
+
+    console.log('accepting farm manager role');
+    const txSig = await this.farmProgram.methods
+      .acceptFarmManager()
+      .accounts({
+        farm,
+        newFarmManager: isKp(newManager)
+          ? (<Keypair>newManager).publicKey
+          : newManager,
+      })
+      .signers(signers)
+      .rpc();
+
+    return { txSig };
+  }
+
+  //whatever's left null is carried over from the farm as is
+  async proposeFarmConfig(
+    farm: PublicKey,
+    farmManager: PublicKey | Keypair,
+    config: FarmConfig | null = null,
+    maxCounts: MaxCounts | null = null,
+    tokenFees: TokenFees | null = null,
+    instantUnstakeFeeLampPerSec: BN | null = null,
+    configDelaySec: BN | null = null
+  ) {
+    const [pendingFarmConfig] = await findPendingFarmConfigPDA(farm);
+
+    const signers = [];
+    if (isKp(farmManager)) signers.push(<Keypair>farmManager);
+
+    console.log('proposing farm config');
+    const txSig = await this.farmProgram.methods
+      .proposeFarmConfig(
+        config,
+        maxCounts,
+        tokenFees,
+        instantUnstakeFeeLampPerSec,
+        configDelaySec
+      )
+      .accounts({
+        farm,
+        farmManager: isKp(farmManager)
+          ? (<Keypair>farmManager).publicKey
+          : farmManager,
+        pendingFarmConfig,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers(signers)
+      .rpc();
+
+    return { pendingFarmConfig, txSig };
+  }
+
+  //permissionless once the farm's config delay has passed
+  async applyFarmConfig(farm: PublicKey) {
+    const farmAcc = await this.fetchFarmAcc(farm);
+    const [pendingFarmConfig, pendingFarmConfigBump] =
+      await findPendingFarmConfigPDA(farm);
+
+    console.log('applying farm config');
+    const txSig = await this.farmProgram.methods
+      .applyFarmConfig(pendingFarmConfigBump)
+      .accounts({
+        farm,
+        farmManager: farmAcc.farmManager,
+        pendingFarmConfig,
+      })
+      .rpc();
+
+    return { pendingFarmConfig, txSig };
+  }
+
+  async cancelFarmConfig(farm: PublicKey, farmManager: PublicKey | Keypair) {
+    const [pendingFarmConfig, pendingFarmConfigBump] =
+      await findPendingFarmConfigPDA(farm);
+
+    const signers = [];
+    if (isKp(farmManager)) signers.push(<Keypair>farmManager);
+
+    console.log('cancelling farm config');
+    const txSig = await this.farmProgram.methods
+      .cancelFarmConfig(pendingFarmConfigBump)
+      .accounts({
+        farm,
+        farmManager: isKp(farmManager)
+          ? (<Keypair>farmManager).publicKey
+          : farmManager,
+        pendingFarmConfig,
+      })
+      .signers(signers)
+      .rpc();
+
+    return { pendingFarmConfig, txSig };
+  }
+
+  //permissionless, token fees can't be collected until it exists
+  async initTokenTreasury(
+    farm: PublicKey,
+    feeMint: PublicKey,
+    payer: PublicKey | Keypair
+  ) {
+    const [farmAuth, farmAuthBump] = await findFarmAuthorityPDA(farm);
+    const [tokenTreasury] = await findTokenTreasuryPDA(farm, feeMint);
+
+    const signers = [];
+    if (isKp(payer)) signers.push(<Keypair>payer);
+
+    console.log('initializing token treasury for', feeMint.toBase58());
+    const txSig = await this.farmProgram.methods
+      .initTokenTreasury(farmAuthBump)
+      .accounts({
+        farm,
+        farmAuthority: farmAuth,
+        tokenTreasury,
+        feeMint,
+        payer: isKp(payer) ? (<Keypair>payer).publicKey : payer,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers(signers)
+      .rpc();
+
+    return { tokenTreasury, txSig };
+  }
+
   async payoutFromTreasury(
     farm: PublicKey,
     farmManager: PublicKey | Keypair,
@@ -474,6 +690,8 @@ export class GemFarmClient extends GemBankClient {
     const [farmer, farmerBump] = await findFarmerPDA(farm, identityPk);
     const [vault, vaultBump] = await findVaultPDA(farmAcc.bank, identityPk);
     const [vaultAuth, vaultAuthBump] = await findVaultAuthorityPDA(vault); //nice-to-have
+    const { protocolConfig, feeAcc, feeExemption } =
+      await this.findProtocolFeeAccs(farm);
 
     const signers = [];
     if (isKp(farmerIdentity)) signers.push(<Keypair>farmerIdentity);
@@ -487,7 +705,9 @@ export class GemFarmClient extends GemBankClient {
         farmer,
         identity: identityPk,
         payer: isKp(payer) ? (<Keypair>payer).publicKey : payer,
-        feeAcc: feeAccount,
+        protocolConfig,
+        feeAcc,
+        feeExemption,
         bank: farmAcc.bank,
         vault,
         gemBank: this.bankProgram.programId,
@@ -506,23 +726,30 @@ export class GemFarmClient extends GemBankClient {
     };
   }
 
+  //tokenFee = the farm's stake/unstake fee, if it charges one
   async stakeCommon(
     farm: PublicKey,
     farmerIdentity: PublicKey | Keypair,
     unstake = false,
-    skipRewards = false
+    skipRewards = false,
+    tokenFee?: TokenFeeAccs
   ) {
     const {
       farmer,
       farmerBump,
       vault,
-      vaultBump,
       farmAuth,
       farmAuthBump,
       farmTreasury,
       farmTreasuryBump,
       builder,
-    } = await this.buildStakeCommon(farm, farmerIdentity, unstake, skipRewards);
+    } = await this.buildStakeCommon(
+      farm,
+      farmerIdentity,
+      unstake,
+      skipRewards,
+      tokenFee
+    );
 
     const txSig = await builder.rpc();
 
@@ -530,7 +757,6 @@ export class GemFarmClient extends GemBankClient {
       farmer,
       farmerBump,
       vault,
-      vaultBump,
       farmAuth,
       farmAuthBump,
       farmTreasury,
@@ -543,7 +769,8 @@ export class GemFarmClient extends GemBankClient {
     farm: PublicKey,
     farmerIdentity: PublicKey | Keypair,
     unstake = false,
-    skipRewards = false
+    skipRewards = false,
+    tokenFee?: TokenFeeAccs
   ) {
     const identityPk = isKp(farmerIdentity)
       ? (<Keypair>farmerIdentity).publicKey
@@ -552,9 +779,17 @@ export class GemFarmClient extends GemBankClient {
     const farmAcc = await this.fetchFarmAcc(farm);
 
     const [farmer, farmerBump] = await findFarmerPDA(farm, identityPk);
-    const [vault, vaultBump] = await findVaultPDA(farmAcc.bank, identityPk);
+    //not derived - a migrated farmer keeps the vault from its first bank
+    const { vault } = await this.fetchFarmerAcc(farmer);
     const [farmAuth, farmAuthBump] = await findFarmAuthorityPDA(farm);
     const [farmTreasury, farmTreasuryBump] = await findFarmTreasuryPDA(farm);
+    const { protocolConfig, feeAcc, feeExemption } =
+      await this.findProtocolFeeAccs(farm);
+
+    const remainingAccounts = await this.tokenFeeRemainingAccounts(
+      farm,
+      tokenFee
+    );
 
     const signers = [];
     if (isKp(farmerIdentity)) signers.push(<Keypair>farmerIdentity);
@@ -572,8 +807,11 @@ export class GemFarmClient extends GemBankClient {
             farmAuthority: farmAuth,
             gemBank: this.bankProgram.programId,
             systemProgram: SystemProgram.programId,
-            feeAcc: feeAccount,
+            protocolConfig,
+            feeAcc,
+            feeExemption,
           })
+          .remainingAccounts(remainingAccounts)
           .signers(signers)
       : await this.farmProgram.methods
           .stake(farmAuthBump, farmerBump)
@@ -585,16 +823,18 @@ export class GemFarmClient extends GemBankClient {
             vault,
             farmAuthority: farmAuth,
             gemBank: this.bankProgram.programId,
-            feeAcc: feeAccount,
+            protocolConfig,
+            feeAcc,
+            feeExemption,
             systemProgram: SystemProgram.programId,
           })
+          .remainingAccounts(remainingAccounts)
           .signers(signers);
 
     return {
       farmer,
       farmerBump,
       vault,
-      vaultBump,
       farmAuth,
       farmAuthBump,
       farmTreasury,
@@ -603,16 +843,21 @@ export class GemFarmClient extends GemBankClient {
     };
   }
 
-  async stake(farm: PublicKey, farmerIdentity: PublicKey | Keypair) {
-    return this.stakeCommon(farm, farmerIdentity, false);
+  async stake(
+    farm: PublicKey,
+    farmerIdentity: PublicKey | Keypair,
+    tokenFee?: TokenFeeAccs
+  ) {
+    return this.stakeCommon(farm, farmerIdentity, false, false, tokenFee);
   }
 
   async unstake(
     farm: PublicKey,
     farmerIdentity: PublicKey | Keypair,
-    skipRewards = false
+    skipRewards = false,
+    tokenFee?: TokenFeeAccs
   ) {
-    return this.stakeCommon(farm, farmerIdentity, true, skipRewards);
+    return this.stakeCommon(farm, farmerIdentity, true, skipRewards, tokenFee);
   }
 
   async claim(
@@ -681,7 +926,8 @@ export class GemFarmClient extends GemBankClient {
     gemSource: PublicKey,
     mintProof?: PublicKey,
     metadata?: PublicKey,
-    creatorProof?: PublicKey
+    creatorProof?: PublicKey,
+    tokenFee?: TokenFeeAccs
   ) {
     const identityPk = isKp(farmerIdentity)
       ? (<Keypair>farmerIdentity).publicKey
@@ -690,7 +936,8 @@ export class GemFarmClient extends GemBankClient {
     const farmAcc = await this.fetchFarmAcc(farm);
 
     const [farmer, farmerBump] = await findFarmerPDA(farm, identityPk);
-    const [vault, vaultBump] = await findVaultPDA(farmAcc.bank, identityPk);
+    //not derived - a migrated farmer keeps the vault from its first bank
+    const { vault } = await this.fetchFarmerAcc(farmer);
     const [farmAuth, farmAuthBump] = await findFarmAuthorityPDA(farm);
 
     const [gemBox, gemBoxBump] = await findGemBoxPDA(vault, gemMint);
@@ -700,6 +947,8 @@ export class GemFarmClient extends GemBankClient {
       farmAcc.bank,
       gemMint
     );
+    const { protocolConfig, feeAcc, feeExemption } =
+      await this.findProtocolFeeAccs(farm);
 
     const remainingAccounts = [];
     if (mintProof)
@@ -720,6 +969,10 @@ export class GemFarmClient extends GemBankClient {
         isWritable: false,
         isSigner: false,
       });
+    //only charged when the flash deposit begins staking, always goes last
+    remainingAccounts.push(
+      ...(await this.tokenFeeRemainingAccounts(farm, tokenFee))
+    );
 
     const signers: Keypair[] = [];
     if (isKp(farmerIdentity)) signers.push(<Keypair>farmerIdentity);
@@ -748,7 +1001,9 @@ export class GemFarmClient extends GemBankClient {
           systemProgram: SystemProgram.programId,
           rent: anchor.web3.SYSVAR_RENT_PUBKEY,
           gemBank: this.bankProgram.programId,
-          feeAcc: feeAccount,
+          protocolConfig,
+          feeAcc,
+          feeExemption,
         },
         remainingAccounts,
       }
@@ -774,7 +1029,6 @@ export class GemFarmClient extends GemBankClient {
       farmer,
       farmerBump,
       vault,
-      vaultBump,
       farmAuth,
       farmAuthBump,
       gemBox,
@@ -794,7 +1048,8 @@ export class GemFarmClient extends GemBankClient {
     gemMint: PublicKey,
     gemSource: PublicKey,
     mintProof?: PublicKey,
-    creatorProof?: PublicKey
+    creatorProof?: PublicKey,
+    tokenFee?: TokenFeeAccs
   ) {
     const identityPk = isKp(farmerIdentity)
       ? (<Keypair>farmerIdentity).publicKey
@@ -803,7 +1058,8 @@ export class GemFarmClient extends GemBankClient {
     const farmAcc = await this.fetchFarmAcc(farm);
 
     const [farmer, farmerBump] = await findFarmerPDA(farm, identityPk);
-    const [vault, vaultBump] = await findVaultPDA(farmAcc.bank, identityPk);
+    //not derived - a migrated farmer keeps the vault from its first bank
+    const { vault } = await this.fetchFarmerAcc(farmer);
     const [farmAuth, farmAuthBump] = await findFarmAuthorityPDA(farm);
 
     const [gemBox, gemBoxBump] = await findGemBoxPDA(vault, gemMint);
@@ -813,6 +1069,8 @@ export class GemFarmClient extends GemBankClient {
       farmAcc.bank,
       gemMint
     );
+    const { protocolConfig, feeAcc, feeExemption } =
+      await this.findProtocolFeeAccs(farm);
 
     //pnft
     const {
@@ -850,6 +1108,10 @@ export class GemFarmClient extends GemBankClient {
         isWritable: false,
         isSigner: false,
       });
+    //only charged when the flash deposit begins staking, always goes last
+    remainingAccounts.push(
+      ...(await this.tokenFeeRemainingAccounts(farm, tokenFee))
+    );
 
     const signers: Keypair[] = [];
     if (isKp(farmerIdentity)) signers.push(<Keypair>farmerIdentity);
@@ -879,7 +1141,9 @@ export class GemFarmClient extends GemBankClient {
           systemProgram: SystemProgram.programId,
           rent: anchor.web3.SYSVAR_RENT_PUBKEY,
           gemBank: this.bankProgram.programId,
-          feeAcc: feeAccount,
+          protocolConfig,
+          feeAcc,
+          feeExemption,
           gemMetadata: meta,
           gemEdition: nftEditionPda,
           ownerTokenRecord: ownerTokenRecordPda,
@@ -913,7 +1177,6 @@ export class GemFarmClient extends GemBankClient {
       farmer,
       farmerBump,
       vault,
-      vaultBump,
       farmAuth,
       farmAuthBump,
       gemBox,
@@ -976,13 +1239,183 @@ export class GemFarmClient extends GemBankClient {
     };
   }
 
+  async closeFarmer(farm: PublicKey, farmerIdentity: PublicKey | Keypair) {
+    const identityPk = isKp(farmerIdentity)
+      ? (<Keypair>farmerIdentity).publicKey
+      : <PublicKey>farmerIdentity;
+
+    const farmAcc = await this.fetchFarmAcc(farm);
+
+    const [farmer, farmerBump] = await findFarmerPDA(farm, identityPk);
+    const farmerAcc = await this.fetchFarmerAcc(farmer);
+
+    const signers = [];
+    if (isKp(farmerIdentity)) signers.push(<Keypair>farmerIdentity);
+
+    console.log('closing farmer', identityPk.toBase58());
+    const txSig = await this.farmProgram.methods
+      .closeFarmer(farmerBump)
+      .accounts({
+        farm,
+        farmer,
+        identity: identityPk,
+        bank: farmAcc.bank,
+        vault: farmerAcc.vault,
+        gemBank: this.bankProgram.programId,
+      })
+      .signers(signers)
+      .rpc();
+
+    return { farmer, farmerBump, txSig };
+  }
+
+  //gem mints = every mint in the vault, rarities are read from the new bank
+  //tokenFee = the stake fee of the farm being joined, paid by the authority
+  async buildMigrateFarmer(
+    fromFarm: PublicKey,
+    toFarm: PublicKey,
+    farmerIdentity: PublicKey,
+    authority: PublicKey | Keypair,
+    gemMints: PublicKey[],
+    tokenFee?: TokenFeeAccs
+  ) {
+    const fromFarmAcc = await this.fetchFarmAcc(fromFarm);
+    const toFarmAcc = await this.fetchFarmAcc(toFarm);
+
+    const [fromFarmAuth, fromFarmAuthBump] = await findFarmAuthorityPDA(
+      fromFarm
+    );
+    const [toFarmAuth, toFarmAuthBump] = await findFarmAuthorityPDA(toFarm);
+    const [fromFarmer, fromFarmerBump] = await findFarmerPDA(
+      fromFarm,
+      farmerIdentity
+    );
+    const [toFarmer, toFarmerBump] = await findFarmerPDA(
+      toFarm,
+      farmerIdentity
+    );
+    const [migrationProof, migrationProofBump] = await findMigrationProofPDA(
+      fromFarm,
+      toFarm
+    );
+    //not derived - a farmer that migrated before keeps the vault from its first bank
+    const { vault } = await this.fetchFarmerAcc(fromFarmer);
+    const { protocolConfig, feeAcc, feeExemption } =
+      await this.findProtocolFeeAccs(toFarm);
+
+    const remainingAccounts = [];
+    for (const gemMint of gemMints) {
+      const [GDR] = await findGdrPDA(vault, gemMint);
+      const [gemRarity] = await findRarityPDA(toFarmAcc.bank, gemMint);
+      remainingAccounts.push(
+        { pubkey: GDR, isWritable: false, isSigner: false },
+        { pubkey: gemRarity, isWritable: false, isSigner: false }
+      );
+    }
+    remainingAccounts.push(
+      ...(await this.tokenFeeRemainingAccounts(toFarm, tokenFee))
+    );
+
+    const signers = [];
+    if (isKp(authority)) signers.push(<Keypair>authority);
+
+    const builder = this.farmProgram.methods
+      .migrateFarmer(
+        fromFarmAuthBump,
+        toFarmAuthBump,
+        fromFarmerBump,
+        migrationProofBump
+      )
+      .accounts({
+        fromFarm,
+        fromFarmAuthority: fromFarmAuth,
+        fromFarmer,
+        toFarm,
+        toFarmAuthority: toFarmAuth,
+        toFarmer,
+        migrationProof,
+        identity: farmerIdentity,
+        authority: isKp(authority) ? (<Keypair>authority).publicKey : authority,
+        fromBank: fromFarmAcc.bank,
+        toBank: toFarmAcc.bank,
+        vault,
+        gemBank: this.bankProgram.programId,
+        protocolConfig,
+        feeAcc,
+        feeExemption,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(remainingAccounts)
+      .signers(signers);
+
+    return { fromFarmer, toFarmer, toFarmerBump, vault, builder };
+  }
+
+  async migrateFarmer(
+    fromFarm: PublicKey,
+    toFarm: PublicKey,
+    farmerIdentity: PublicKey,
+    authority: PublicKey | Keypair,
+    gemMints: PublicKey[],
+    tokenFee?: TokenFeeAccs
+  ) {
+    const { fromFarmer, toFarmer, toFarmerBump, vault, builder } =
+      await this.buildMigrateFarmer(
+        fromFarm,
+        toFarm,
+        farmerIdentity,
+        authority,
+        gemMints,
+        tokenFee
+      );
+
+    console.log('migrating farmer', farmerIdentity.toBase58());
+    const txSig = await builder.rpc();
+
+    return { fromFarmer, toFarmer, toFarmerBump, vault, txSig };
+  }
+
+  // --------------------------------------- migration ops ixs
+
+  //both farm managers have to sign off
+  async authorizeMigration(
+    fromFarm: PublicKey,
+    fromFarmManager: Keypair,
+    toFarm: PublicKey,
+    toFarmManager: Keypair
+  ) {
+    const [migrationProof, migrationProofBump] = await findMigrationProofPDA(
+      fromFarm,
+      toFarm
+    );
+
+    console.log(
+      `authorizing migration ${fromFarm.toBase58()} -> ${toFarm.toBase58()}`
+    );
+    const txSig = await this.farmProgram.methods
+      .authorizeMigration()
+      .accounts({
+        fromFarm,
+        fromFarmManager: fromFarmManager.publicKey,
+        toFarm,
+        toFarmManager: toFarmManager.publicKey,
+        migrationProof,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([fromFarmManager, toFarmManager])
+      .rpc();
+
+    return { migrationProof, migrationProofBump, txSig };
+  }
+
   // --------------------------------------- funder ops ixs
 
   async authorizeCommon(
     farm: PublicKey,
     farmManager: PublicKey | Keypair,
     funder: PublicKey,
-    deauthorize = false
+    deauthorize = false,
+    limits: FundingLimits = noFundingLimits
   ) {
     const [authorizationProof, authorizationProofBump] =
       await findAuthorizationProofPDA(farm, funder);
@@ -1009,7 +1442,7 @@ export class GemFarmClient extends GemBankClient {
     } else {
       console.log('authorizing funder', funder.toBase58());
       txSig = await this.farmProgram.methods
-        .authorizeFunder()
+        .authorizeFunder(limits)
         .accounts({
           farm,
           farmManager: isKp(farmManager)
@@ -1029,9 +1462,16 @@ export class GemFarmClient extends GemBankClient {
   async authorizeFunder(
     farm: PublicKey,
     farmManager: PublicKey | Keypair,
-    funderToAuthorize: PublicKey
+    funderToAuthorize: PublicKey,
+    limits: FundingLimits = noFundingLimits
   ) {
-    return this.authorizeCommon(farm, farmManager, funderToAuthorize, false);
+    return this.authorizeCommon(
+      farm,
+      farmManager,
+      funderToAuthorize,
+      false,
+      limits
+    );
   }
 
   async deauthorizeFunder(
@@ -1223,6 +1663,111 @@ export class GemFarmClient extends GemBankClient {
     };
   }
 
+  // --------------------------------------- protocol ixs
+
+  //signed by the program's upgrade authority, who becomes the config's admin
+  async initProtocolConfig(
+    admin: PublicKey | Keypair,
+    feeWallet: PublicKey,
+    fees: FeeTable
+  ) {
+    const adminPk = isKp(admin) ? (<Keypair>admin).publicKey : <PublicKey>admin;
+
+    const [protocolConfig, protocolConfigBump] = await findProtocolConfigPDA();
+    const [programData] = await PublicKey.findProgramAddress(
+      [this.farmProgram.programId.toBytes()],
+      BPF_LOADER_UPGRADEABLE_PROGRAM_ID
+    );
+
+    const signers = [];
+    if (isKp(admin)) signers.push(<Keypair>admin);
+
+    console.log('initializing protocol config, fees to', feeWallet.toBase58());
+    const txSig = await this.farmProgram.methods
+      .initProtocolConfig(feeWallet, fees)
+      .accounts({
+        protocolConfig,
+        admin: adminPk,
+        program: this.farmProgram.programId,
+        programData,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers(signers)
+      .rpc();
+
+    return { protocolConfig, protocolConfigBump, txSig };
+  }
+
+  async updateProtocolConfig(
+    admin: PublicKey | Keypair,
+    newAdmin: PublicKey | null = null,
+    feeWallet: PublicKey | null = null,
+    fees: FeeTable | null = null
+  ) {
+    const [protocolConfig] = await findProtocolConfigPDA();
+
+    const signers = [];
+    if (isKp(admin)) signers.push(<Keypair>admin);
+
+    console.log('updating protocol config');
+    const txSig = await this.farmProgram.methods
+      .updateProtocolConfig(newAdmin, feeWallet, fees)
+      .accounts({
+        protocolConfig,
+        admin: isKp(admin) ? (<Keypair>admin).publicKey : admin,
+      })
+      .signers(signers)
+      .rpc();
+
+    return { protocolConfig, txSig };
+  }
+
+  async addFeeExemption(admin: PublicKey | Keypair, farm: PublicKey) {
+    const [protocolConfig] = await findProtocolConfigPDA();
+    const [feeExemption] = await findFeeExemptionPDA(farm);
+
+    const signers = [];
+    if (isKp(admin)) signers.push(<Keypair>admin);
+
+    console.log(`adding fee exemption for ${farm.toBase58()}`);
+    const txSig = await this.farmProgram.methods
+      .addFeeExemption()
+      .accounts({
+        protocolConfig,
+        admin: isKp(admin) ? (<Keypair>admin).publicKey : admin,
+        farm,
+        feeExemption,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers(signers)
+      .rpc();
+
+    return { protocolConfig, feeExemption, txSig };
+  }
+
+  async removeFeeExemption(admin: PublicKey | Keypair, farm: PublicKey) {
+    const [protocolConfig] = await findProtocolConfigPDA();
+    const [feeExemption, feeExemptionBump] = await findFeeExemptionPDA(farm);
+
+    const signers = [];
+    if (isKp(admin)) signers.push(<Keypair>admin);
+
+    console.log(`removing fee exemption for ${farm.toBase58()}`);
+    const txSig = await this.farmProgram.methods
+      .removeFeeExemption(feeExemptionBump)
+      .accounts({
+        protocolConfig,
+        admin: isKp(admin) ? (<Keypair>admin).publicKey : admin,
+        farm,
+        feeExemption,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers(signers)
+      .rpc();
+
+    return { protocolConfig, feeExemption, txSig };
+  }
+
   // --------------------------------------- helpers
 
   //returns "variable" or "fixed"
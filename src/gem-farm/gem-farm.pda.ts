@@ -35,3 +35,41 @@ export const findRewardsPotPDA = (farm: PublicKey, rewardMint: PublicKey) => {
     GEM_FARM_PROG_ID
   );
 };
+
+export const findProtocolConfigPDA = () => {
+  return PublicKey.findProgramAddress(
+    [Buffer.from('protocol_config')],
+    GEM_FARM_PROG_ID
+  );
+};
+
+export const findFeeExemptionPDA = (farm: PublicKey) => {
+  return PublicKey.findProgramAddress(
+    [Buffer.from('fee_exemption'), farm.toBytes()],
+    GEM_FARM_PROG_ID
+  );
+};
+
+export const findMigrationProofPDA = (
+  fromFarm: PublicKey,
+  toFarm: PublicKey
+) => {
+  return PublicKey.findProgramAddress(
+    [Buffer.from('migration'), fromFarm.toBytes(), toFarm.toBytes()],
+    GEM_FARM_PROG_ID
+  );
+};
+
+export const findPendingFarmConfigPDA = (farm: PublicKey) => {
+  return PublicKey.findProgramAddress(
+    [Buffer.from('pending_config'), farm.toBytes()],
+    GEM_FARM_PROG_ID
+  );
+};
+
+export const findTokenTreasuryPDA = (farm: PublicKey, feeMint: PublicKey) => {
+  return PublicKey.findProgramAddress(
+    [Buffer.from('token_treasury'), farm.toBytes(), feeMint.toBytes()],
+    GEM_FARM_PROG_ID
+  );
+};
@@ -81,19 +81,36 @@ describe.only('gem bank', () => {
     );
   });
 
-  it('updates bank manager', async () => {
-    const newManager = Keypair.generate();
+  it('proposes and accepts a new bank manager', async () => {
+    const newManager = await nw.createFundedWallet(LAMPORTS_PER_SOL);
     await gb.updateBankManager(
       bank.publicKey,
       bankManager,
       newManager.publicKey
     );
 
-    const bankAcc = await gb.fetchBankAcc(bank.publicKey);
+    //only proposed - the existing manager stays in charge until accepted
+    let bankAcc = await gb.fetchBankAcc(bank.publicKey);
+    assert.equal(
+      bankAcc.bankManager.toBase58(),
+      bankManager.publicKey.toBase58()
+    );
+    assert.equal(
+      bankAcc.pendingBankManager.toBase58(),
+      newManager.publicKey.toBase58()
+    );
+
+    await gb.acceptBankManager(bank.publicKey, newManager);
+
+    bankAcc = await gb.fetchBankAcc(bank.publicKey);
     assert.equal(
       bankAcc.bankManager.toBase58(),
       newManager.publicKey.toBase58()
     );
+    assert.equal(
+      bankAcc.pendingBankManager.toBase58(),
+      PublicKey.default.toBase58()
+    );
 
     //reset back
     await gb.updateBankManager(
@@ -101,6 +118,32 @@ describe.only('gem bank', () => {
       newManager,
       bankManager.publicKey
     );
+    await gb.acceptBankManager(bank.publicKey, bankManager);
+  });
+
+  it('cancels a bank manager transfer', async () => {
+    const newManager = Keypair.generate();
+    await gb.updateBankManager(
+      bank.publicKey,
+      bankManager,
+      newManager.publicKey
+    );
+    await gb.cancelBankManagerTransfer(bank.publicKey, bankManager);
+
+    const bankAcc = await gb.fetchBankAcc(bank.publicKey);
+    assert.equal(
+      bankAcc.bankManager.toBase58(),
+      bankManager.publicKey.toBase58()
+    );
+    assert.equal(
+      bankAcc.pendingBankManager.toBase58(),
+      PublicKey.default.toBase58()
+    );
+
+    //nothing left to accept
+    await expect(
+      gb.acceptBankManager(bank.publicKey, newManager)
+    ).to.be.rejectedWith('NotPendingOwner');
   });
 
   it('FAILS to update bank manager w/ wrong existing manager', async () => {
@@ -110,7 +153,22 @@ describe.only('gem bank', () => {
     ).to.be.rejectedWith('ConstraintHasOne');
   });
 
-  it('updates vault owner', async () => {
+  it('FAILS to accept bank manager w/o being proposed', async () => {
+    const newManager = Keypair.generate();
+    await gb.updateBankManager(
+      bank.publicKey,
+      bankManager,
+      newManager.publicKey
+    );
+
+    await expect(
+      gb.acceptBankManager(bank.publicKey, randomWallet)
+    ).to.be.rejectedWith('NotPendingOwner');
+
+    await gb.cancelBankManagerTransfer(bank.publicKey, bankManager);
+  });
+
+  it('proposes and accepts a new vault owner', async () => {
     await gb.updateVaultOwner(
       bank.publicKey,
       vault,
@@ -118,8 +176,44 @@ describe.only('gem bank', () => {
       vaultOwner.publicKey
     );
 
+    //only proposed - the creator still owns the vault until accepted
+    let vaultAcc = await gb.fetchVaultAcc(vault);
+    assert.equal(vaultAcc.owner.toBase58(), vaultCreator.publicKey.toBase58());
+    assert.equal(
+      vaultAcc.pendingOwner.toBase58(),
+      vaultOwner.publicKey.toBase58()
+    );
+
+    await gb.acceptVaultOwner(bank.publicKey, vault, vaultOwner);
+
+    vaultAcc = await gb.fetchVaultAcc(vault);
+    assert.equal(vaultAcc.owner.toBase58(), vaultOwner.publicKey.toBase58());
+    assert.equal(
+      vaultAcc.pendingOwner.toBase58(),
+      PublicKey.default.toBase58()
+    );
+  });
+
+  it('cancels a vault owner transfer', async () => {
+    await gb.updateVaultOwner(
+      bank.publicKey,
+      vault,
+      vaultOwner,
+      randomWallet.publicKey
+    );
+    await gb.cancelVaultOwnerTransfer(vault, vaultOwner);
+
     const vaultAcc = await gb.fetchVaultAcc(vault);
     assert.equal(vaultAcc.owner.toBase58(), vaultOwner.publicKey.toBase58());
+    assert.equal(
+      vaultAcc.pendingOwner.toBase58(),
+      PublicKey.default.toBase58()
+    );
+
+    //nothing left to accept
+    await expect(
+      gb.acceptVaultOwner(bank.publicKey, vault, randomWallet)
+    ).to.be.rejectedWith('NotPendingOwner');
   });
 
   it('FAILS to update vault owner w/ wrong existing owner', async () => {
@@ -133,6 +227,21 @@ describe.only('gem bank', () => {
     ).to.be.rejectedWith('ConstraintHasOne');
   });
 
+  it('FAILS to accept vault owner w/o being proposed', async () => {
+    await gb.updateVaultOwner(
+      bank.publicKey,
+      vault,
+      vaultOwner,
+      vaultCreator.publicKey
+    );
+
+    await expect(
+      gb.acceptVaultOwner(bank.publicKey, vault, randomWallet)
+    ).to.be.rejectedWith('NotPendingOwner');
+
+    await gb.cancelVaultOwnerTransfer(vault, vaultOwner);
+  });
+
   // --------------------------------------- gem boxes
 
   describe('gem operations', () => {
@@ -1,9 +1,12 @@
 import { Keypair, LAMPORTS_PER_SOL, PublicKey } from '@solana/web3.js';
 import {
   FarmConfig,
+  feeAccount,
+  FeeTable,
   findFarmerPDA,
   findVaultPDA,
   FixedRateConfig,
+  FundingLimits,
   GemFarmClient,
   ITokenData,
   MaxCounts,
@@ -13,6 +16,7 @@ import {
   RewardType,
   stringifyPKsAndBNs,
   toBN,
+  TokenFeeAccs,
   VariableRateConfig,
   WhitelistType,
 } from '../../src';
@@ -20,6 +24,8 @@ import * as anchor from '@project-serum/anchor';
 import { BN } from '@project-serum/anchor';
 import { Token } from '@solana/spl-token';
 import { assert } from 'chai';
+import fs from 'fs';
+import path from 'path';
 
 // --------------------------------------- configs
 
@@ -59,6 +65,29 @@ export const defaultFixedConfig = <FixedRateConfig>{
   durationSec: new BN(100),
 };
 
+//same fees the program used to hard-code
+export const defaultFeeTable = <FeeTable>{
+  initFarmLamp: new BN(2_500_000_000),
+  initFarmerLamp: new BN(10_000_000),
+  stakeLamp: new BN(2_000_000),
+  unstakeLamp: new BN(1_000_000),
+  flashDepositLamp: new BN(1_000_000),
+  flashWithdrawLamp: new BN(1_000_000),
+  instantUnstakeLamp: new BN(1_000_000),
+};
+
+//admin of the protocol config preloaded into the test validator (Anchor.toml)
+export const protocolAdmin = Keypair.fromSecretKey(
+  Uint8Array.from(
+    JSON.parse(
+      fs.readFileSync(
+        path.join(__dirname, '../artifacts/protocol-admin.json'),
+        'utf-8'
+      )
+    )
+  )
+);
+
 // --------------------------------------- tester class
 
 export class GemFarmTester extends GemFarmClient {
@@ -109,6 +138,8 @@ export class GemFarmTester extends GemFarmClient {
     gem2PerGemRarity: number = 1,
     reward?: string
   ) {
+    await this.prepProtocolConfig();
+
     reward = Math.random() < 0.5 ? 'rewardA' : 'rewardB';
     console.log('running tests for', reward);
 
@@ -153,6 +184,20 @@ export class GemFarmTester extends GemFarmClient {
     this.gem2PerGemRarity = gem2PerGemRarity;
   }
 
+  //every fee-charging ix needs the config, the test validator has one preloaded.
+  //Anywhere else (provider = upgrade authority) it gets initialized here
+  async prepProtocolConfig() {
+    try {
+      await this.fetchProtocolConfigAcc();
+    } catch (e) {
+      await this.initProtocolConfig(
+        this.nw.wallet.payer,
+        feeAccount,
+        defaultFeeTable
+      );
+    }
+  }
+
   randomInt(min: number, max: number) {
     // min and max included
     return Math.floor(Math.random() * (max - min + 1) + min);
@@ -211,19 +256,38 @@ export class GemFarmTester extends GemFarmClient {
   }
 
   async callUpdateFarm(
-    farmConfig?: FarmConfig,
     newManager?: PublicKey,
-    maxCounts?: MaxCounts
+    crankTipBps?: number,
+    configDelaySec?: Numerical
   ) {
     return this.updateFarm(
       this.farm.publicKey,
       this.farmManager,
-      farmConfig,
       newManager,
+      crankTipBps,
+      configDelaySec !== undefined ? toBN(configDelaySec) : null
+    );
+  }
+
+  async callProposeFarmConfig(farmConfig?: FarmConfig, maxCounts?: MaxCounts) {
+    return this.proposeFarmConfig(
+      this.farm.publicKey,
+      this.farmManager,
+      farmConfig,
       maxCounts
     );
   }
 
+  async callApplyFarmConfig() {
+    return this.applyFarmConfig(this.farm.publicKey);
+  }
+
+  //test farms have no config delay, so a proposal can be applied right away
+  async callUpdateFarmConfig(farmConfig?: FarmConfig, maxCounts?: MaxCounts) {
+    await this.callProposeFarmConfig(farmConfig, maxCounts);
+    return this.callApplyFarmConfig();
+  }
+
   async callPayout(destination: PublicKey, lamports: Numerical) {
     return this.payoutFromTreasury(
       this.farm.publicKey,
@@ -259,12 +323,12 @@ export class GemFarmTester extends GemFarmClient {
     return this.initFarmer(this.farm.publicKey, identity, identity);
   }
 
-  async callStake(identity: Keypair) {
-    return this.stake(this.farm.publicKey, identity);
+  async callStake(identity: Keypair, tokenFee?: TokenFeeAccs) {
+    return this.stake(this.farm.publicKey, identity, tokenFee);
   }
 
-  async callUnstake(identity: Keypair) {
-    return this.unstake(this.farm.publicKey, identity);
+  async callUnstake(identity: Keypair, tokenFee?: TokenFeeAccs) {
+    return this.unstake(this.farm.publicKey, identity, false, tokenFee);
   }
 
   async callDeposit(gems: Numerical, identity: Keypair) {
@@ -355,11 +419,12 @@ export class GemFarmTester extends GemFarmClient {
 
   // ----------------- funder
 
-  async callAuthorize() {
+  async callAuthorize(limits?: FundingLimits) {
     return this.authorizeFunder(
       this.farm.publicKey,
       this.farmManager,
-      this.funder.publicKey
+      this.funder.publicKey,
+      limits
     );
   }
 
@@ -67,4 +67,59 @@ describe('farmer lifecycle (unstaked -> staked -> cooldown)', () => {
     assert(farmerAcc.gemsStaked.eq(new BN(0)));
     assert(farmerAcc.rarityPointsStaked.eq(new BN(0)));
   });
+
+  it('charges the token fees on stake and unstake', async () => {
+    const feeToken = await gf.nw.createMintAndFundATA(
+      gf.farmer1Identity.publicKey,
+      new BN(1000)
+    );
+    const { tokenTreasury } = await gf.initTokenTreasury(
+      gf.farm.publicKey,
+      feeToken.tokenMint,
+      gf.farmManager
+    );
+    await gf.proposeFarmConfig(gf.farm.publicKey, gf.farmManager, null, null, {
+      feeMint: feeToken.tokenMint,
+      stakeFee: new BN(100),
+      unstakeFee: new BN(50),
+    });
+    await gf.callApplyFarmConfig();
+
+    const tokenFee = {
+      feeMint: feeToken.tokenMint,
+      feeSource: feeToken.tokenAcc,
+    };
+    const verifyFees = async (treasury: number, source: number) => {
+      const treasuryAcc = await gf.fetchTokenAcc(
+        feeToken.tokenMint,
+        tokenTreasury
+      );
+      assert(treasuryAcc.amount.eq(new BN(treasury)));
+      const sourceAcc = await gf.fetchTokenAcc(
+        feeToken.tokenMint,
+        feeToken.tokenAcc
+      );
+      assert(sourceAcc.amount.eq(new BN(source)));
+    };
+
+    await gf.callDeposit(gf.gem1Amount, gf.farmer1Identity);
+
+    //fee accounts are required once the farm charges a fee
+    await expect(gf.callStake(gf.farmer1Identity)).to.be.rejected;
+
+    await gf.callStake(gf.farmer1Identity, tokenFee);
+    await verifyFees(100, 900);
+
+    await pause(3000);
+
+    //begin cooldown - that's when the unstake fee is charged
+    await gf.callUnstake(gf.farmer1Identity, tokenFee);
+    await verifyFees(150, 850);
+
+    await pause(3000);
+
+    //ending cooldown is free
+    await gf.callUnstake(gf.farmer1Identity);
+    await verifyFees(150, 850);
+  });
 });
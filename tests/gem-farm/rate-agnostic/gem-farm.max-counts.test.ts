@@ -131,7 +131,13 @@ describe('misc', () => {
       maxRarityPoints: 123,
     };
 
-    await gf.callUpdateFarm(undefined, undefined, maxCounts);
+    await gf.callProposeFarmConfig(undefined, maxCounts);
+
+    //only proposed - nothing changes until applied
+    farm = await gf.fetchFarm();
+    assert.equal(farm.maxCounts.maxFarmers, 0);
+
+    await gf.callApplyFarmConfig();
 
     farm = await gf.fetchFarm();
     assert.equal(farm.maxCounts.maxFarmers, 123);
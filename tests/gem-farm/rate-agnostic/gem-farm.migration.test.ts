@@ -0,0 +1,87 @@
+import { BN } from '@project-serum/anchor';
+import chai, { assert, expect } from 'chai';
+import chaiAsPromised from 'chai-as-promised';
+import { defaultFarmConfig, GemFarmTester } from '../gem-farm.tester';
+
+chai.use(chaiAsPromised);
+
+describe('migrating farmers between farms', () => {
+  let gf = new GemFarmTester(); //farm being left
+  let gf2 = new GemFarmTester(); //farm being joined
+
+  before('preps accs', async () => {
+    await gf.prepAccounts(10000);
+    await gf.callInitFarm(defaultFarmConfig);
+    await gf.callInitFarmer(gf.farmer1Identity);
+    await gf.callDeposit(gf.gem1Amount, gf.farmer1Identity);
+    await gf.callStake(gf.farmer1Identity);
+
+    await gf2.prepAccounts(10000);
+    await gf2.callInitFarm(defaultFarmConfig);
+
+    await gf.authorizeMigration(
+      gf.farm.publicKey,
+      gf.farmManager,
+      gf2.farm.publicKey,
+      gf2.farmManager
+    );
+  });
+
+  it('FAILS to migrate into the bank being left', async () => {
+    const { builder } = await gf.buildMigrateFarmer(
+      gf.farm.publicKey,
+      gf2.farm.publicKey,
+      gf.farmer1Identity.publicKey,
+      gf.farmer1Identity,
+      [gf.gem1.tokenMint]
+    );
+
+    //the vault would end up in a bank the new farm can't unlock
+    await expect(
+      builder.accounts({ toBank: gf.bank.publicKey }).rpc()
+    ).to.be.rejectedWith('ConstraintRaw');
+  });
+
+  it('migrates a staked farmer and closes the one left behind', async () => {
+    const { fromFarmer, toFarmer, vault } = await gf.migrateFarmer(
+      gf.farm.publicKey,
+      gf2.farm.publicKey,
+      gf.farmer1Identity.publicKey,
+      gf.farmer1Identity,
+      [gf.gem1.tokenMint]
+    );
+
+    //nothing was owed on the farm being left, so the old farmer is gone
+    const fromFarmerAcc = await gf.farmProgram.account.farmer.fetchNullable(
+      fromFarmer
+    );
+    assert.isNull(fromFarmerAcc);
+
+    const fromFarmAcc = await gf.fetchFarm();
+    assert(fromFarmAcc.farmerCount.eq(new BN(0)));
+    assert(fromFarmAcc.stakedFarmerCount.eq(new BN(0)));
+    assert(fromFarmAcc.gemsStaked.eq(new BN(0)));
+
+    //staked on the new farm, with the same (still locked) vault
+    const toFarmerAcc = await gf2.fetchFarmerAcc(toFarmer);
+    assert.equal(gf2.parseFarmerState(toFarmerAcc), 'staked');
+    assert(toFarmerAcc.gemsStaked.eq(gf.gem1Amount));
+    assert.equal(toFarmerAcc.vault.toBase58(), vault.toBase58());
+
+    const vaultAcc = await gf2.fetchVaultAcc(vault);
+    assert.equal(vaultAcc.bank.toBase58(), gf2.bank.publicKey.toBase58());
+    assert.isTrue(vaultAcc.locked);
+  });
+
+  it('unstakes from the farm joined, vault and all', async () => {
+    //the vault is still at the address derived from the bank it was created in
+    const { farmer, vault } = await gf2.unstake(
+      gf2.farm.publicKey,
+      gf.farmer1Identity
+    );
+
+    const farmerAcc = await gf2.fetchFarmerAcc(farmer);
+    assert.equal(farmerAcc.vault.toBase58(), vault.toBase58());
+    assert.notEqual(gf2.parseFarmerState(farmerAcc), 'staked');
+  });
+});
@@ -59,7 +59,7 @@ describe('misc', () => {
   });
 
   it('updates the farm', async () => {
-    await gf.callUpdateFarm(updatedFarmConfig);
+    await gf.callUpdateFarmConfig(updatedFarmConfig);
 
     const farmAcc = await gf.fetchFarm();
     assert.equal(
@@ -6,7 +6,12 @@ import {
   GemFarmTester,
 } from '../gem-farm.tester';
 import { BN } from '@project-serum/anchor';
-import { pause, VariableRateConfig } from '../../../src';
+import {
+  noFundingLimits,
+  pause,
+  RewardType,
+  VariableRateConfig,
+} from '../../../src';
 
 chai.use(chaiAsPromised);
 
@@ -261,4 +266,53 @@ describe('funding (variable rate)', () => {
     await gf.verifyFunderAccContains(0);
     await gf.verifyPotContains(pot, 20000);
   });
+
+  // --------------------------------------- funding limits
+
+  it('funds up to the cap', async () => {
+    await gf.callAuthorize({
+      ...noFundingLimits,
+      capA: new BN(10000),
+      capB: new BN(10000),
+    });
+
+    await gf.callFundReward(defaultVariableConfig);
+    await gf.verifyFunds(10000, 0, 0);
+
+    //cap used up - rejected before any tokens move
+    await expect(gf.callFundReward(defaultVariableConfig)).to.be.rejectedWith(
+      'FundingAllowanceExceeded'
+    );
+  });
+
+  it('FAILS to fund once authorization expired', async () => {
+    await gf.callAuthorize({
+      ...noFundingLimits,
+      expiryTs: new BN(1), //long gone
+    });
+
+    await expect(gf.callFundReward(defaultVariableConfig)).to.be.rejectedWith(
+      'FundingNotAllowed'
+    );
+  });
+
+  it('FAILS to fund a reward type not authorized', async () => {
+    //farm's rewards are variable rate
+    await gf.callAuthorize({
+      ...noFundingLimits,
+      rewardType: RewardType.Fixed,
+    });
+
+    await expect(gf.callFundReward(defaultVariableConfig)).to.be.rejectedWith(
+      'FundingNotAllowed'
+    );
+
+    //re-authorizing replaces the limits
+    await gf.callAuthorize({
+      ...noFundingLimits,
+      rewardType: RewardType.Variable,
+    });
+    await gf.callFundReward(defaultVariableConfig);
+    await gf.verifyFunds(10000, 0, 0);
+  });
 });